@@ -12,13 +12,13 @@ SEXP get_namespace(const char* namesp) {
 }
 
 /* Call grid::textGrob() */
-SEXP text_grob(SEXP label, SEXP x, SEXP y, SEXP hjust, SEXP vjust, SEXP gp) {
+SEXP text_grob(SEXP label, SEXP x, SEXP y, SEXP hjust, SEXP vjust, SEXP rot, SEXP gp) {
   SEXP out, grid, fun, call, s;
   
   PROTECT(grid = get_namespace("grid"));
   PROTECT(fun = findFun(install("textGrob"), grid));
   
-  PROTECT(call = allocVector(LANGSXP, 7)); 
+  PROTECT(call = allocVector(LANGSXP, 8)); 
   SETCAR(call, fun);  
   
   s = CDR(call);
@@ -41,6 +41,10 @@ SEXP text_grob(SEXP label, SEXP x, SEXP y, SEXP hjust, SEXP vjust, SEXP gp) {
   SETCAR(s, vjust);
   SET_TAG(s, install("vjust"));
 
+  s = CDR(s);
+  SETCAR(s, rot);
+  SET_TAG(s, install("rot"));
+
   s = CDR(s);
   SETCAR(s, gp);
   SET_TAG(s, install("gp"));
@@ -123,6 +127,263 @@ SEXP lines_grob(SEXP x, SEXP y, SEXP gp) {
   return out;
 }
 
+/* Call grid::polygonGrob() */
+SEXP polygon_grob(SEXP x, SEXP y, SEXP gp) {
+  SEXP out, grid, fun, call, s;
+  
+  PROTECT(grid = get_namespace("grid"));
+  PROTECT(fun = findFun(install("polygonGrob"), grid));
+  
+  PROTECT(call = allocVector(LANGSXP, 4)); 
+  SETCAR(call, fun);  
+  
+  s = CDR(call);
+  SETCAR(s, x);
+  SET_TAG(s, install("x"));
+  
+  s = CDR(s);
+  SETCAR(s, y);
+  SET_TAG(s, install("y"));
+  
+  s = CDR(s);
+  SETCAR(s, gp);
+  SET_TAG(s, install("gp"));
+  
+  out = eval(call, R_GlobalEnv);
+  
+  UNPROTECT(3);
+  return out;
+}
+
+
+/* Call sinab:::image_grob(). All arguments other than path, format,
+ * opacity, and rot are numeric values in inches.
+ */
+SEXP image_grob(SEXP path, SEXP format, SEXP x, SEXP y, SEXP width, SEXP height,
+                SEXP clip_x, SEXP clip_y, SEXP clip_width, SEXP clip_height,
+                SEXP opacity, SEXP rot) {
+  SEXP out, sinab, fun, call, s;
+  
+  PROTECT(sinab = get_namespace("sinab"));
+  PROTECT(fun = findFun(install("image_grob"), sinab));
+  
+  PROTECT(call = allocVector(LANGSXP, 13)); 
+  SETCAR(call, fun);  
+  
+  s = CDR(call);
+  SETCAR(s, path);
+  SET_TAG(s, install("path"));
+  
+  s = CDR(s);
+  SETCAR(s, format);
+  SET_TAG(s, install("format"));
+  
+  s = CDR(s);
+  SETCAR(s, x);
+  SET_TAG(s, install("x"));
+  
+  s = CDR(s);
+  SETCAR(s, y);
+  SET_TAG(s, install("y"));
+  
+  s = CDR(s);
+  SETCAR(s, width);
+  SET_TAG(s, install("width"));
+  
+  s = CDR(s);
+  SETCAR(s, height);
+  SET_TAG(s, install("height"));
+  
+  s = CDR(s);
+  SETCAR(s, clip_x);
+  SET_TAG(s, install("clip_x"));
+  
+  s = CDR(s);
+  SETCAR(s, clip_y);
+  SET_TAG(s, install("clip_y"));
+  
+  s = CDR(s);
+  SETCAR(s, clip_width);
+  SET_TAG(s, install("clip_width"));
+  
+  s = CDR(s);
+  SETCAR(s, clip_height);
+  SET_TAG(s, install("clip_height"));
+  
+  s = CDR(s);
+  SETCAR(s, opacity);
+  SET_TAG(s, install("opacity"));
+  
+  s = CDR(s);
+  SETCAR(s, rot);
+  SET_TAG(s, install("rot"));
+  
+  out = eval(call, R_GlobalEnv);
+  
+  UNPROTECT(3);
+  return out;
+}
+
+
+/* Call sinab:::clip_grob(). `children` is a gList; all other arguments
+ * are numeric values in inches. `path_x` and `path_y` may be NULL.
+ */
+SEXP clip_grob(SEXP children, SEXP x, SEXP y, SEXP width, SEXP height,
+               SEXP path_x, SEXP path_y) {
+  SEXP out, sinab, fun, call, s;
+  
+  PROTECT(sinab = get_namespace("sinab"));
+  PROTECT(fun = findFun(install("clip_grob"), sinab));
+  
+  PROTECT(call = allocVector(LANGSXP, 8)); 
+  SETCAR(call, fun);  
+  
+  s = CDR(call);
+  SETCAR(s, children);
+  SET_TAG(s, install("children"));
+  
+  s = CDR(s);
+  SETCAR(s, x);
+  SET_TAG(s, install("x"));
+  
+  s = CDR(s);
+  SETCAR(s, y);
+  SET_TAG(s, install("y"));
+  
+  s = CDR(s);
+  SETCAR(s, width);
+  SET_TAG(s, install("width"));
+  
+  s = CDR(s);
+  SETCAR(s, height);
+  SET_TAG(s, install("height"));
+  
+  s = CDR(s);
+  SETCAR(s, path_x);
+  SET_TAG(s, install("path_x"));
+  
+  s = CDR(s);
+  SETCAR(s, path_y);
+  SET_TAG(s, install("path_y"));
+  
+  out = eval(call, R_GlobalEnv);
+  
+  UNPROTECT(3);
+  return out;
+}
+
+/* Call sinab:::linear_gradient_grob(). `colours` is a character vector;
+ * all other arguments are numeric values in inches.
+ */
+SEXP linear_gradient_grob(SEXP x, SEXP y, SEXP width, SEXP height, SEXP x1, SEXP y1, SEXP x2, SEXP y2, SEXP colours, SEXP stops) {
+  SEXP out, sinab, fun, call, s;
+  
+  PROTECT(sinab = get_namespace("sinab"));
+  PROTECT(fun = findFun(install("linear_gradient_grob"), sinab));
+  
+  PROTECT(call = allocVector(LANGSXP, 11)); 
+  SETCAR(call, fun);  
+  
+  s = CDR(call);
+  SETCAR(s, x);
+  SET_TAG(s, install("x"));
+  
+  s = CDR(s);
+  SETCAR(s, y);
+  SET_TAG(s, install("y"));
+  
+  s = CDR(s);
+  SETCAR(s, width);
+  SET_TAG(s, install("width"));
+  
+  s = CDR(s);
+  SETCAR(s, height);
+  SET_TAG(s, install("height"));
+  
+  s = CDR(s);
+  SETCAR(s, x1);
+  SET_TAG(s, install("x1"));
+  
+  s = CDR(s);
+  SETCAR(s, y1);
+  SET_TAG(s, install("y1"));
+  
+  s = CDR(s);
+  SETCAR(s, x2);
+  SET_TAG(s, install("x2"));
+  
+  s = CDR(s);
+  SETCAR(s, y2);
+  SET_TAG(s, install("y2"));
+  
+  s = CDR(s);
+  SETCAR(s, colours);
+  SET_TAG(s, install("colours"));
+  
+  s = CDR(s);
+  SETCAR(s, stops);
+  SET_TAG(s, install("stops"));
+  
+  out = eval(call, R_GlobalEnv);
+  
+  UNPROTECT(3);
+  return out;
+}
+
+/* Call sinab:::radial_gradient_grob(). `colours` is a character vector;
+ * all other arguments are numeric values in inches.
+ */
+SEXP radial_gradient_grob(SEXP x, SEXP y, SEXP width, SEXP height, SEXP cx, SEXP cy, SEXP r, SEXP colours, SEXP stops) {
+  SEXP out, sinab, fun, call, s;
+  
+  PROTECT(sinab = get_namespace("sinab"));
+  PROTECT(fun = findFun(install("radial_gradient_grob"), sinab));
+  
+  PROTECT(call = allocVector(LANGSXP, 10)); 
+  SETCAR(call, fun);  
+  
+  s = CDR(call);
+  SETCAR(s, x);
+  SET_TAG(s, install("x"));
+  
+  s = CDR(s);
+  SETCAR(s, y);
+  SET_TAG(s, install("y"));
+  
+  s = CDR(s);
+  SETCAR(s, width);
+  SET_TAG(s, install("width"));
+  
+  s = CDR(s);
+  SETCAR(s, height);
+  SET_TAG(s, install("height"));
+  
+  s = CDR(s);
+  SETCAR(s, cx);
+  SET_TAG(s, install("cx"));
+  
+  s = CDR(s);
+  SETCAR(s, cy);
+  SET_TAG(s, install("cy"));
+  
+  s = CDR(s);
+  SETCAR(s, r);
+  SET_TAG(s, install("r"));
+  
+  s = CDR(s);
+  SETCAR(s, colours);
+  SET_TAG(s, install("colours"));
+  
+  s = CDR(s);
+  SETCAR(s, stops);
+  SET_TAG(s, install("stops"));
+  
+  out = eval(call, R_GlobalEnv);
+  
+  UNPROTECT(3);
+  return out;
+}
+
 
 /* Call grid::unit(x, "inches") */
 SEXP unit_in(SEXP x) {
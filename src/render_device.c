@@ -1,4 +1,5 @@
 #include "renderer.h"
+#include <math.h>
 
 /* Create a grid render device. Must be deleted with rdev_release().*/
 RenderDevice* rdev_new(double y0) {
@@ -18,6 +19,11 @@ RenderDevice* rdev_new(double y0) {
   rdev->bb_xmin = 0;
   rdev->bb_xmax = 0;
   rdev->bb_set = false;
+
+  /* no clipping initially */
+  rdev->clips = NULL;
+  rdev->clip_depth = 0;
+  rdev->clip_capacity = 0;
   
   return rdev;
 }
@@ -28,6 +34,15 @@ SEXP rdev_release(RenderDevice* rdev) {
    * of the list, so we copy the relevant part into a new list.
    */
   SEXP grobs_old, grobs_new, cl;
+
+  /* close any clip rectangles that are still open */
+  while (rdev->clip_depth > 0) {
+    rdev_pop_clip(rdev);
+  }
+  if (rdev->clips) {
+    Free(rdev->clips);
+  }
+
   grobs_old = rdev->grobs;
   grobs_new = PROTECT(allocVector(VECSXP, rdev->size));
   
@@ -104,8 +119,9 @@ void rdev_add_SEXP(RenderDevice* rdev, SEXP s) {
   rdev->size += 1;
 }
 
-void rdev_draw_text(RenderDevice* rdev, const char* label, double x, double y, const GContext *gc) {
-  SEXP slabel, sx, sy, sxu, syu, hjust, vjust, gp, grob;
+// rot: rotation in degrees, counterclockwise around the anchor point
+void rdev_draw_text(RenderDevice* rdev, const char* label, double x, double y, double rot, const GContext *gc) {
+  SEXP slabel, sx, sy, sxu, syu, hjust, vjust, srot, gp, grob;
   
   PROTECT(slabel = mkString(label));
   PROTECT(sx = ScalarReal(x));
@@ -114,13 +130,14 @@ void rdev_draw_text(RenderDevice* rdev, const char* label, double x, double y, c
   PROTECT(syu = unit_in(sy));
   PROTECT(hjust = ScalarReal(0));
   PROTECT(vjust = ScalarReal(0));
+  PROTECT(srot = ScalarReal(rot));
   PROTECT(gp = gpar_gcontext(gc));
   
-  PROTECT(grob = text_grob(slabel, sxu, syu, hjust, vjust, gp));
+  PROTECT(grob = text_grob(slabel, sxu, syu, hjust, vjust, srot, gp));
   
   rdev_add_SEXP(rdev, grob);
   
-  UNPROTECT(9);
+  UNPROTECT(10);
 }
 
 // x, y: top left corner
@@ -173,6 +190,231 @@ void rdev_draw_line(RenderDevice* rdev, const double *x, const double *y, unsign
   UNPROTECT(6);
 }
 
+// n: number of vertices specified by *x, *y; the polygon is closed automatically
+void rdev_draw_polygon(RenderDevice* rdev, const double *x, const double *y, unsigned int n, const GContext *gc) {
+  SEXP sx, sy, sxu, syu, gp, grob;
+  
+  PROTECT(sx = allocVector(REALSXP, n));
+  PROTECT(sy = allocVector(REALSXP, n));
+  
+  // copy coordinates into R vectors, inverting y coordinate system
+  double y0 = rdev->y0;
+  double* px = REAL(sx);
+  double* py = REAL(sy);
+  for (int i = 0; i < n; ++i) {
+    px[i] = x[i];
+    py[i] = y0 - y[i];
+  }
+  
+  PROTECT(sxu = unit_in(sx));
+  PROTECT(syu = unit_in(sy));
+  PROTECT(gp = gpar_gcontext(gc));
+    
+  PROTECT(grob = polygon_grob(sxu, syu, gp));
+    
+  rdev_add_SEXP(rdev, grob);
+    
+  UNPROTECT(6);
+}
+
+
+/* Copy n colors and stop offsets of a gradient into R vectors of length n. */
+static void copy_gradient_stops(const char **colors, const double *stops, unsigned int n,
+                                SEXP scolors, SEXP sstops) {
+  double* pstops = REAL(sstops);
+  for (int i = 0; i < n; ++i) {
+    SET_STRING_ELT(scolors, i, mkChar(colors[i]));
+    pstops[i] = stops[i];
+  }
+}
+
+// x, y: top left corner of the filled rectangle; x1, y1, x2, y2: start and end
+// of the gradient line; colors, stops: n color stops
+void rdev_draw_linear_gradient(RenderDevice* rdev, double x, double y, double width, double height,
+                               double x1, double y1, double x2, double y2,
+                               const char **colors, const double *stops, unsigned int n) {
+  SEXP scolors, sstops, sx, sy, sw, sh, sx1, sy1, sx2, sy2, grob;
+  
+  PROTECT(scolors = allocVector(STRSXP, n));
+  PROTECT(sstops = allocVector(REALSXP, n));
+  copy_gradient_stops(colors, stops, n, scolors, sstops);
+  
+  /* invert y coordinate system */
+  PROTECT(sx = ScalarReal(x));
+  PROTECT(sy = ScalarReal(rdev->y0 - y));
+  PROTECT(sw = ScalarReal(width));
+  PROTECT(sh = ScalarReal(height));
+  PROTECT(sx1 = ScalarReal(x1));
+  PROTECT(sy1 = ScalarReal(rdev->y0 - y1));
+  PROTECT(sx2 = ScalarReal(x2));
+  PROTECT(sy2 = ScalarReal(rdev->y0 - y2));
+  
+  PROTECT(grob = linear_gradient_grob(sx, sy, sw, sh, sx1, sy1, sx2, sy2, scolors, sstops));
+  
+  rdev_add_SEXP(rdev, grob);
+  
+  UNPROTECT(11);
+}
+
+// x, y: top left corner of the filled rectangle; cx, cy, r: center and radius
+// of the gradient circle; colors, stops: n color stops
+void rdev_draw_radial_gradient(RenderDevice* rdev, double x, double y, double width, double height,
+                               double cx, double cy, double r,
+                               const char **colors, const double *stops, unsigned int n) {
+  SEXP scolors, sstops, sx, sy, sw, sh, scx, scy, sr, grob;
+  
+  PROTECT(scolors = allocVector(STRSXP, n));
+  PROTECT(sstops = allocVector(REALSXP, n));
+  copy_gradient_stops(colors, stops, n, scolors, sstops);
+  
+  /* invert y coordinate system */
+  PROTECT(sx = ScalarReal(x));
+  PROTECT(sy = ScalarReal(rdev->y0 - y));
+  PROTECT(sw = ScalarReal(width));
+  PROTECT(sh = ScalarReal(height));
+  PROTECT(scx = ScalarReal(cx));
+  PROTECT(scy = ScalarReal(rdev->y0 - cy));
+  PROTECT(sr = ScalarReal(r));
+  
+  PROTECT(grob = radial_gradient_grob(sx, sy, sw, sh, scx, scy, sr, scolors, sstops));
+  
+  rdev_add_SEXP(rdev, grob);
+  
+  UNPROTECT(10);
+}
+
+
+// x, y: top left corner of the image; clip_x, clip_y: top left corner of the clip rectangle;
+// opacity: multiplies the alpha channel of the image;
+// rot: rotation in degrees, counterclockwise around the top left corner of the image
+void rdev_draw_image(RenderDevice* rdev, const char* path, const char* format,
+                     double x, double y, double width, double height,
+                     double clip_x, double clip_y, double clip_width, double clip_height,
+                     double opacity, double rot) {
+  SEXP spath, sformat, sx, sy, sw, sh, scx, scy, scw, sch, sopacity, srot, grob;
+  
+  PROTECT(spath = mkString(path));
+  PROTECT(sformat = mkString(format));
+  PROTECT(sx = ScalarReal(x));
+  PROTECT(sy = ScalarReal(rdev->y0 - y)); /* invert y coordinate system */
+  PROTECT(sw = ScalarReal(width));
+  PROTECT(sh = ScalarReal(height));
+  PROTECT(scx = ScalarReal(clip_x));
+  PROTECT(scy = ScalarReal(rdev->y0 - clip_y)); /* invert y coordinate system */
+  PROTECT(scw = ScalarReal(clip_width));
+  PROTECT(sch = ScalarReal(clip_height));
+  PROTECT(sopacity = ScalarReal(opacity));
+  PROTECT(srot = ScalarReal(rot));
+  
+  PROTECT(grob = image_grob(spath, sformat, sx, sy, sw, sh, scx, scy, scw, sch, sopacity, srot));
+  
+  rdev_add_SEXP(rdev, grob);
+  
+  UNPROTECT(13);
+}
+
+
+/* Clip rectangles form a stack. Grobs drawn while a clip rectangle is
+ * pushed are collected, and upon popping they are replaced by a single
+ * grob clipping them to the rectangle. Nested clip rectangles are
+ * intersected with their parents, since grid viewports with clip = "on"
+ * replace the clip region rather than intersect it. A clip rectangle
+ * may carry a clip path (e.g., for rounded corners), which is not
+ * intersected with the clip paths of its parents.
+ */
+
+// x, y: top left corner; n: number of vertices of the optional clip path
+// specified by *path_x, *path_y
+void rdev_push_clip(RenderDevice* rdev, double x, double y, double width, double height,
+                    const double *path_x, const double *path_y, unsigned int n) {
+  y = rdev->y0 - y; /* invert y coordinate system */
+  
+  if (rdev->clip_depth > 0) {
+    /* intersect with the enclosing clip rectangle */
+    ClipRect *outer = &rdev->clips[rdev->clip_depth - 1];
+    double xmax = fmin(x + width, outer->x + outer->width);
+    double ymin = fmax(y - height, outer->y - outer->height);
+    x = fmax(x, outer->x);
+    y = fmin(y, outer->y);
+    width = fmax(xmax - x, 0);
+    height = fmax(y - ymin, 0);
+  }
+  
+  if (rdev->clip_depth == rdev->clip_capacity) {
+    rdev->clip_capacity = rdev->clip_capacity ? 2*rdev->clip_capacity : 4;
+    rdev->clips = rdev->clips ? Realloc(rdev->clips, rdev->clip_capacity, ClipRect) :
+      Calloc(rdev->clip_capacity, ClipRect);
+  }
+  
+  ClipRect *clip = &rdev->clips[rdev->clip_depth];
+  clip->start = rdev->size;
+  clip->x = x;
+  clip->y = y;
+  clip->width = width;
+  clip->height = height;
+  clip->path_x = R_NilValue;
+  clip->path_y = R_NilValue;
+  
+  if (n > 0) {
+    /* copy path into R vectors, inverting y coordinate system */
+    clip->path_x = allocVector(REALSXP, n);
+    R_PreserveObject(clip->path_x);
+    clip->path_y = allocVector(REALSXP, n);
+    R_PreserveObject(clip->path_y);
+    double* px = REAL(clip->path_x);
+    double* py = REAL(clip->path_y);
+    for (int i = 0; i < n; ++i) {
+      px[i] = path_x[i];
+      py[i] = rdev->y0 - path_y[i];
+    }
+  }
+  
+  rdev->clip_depth += 1;
+}
+
+void rdev_pop_clip(RenderDevice* rdev) {
+  if (rdev->clip_depth == 0) return;
+  
+  rdev->clip_depth -= 1;
+  ClipRect *clip = &rdev->clips[rdev->clip_depth];
+  SEXP path_x = clip->path_x, path_y = clip->path_y;
+  PROTECT(path_x);
+  PROTECT(path_y);
+  if (path_x != R_NilValue) {
+    R_ReleaseObject(path_x);
+    R_ReleaseObject(path_y);
+  }
+  
+  R_xlen_t n = rdev->size - clip->start;
+  if (n == 0) {
+    UNPROTECT(2);
+    return;
+  }
+  
+  SEXP children, cl, sx, sy, sw, sh, grob;
+  
+  /* move the grobs drawn under this clip rectangle into a separate gList */
+  PROTECT(children = allocVector(VECSXP, n));
+  for (R_xlen_t i = 0; i < n; i++) {
+    SET_VECTOR_ELT(children, i, VECTOR_ELT(rdev->grobs, clip->start + i));
+    SET_VECTOR_ELT(rdev->grobs, clip->start + i, R_NilValue);
+  }
+  rdev->size = clip->start;
+  PROTECT(cl = mkString("gList"));
+  classgets(children, cl);
+  
+  PROTECT(sx = ScalarReal(clip->x));
+  PROTECT(sy = ScalarReal(clip->y));
+  PROTECT(sw = ScalarReal(clip->width));
+  PROTECT(sh = ScalarReal(clip->height));
+  
+  PROTECT(grob = clip_grob(children, sx, sy, sw, sh, path_x, path_y));
+  
+  rdev_add_SEXP(rdev, grob);
+  
+  UNPROTECT(9);
+}
+
 
 /* Bounding boxes are recorded manually, rather than automatically
  * upon drawing rectangles or lines or text, so that the client code
@@ -198,6 +440,16 @@ void rdev_record_bbox(RenderDevice* rdev, double xmin, double ymin, double xmax,
     ymax = tmp;
   }
   
+  /* clipped content does not count towards the bounding box */
+  if (rdev->clip_depth > 0) {
+    ClipRect *clip = &rdev->clips[rdev->clip_depth - 1];
+    xmin = fmax(xmin, clip->x);
+    xmax = fmin(xmax, clip->x + clip->width);
+    ymin = fmax(ymin, clip->y - clip->height);
+    ymax = fmin(ymax, clip->y);
+    if (xmin > xmax || ymin > ymax) return;
+  }
+  
   if (rdev->bb_set) {
     /* bounding box exists already, enlarge if necessary */
     if (xmin < rdev->bb_xmin) rdev->bb_xmin = xmin;
@@ -27,3 +27,22 @@ SEXP C_render_markdown(SEXP text_str, SEXP css_str, SEXP width_in, SEXP height_i
   
   return rdev_release(rdev);
 }
+
+SEXP C_content_sizes(SEXP text_str, SEXP css_str) {
+  double min_width_px = 0, max_width_px = 0;
+  
+  if (xlength(text_str) >= 1 && xlength(css_str) >= 1 ) {
+    sinab_content_sizes(
+      Rf_translateCharUTF8(STRING_ELT(text_str, 0)),
+      Rf_translateCharUTF8(STRING_ELT(css_str, 0)),
+      &min_width_px, &max_width_px
+    );
+  }
+  
+  SEXP out = PROTECT(allocVector(REALSXP, 2));
+  REAL(out)[0] = min_width_px / 96;
+  REAL(out)[1] = max_width_px / 96;
+  UNPROTECT(1);
+  
+  return out;
+}
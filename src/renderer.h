@@ -12,6 +12,18 @@ extern "C" {
 /* for bool data type */
 #include <stdbool.h>
 
+/*
+ * Clip rectangle pushed onto a render device. All grobs drawn
+ * since the push, starting at index `start`, are clipped to it.
+ */
+
+typedef struct {
+  R_xlen_t start;     /* index of the first grob drawn under this clip */
+  double x, y;        /* top left corner, in inches, y inverted */
+  double width, height;
+  SEXP path_x, path_y; /* optional clip path within the rectangle, or R_NilValue */
+} ClipRect;
+
 /* 
  * Grid renderer object.
  */
@@ -23,6 +35,8 @@ typedef struct {
   double y0;          /* y reference value, used to invert coordinate system */
   double bb_xmin, bb_ymin, bb_xmax, bb_ymax; /* bounding box */
   bool bb_set;        /* has bounding box been set at least once or not? */
+  ClipRect *clips;    /* stack of active clip rectangles */
+  int clip_depth, clip_capacity; /* current and maximum size of the clip stack */
 } RenderDevice;
 
 /* 
@@ -58,9 +72,23 @@ typedef struct {
 /* render_device.c */
 extern RenderDevice* rdev_new(double y0);
 extern SEXP rdev_release(RenderDevice*); 
-extern void rdev_draw_text(RenderDevice*, const char* label, double x, double y, const GContext *);
+extern void rdev_draw_text(RenderDevice*, const char* label, double x, double y, double rot, const GContext *);
 extern void rdev_draw_rect(RenderDevice*, double x, double y, double width, double height, const GContext *);
 extern void rdev_draw_line(RenderDevice*, const double *x, const double *y, unsigned int n, const GContext *);
+extern void rdev_draw_image(RenderDevice*, const char* path, const char* format,
+                            double x, double y, double width, double height,
+                            double clip_x, double clip_y, double clip_width, double clip_height,
+                            double opacity, double rot);
+extern void rdev_draw_polygon(RenderDevice*, const double *x, const double *y, unsigned int n, const GContext *);
+extern void rdev_draw_linear_gradient(RenderDevice*, double x, double y, double width, double height,
+                                      double x1, double y1, double x2, double y2,
+                                      const char **colors, const double *stops, unsigned int n);
+extern void rdev_draw_radial_gradient(RenderDevice*, double x, double y, double width, double height,
+                                      double cx, double cy, double r,
+                                      const char **colors, const double *stops, unsigned int n);
+extern void rdev_push_clip(RenderDevice*, double x, double y, double width, double height,
+                           const double *path_x, const double *path_y, unsigned int n);
+extern void rdev_pop_clip(RenderDevice*);
 extern void rdev_record_bbox(RenderDevice*, double xmin, double ymin, double xmax, double ymax);
 extern void rdev_string_metrics(const char* label, const GContext *,
                                 double *ascent, double *descent, double *width);
@@ -93,9 +121,14 @@ extern void gcontext_set_linewidth(GContext*, double);
 extern double gcontext_linewidth(GContext*);
 
 /* r-callbacks.c */
-extern SEXP text_grob(SEXP, SEXP, SEXP, SEXP, SEXP, SEXP);
+extern SEXP text_grob(SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP);
 extern SEXP rect_grob(SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP);
 extern SEXP lines_grob(SEXP, SEXP, SEXP);
+extern SEXP polygon_grob(SEXP, SEXP, SEXP);
+extern SEXP image_grob(SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP);
+extern SEXP clip_grob(SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP);
+extern SEXP linear_gradient_grob(SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP);
+extern SEXP radial_gradient_grob(SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP, SEXP);
 extern SEXP gpar_empty();
 extern SEXP gpar_gcontext(const GContext *);
 extern SEXP unit_in(SEXP);
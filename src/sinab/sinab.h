@@ -7,6 +7,8 @@ extern "C" {
 
   extern void sinab_test_renderer(void *render_device, const char* text, const char* css,
                                 double width_px, double height_px);
+  extern void sinab_content_sizes(const char* text, const char* css,
+                                  double *min_width_px, double *max_width_px);
     
 #ifdef __cplusplus
 }
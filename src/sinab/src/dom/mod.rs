@@ -8,7 +8,6 @@ use html5ever::{LocalName, QualName};
 use std::borrow::Cow;
 use std::fmt;
 use std::iter::successors;
-use std::cell::RefCell;
 
 //pub use self::xml::XmlError;
 
@@ -248,7 +247,7 @@ pub(crate) struct ElementData {
     pub(crate) name: QualName,
     pub(crate) attrs: Vec<Attribute>,
     //pub(crate) mathml_annotation_xml_integration_point: bool,
-    pub(crate) layout_data: RefCell<crate::layout::LayoutDataForElement>,
+    pub(crate) layout_data: crate::layout::LayoutDataForElement,
 }
 
 #[derive(Debug)]
@@ -10,9 +10,10 @@ use std::panic::UnwindSafe;
 use std::rc::Rc;
 use std::ops::{Deref, DerefMut};
 
-use crate::primitives::RGBA;
+use crate::primitives::{CssPx, Transform2D, RGBA};
 use crate::style::values::{Length, FontStyle, FontWeight, LineStyle};
 use crate::graphics_engine::font::Font;
+use crate::layout::ImageFormat;
 use crate::geom::physical::*;
 
 #[repr(C)]
@@ -131,13 +132,14 @@ impl GContextImpl {
         unsafe { gcontext_set_fontsize(self.gc_ptr, csize); }
     }
 
-    // linestyle in CSS is linetype (lty) in R
+    // linestyle in CSS is linetype (lty) in R; styles with no
+    // equivalent line type are drawn as solid lines
     pub(crate) fn set_linestyle(&mut self, style: LineStyle) {
         let ctype:c_int = match style {
-            LineStyle::None => 0,
-            LineStyle::Solid => 1,
+            LineStyle::None | LineStyle::Hidden => 0,
             LineStyle::Dashed => 2,
             LineStyle::Dotted => 3,
+            _ => 1,
         };
         unsafe { gcontext_set_linetype(self.gc_ptr, ctype); }
     }
@@ -249,41 +251,182 @@ impl DerefMut for GContext {
 pub struct C_RenderDevice { _private: [u8; 0] }
 
 extern {
-    fn rdev_draw_text(rdev_ptr: *mut C_RenderDevice, label: *const c_char, x: c_double, y: c_double, gc: *const C_GContext);
+    fn rdev_draw_text(rdev_ptr: *mut C_RenderDevice, label: *const c_char, x: c_double, y: c_double, rot: c_double, gc: *const C_GContext);
     fn rdev_draw_rect(rdev_ptr: *mut C_RenderDevice, x: c_double, y: c_double, width: c_double, height: c_double, gc: *const C_GContext);
     fn rdev_draw_line(rdev_ptr: *mut C_RenderDevice, x: *const c_double, y: *const c_double, n: c_uint, gc: *const C_GContext);
+    fn rdev_draw_polygon(rdev_ptr: *mut C_RenderDevice, x: *const c_double, y: *const c_double, n: c_uint, gc: *const C_GContext);
+    fn rdev_draw_linear_gradient(
+        rdev_ptr: *mut C_RenderDevice, x: c_double, y: c_double, width: c_double, height: c_double,
+        x1: c_double, y1: c_double, x2: c_double, y2: c_double,
+        colors: *const *const c_char, stops: *const c_double, n: c_uint
+    );
+    fn rdev_draw_radial_gradient(
+        rdev_ptr: *mut C_RenderDevice, x: c_double, y: c_double, width: c_double, height: c_double,
+        cx: c_double, cy: c_double, r: c_double,
+        colors: *const *const c_char, stops: *const c_double, n: c_uint
+    );
+    fn rdev_draw_image(
+        rdev_ptr: *mut C_RenderDevice, path: *const c_char, format: *const c_char,
+        x: c_double, y: c_double, width: c_double, height: c_double,
+        clip_x: c_double, clip_y: c_double, clip_width: c_double, clip_height: c_double,
+        opacity: c_double, rot: c_double
+    );
+
+    fn rdev_push_clip(
+        rdev_ptr: *mut C_RenderDevice, x: c_double, y: c_double, width: c_double, height: c_double,
+        path_x: *const c_double, path_y: *const c_double, n: c_uint
+    );
+    fn rdev_pop_clip(rdev_ptr: *mut C_RenderDevice);
 
     fn rdev_record_bbox(rdev_ptr: *mut C_RenderDevice, xmin: c_double, ymin: c_double, xmax: c_double, ymax: c_double);
 
     pub(super) fn rdev_string_metrics(label: *const c_char, gc: *const C_GContext, ascent: &mut c_double, descent: &mut c_double, width: &mut c_double);
 }
 
+/// The geometry of a gradient fill. Its color stops are at offsets from
+/// 0 to 1 along the gradient, and the colors of the first and last stops
+/// extend beyond it.
+pub(crate) enum Gradient {
+    /// Along the line from `start` to `end`.
+    Linear { start: Vec2<Length>, end: Vec2<Length> },
+    /// From `center` outwards to the ellipse with the given radii.
+    Radial { center: Vec2<Length>, radii: Vec2<Length> },
+}
+
 pub struct RenderDevice {
     rdev_ptr: *mut C_RenderDevice,
+    /// The opacities pushed by `push_opacity()`, each multiplied
+    /// by those pushed before.
+    opacities: Vec<f32>,
+    /// The transforms pushed by `push_transform()`, each followed
+    /// by those pushed before.
+    transforms: Vec<Transform2D<CssPx, CssPx>>,
 }
 
 impl RenderDevice {
     pub(crate) fn new(rdev_ptr: *mut C_RenderDevice) -> Self {
         Self {
-            rdev_ptr
+            rdev_ptr,
+            opacities: vec![],
+            transforms: vec![],
+        }
+    }
+
+    /// Transforms everything drawn until the matching `pop_transform()`,
+    /// in px, before the transforms pushed before.
+    ///
+    /// Shapes are transformed exactly. Text is rotated and scaled, but not
+    /// skewed. Images are drawn into the bounding box of their transformed
+    /// rectangle.
+    pub(crate) fn push_transform(&mut self, transform: &Transform2D<CssPx, CssPx>) {
+        let transform = transform.post_transform(&self.transform());
+        self.transforms.push(transform);
+    }
+
+    pub(crate) fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+
+    fn transform(&self) -> Transform2D<CssPx, CssPx> {
+        self.transforms.last().copied().unwrap_or_else(Transform2D::identity)
+    }
+
+    fn transform_point(&self, point: &Vec2<Length>) -> Vec2<Length> {
+        let point = self.transform().transform_point(euclid::point2(point.x.px, point.y.px));
+        Vec2 {
+            x: Length { px: point.x },
+            y: Length { px: point.y },
+        }
+    }
+
+    /// Whether the current transform maps rectangles to rectangles
+    /// with the same orientation.
+    fn is_axis_aligned(&self) -> bool {
+        let transform = self.transform();
+        transform.m12 == 0. && transform.m21 == 0.
+    }
+
+    /// The factor by which the current transform scales areas, as a length.
+    fn scale(&self) -> f32 {
+        self.transform().determinant().abs().sqrt()
+    }
+
+    /// The bounding box of `rect` once transformed.
+    fn transform_rect(&self, rect: &Rect<Length>) -> Rect<Length> {
+        let corners: Vec<_> = rect_corners(rect).iter().map(|corner| self.transform_point(corner)).collect();
+        let left = corners.iter().map(|corner| corner.x).fold(corners[0].x, Length::min);
+        let right = corners.iter().map(|corner| corner.x).fold(corners[0].x, Length::max);
+        let top = corners.iter().map(|corner| corner.y).fold(corners[0].y, Length::min);
+        let bottom = corners.iter().map(|corner| corner.y).fold(corners[0].y, Length::max);
+        Rect {
+            top_left: Vec2 { x: left, y: top },
+            size: Vec2 {
+                x: right - left,
+                y: bottom - top,
+            },
         }
     }
 
+    /// Makes everything drawn until the matching `pop_opacity()` partially
+    /// transparent, as a group.
+    ///
+    /// The grid backend has no group opacity: the alpha of everything drawn
+    /// is multiplied by `opacity` instead, so that overlapping parts of the
+    /// group show through each other.
+    pub(crate) fn push_opacity(&mut self, opacity: f32) {
+        let opacity = opacity * self.opacity();
+        self.opacities.push(opacity);
+    }
+
+    pub(crate) fn pop_opacity(&mut self) {
+        self.opacities.pop();
+    }
+
+    fn opacity(&self) -> f32 {
+        self.opacities.last().copied().unwrap_or(1.)
+    }
+
+    /// `color` with its alpha multiplied by the current opacity.
+    fn faded(&self, color: RGBA) -> RGBA {
+        let alpha = (color.3 as f32 * self.opacity()).round() as u8;
+        RGBA(color.0, color.1, color.2, alpha)
+    }
+
     pub(crate) fn draw_text(&mut self, label: &str, x: Length, y: Length, font: &Font, color: RGBA) {
         let clabel = CString::new(label).unwrap();
+        let origin = self.transform_point(&Vec2 { x, y });
         // divide by 96.0 to convert px to in
-        let cx = (x.px as c_double) / 96.0;
-        let cy = (y.px as c_double) / 96.0;
+        let cx = (origin.x.px as c_double) / 96.0;
+        let cy = (origin.y.px as c_double) / 96.0;
+        // counterclockwise in R, along the transformed baseline
+        let transform = self.transform();
+        let rot = -(transform.m12.atan2(transform.m11).to_degrees()) as c_double;
 
         let mut gc = font.graphics_context();
-        gc.set_color(color);
+        gc.set_color(self.faded(color));
+        let scale = self.scale();
+        if scale != 1. {
+            let size = gc.get_fontsize();
+            gc.set_fontsize(size * scale);
+        }
 
         unsafe {
-            rdev_draw_text(self.rdev_ptr, clabel.as_ptr(), cx, cy, gc.as_ptr());
+            rdev_draw_text(self.rdev_ptr, clabel.as_ptr(), cx, cy, rot, gc.as_ptr());
         }
     }
 
     pub(crate) fn draw_rect(&mut self, x: Length, y: Length, width: Length, height: Length, fill: RGBA) {
+        let rect = Rect {
+            top_left: Vec2 { x, y },
+            size: Vec2 { x: width, y: height },
+        };
+        if !self.is_axis_aligned() {
+            return self.draw_polygon(&rect_corners(&rect), fill);
+        }
+        let Rect {
+            top_left: Vec2 { x, y },
+            size: Vec2 { x: width, y: height },
+        } = self.transform_rect(&rect);
         // divide by 96.0 to convert px to in
         let cx = (x.px as c_double) / 96.0;
         let cy = (y.px as c_double) / 96.0;
@@ -292,7 +435,7 @@ impl RenderDevice {
 
         let mut gc = GContext::new();
         gc.set_color(RGBA(0, 0, 0, 0));
-        gc.set_fill(fill);
+        gc.set_fill(self.faded(fill));
 
         unsafe {
             rdev_draw_rect(self.rdev_ptr, cx, cy, cwidth, cheight,gc.as_ptr());
@@ -305,22 +448,294 @@ impl RenderDevice {
         let mut cy = Vec::<c_double>::with_capacity(n);
 
         for p in points.iter() {
+            let p = self.transform_point(p);
             // divide by 96.0 to convert px to in
             cx.push((p.x.px as c_double) / 96.0);
             cy.push((p.y.px as c_double) / 96.0);
         }
 
         let mut gc = GContext::new();
-        gc.set_color(color);
+        gc.set_color(self.faded(color));
         gc.set_linestyle(style);
-        gc.set_linewidth(width);
+        gc.set_linewidth(width * self.scale());
 
         unsafe {
             rdev_draw_line(self.rdev_ptr,cx.as_ptr(), cy.as_ptr(), n as c_uint, gc.as_ptr());
         }
     }
 
+    /// Fills the polygon with the given vertices.
+    pub(crate) fn draw_polygon(&mut self, points: &[Vec2<Length>], fill: RGBA) {
+        let points: Vec<_> = points.iter().map(|point| self.transform_point(point)).collect();
+        let (cx, cy) = points_to_in(&points);
+
+        let mut gc = GContext::new();
+        gc.set_color(RGBA(0, 0, 0, 0));
+        gc.set_fill(self.faded(fill));
+
+        unsafe {
+            rdev_draw_polygon(self.rdev_ptr, cx.as_ptr(), cy.as_ptr(), points.len() as c_uint, gc.as_ptr());
+        }
+    }
+
+    /// Fills a shadow of the shape whose outline, grown outwards by a given
+    /// distance, is returned by `outline`. The shadow is blurred by `blur`,
+    /// twice the standard deviation of a Gaussian blur. If `outer` is given,
+    /// the area between `outer` and the outline is filled instead, as for an
+    /// inset shadow.
+    ///
+    /// The grid backend has no blur filter: the blur is approximated by
+    /// layers of the shape grown or shrunk by distances spread evenly across
+    /// `-blur..blur`, each partially transparent so that they add up to
+    /// `color` where they all overlap.
+    pub(crate) fn draw_shadow(
+        &mut self,
+        outline: impl Fn(Length) -> Vec<Vec2<Length>>,
+        outer: Option<&Rect<Length>>,
+        color: RGBA,
+        blur: Length,
+    ) {
+        let layers = if blur.px > 0. { SHADOW_LAYERS } else { 1 };
+        let fill = RGBA(color.0, color.1, color.2, layer_alpha(color.3, layers));
+        for i in 0..layers {
+            let spread = blur * ((2 * i + 1) as f32 / layers as f32 - 1.);
+            let mut points = outline(spread);
+            if let Some(outer) = outer {
+                // Wind the outline the other way round to cut a hole.
+                let Vec2 { x: left, y: top } = outer.top_left;
+                let right = left + outer.size.x;
+                let bottom = top + outer.size.y;
+                let mut ring = vec![
+                    Vec2 { x: left, y: top },
+                    Vec2 { x: right, y: top },
+                    Vec2 { x: right, y: bottom },
+                    Vec2 { x: left, y: bottom },
+                    Vec2 { x: left, y: top },
+                ];
+                ring.push(points[0].clone());
+                ring.extend(points.into_iter().rev());
+                points = ring;
+            }
+            self.draw_polygon(&points, fill);
+        }
+    }
+
+    /// Draws the shadow of text drawn by `draw_text`, blurred by `blur`.
+    ///
+    /// As in `draw_shadow`, the grid backend approximates the blur,
+    /// with copies of the text spread around a circle of radius `blur / 2`.
+    pub(crate) fn draw_text_shadow(&mut self, label: &str, x: Length, y: Length, font: &Font, color: RGBA, blur: Length) {
+        if blur.px <= 0. {
+            return self.draw_text(label, x, y, font, color);
+        }
+        let fill = RGBA(color.0, color.1, color.2, layer_alpha(color.3, SHADOW_LAYERS));
+        for i in 0..SHADOW_LAYERS {
+            let angle = 2. * std::f32::consts::PI * i as f32 / SHADOW_LAYERS as f32;
+            let dx = blur * (angle.cos() / 2.);
+            let dy = blur * (angle.sin() / 2.);
+            self.draw_text(label, x + dx, y + dy, font, fill);
+        }
+    }
+
+    /// Fills `rect` with a gradient, whose color stops have increasing
+    /// offsets.
+    ///
+    /// Grid has linear and circular radial gradients since R 4.1. Elliptical
+    /// radial gradients, including circular ones transformed into ellipses,
+    /// are approximated by bands of nested ellipses.
+    pub(crate) fn draw_gradient(&mut self, rect: &Rect<Length>, gradient: &Gradient, stops: &[(f32, RGBA)]) {
+        if let Gradient::Radial { center, radii } = gradient {
+            if (radii.x - radii.y).px.abs() >= 0.01 || !self.is_conformal() {
+                return self.draw_elliptical_gradient(rect, center, radii, stops);
+            }
+        }
+
+        // Rotated or skewed gradients fill the bounding box of their
+        // transformed rectangle, clipped to the latter.
+        let aligned = self.is_axis_aligned();
+        if !aligned {
+            self.push_clip(rect, &[]);
+        }
+        // divide by 96.0 to convert px to in
+        let to_in = |l: Length| (l.px as c_double) / 96.0;
+        let ccolors: Vec<CString> = stops
+            .iter()
+            .map(|(_, color)| CString::new(self.faded(*color).to_string()).unwrap())
+            .collect();
+        let color_ptrs: Vec<*const c_char> = ccolors.iter().map(|color| color.as_ptr()).collect();
+        let offsets: Vec<c_double> = stops.iter().map(|(offset, _)| *offset as c_double).collect();
+        let rect = self.transform_rect(rect);
+        let (x, y) = (to_in(rect.top_left.x), to_in(rect.top_left.y));
+        let (width, height) = (to_in(rect.size.x), to_in(rect.size.y));
+
+        match gradient {
+            Gradient::Linear { start, end } => unsafe {
+                let (start, end) = self.transform_gradient_line(start, end);
+                rdev_draw_linear_gradient(
+                    self.rdev_ptr, x, y, width, height,
+                    to_in(start.x), to_in(start.y), to_in(end.x), to_in(end.y),
+                    color_ptrs.as_ptr(), offsets.as_ptr(), stops.len() as c_uint,
+                );
+            },
+            Gradient::Radial { center, radii } => unsafe {
+                let center = self.transform_point(center);
+                rdev_draw_radial_gradient(
+                    self.rdev_ptr, x, y, width, height,
+                    to_in(center.x), to_in(center.y), to_in(radii.x * self.scale()),
+                    color_ptrs.as_ptr(), offsets.as_ptr(), stops.len() as c_uint,
+                );
+            },
+        }
+        if !aligned {
+            self.pop_clip();
+        }
+    }
+
+    fn draw_elliptical_gradient(
+        &mut self,
+        rect: &Rect<Length>,
+        center: &Vec2<Length>,
+        radii: &Vec2<Length>,
+        stops: &[(f32, RGBA)],
+    ) {
+        self.push_clip(rect, &[]);
+        let outside = color_at(stops, 1.);
+        self.draw_rect(rect.top_left.x, rect.top_left.y, rect.size.x, rect.size.y, outside);
+        for band in (0..ELLIPSE_BANDS).rev() {
+            let scale = (band + 1) as f32 / ELLIPSE_BANDS as f32;
+            let points: Vec<_> = (0..ELLIPSE_SEGMENTS)
+                .map(|i| {
+                    let angle = 2. * std::f32::consts::PI * i as f32 / ELLIPSE_SEGMENTS as f32;
+                    Vec2 {
+                        x: center.x + radii.x * (scale * angle.cos()),
+                        y: center.y + radii.y * (scale * angle.sin()),
+                    }
+                })
+                .collect();
+            let color = color_at(stops, (band as f32 + 0.5) / ELLIPSE_BANDS as f32);
+            self.draw_polygon(&points, color);
+        }
+        self.pop_clip();
+    }
+
+    /// Whether the current transform preserves angles, and so maps circles
+    /// to circles.
+    fn is_conformal(&self) -> bool {
+        let transform = self.transform();
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        (close(transform.m11, transform.m22) && close(transform.m12, -transform.m21))
+            || (close(transform.m11, -transform.m22) && close(transform.m12, transform.m21))
+    }
+
+    /// The ends of a transformed gradient line. The lines of constant color
+    /// remain perpendicular to it, which skews and uneven scales would not
+    /// preserve if its ends were only transformed.
+    fn transform_gradient_line(&self, start: &Vec2<Length>, end: &Vec2<Length>) -> (Vec2<Length>, Vec2<Length>) {
+        let along = euclid::vec2::<f32, CssPx>((end.x - start.x).px, (end.y - start.y).px);
+        let constant = self.transform().transform_vector(euclid::vec2(-along.y, along.x));
+        let normal = euclid::vec2::<f32, CssPx>(-constant.y, constant.x);
+        let (start, end) = (self.transform_point(start), self.transform_point(end));
+        if normal.length() == 0. {
+            return (start.clone(), start);
+        }
+        let normal = normal / normal.length();
+        let distance = (end.x - start.x).px * normal.x + (end.y - start.y).px * normal.y;
+        let end = Vec2 {
+            x: start.x + Length { px: normal.x * distance },
+            y: start.y + Length { px: normal.y * distance },
+        };
+        (start, end)
+    }
+
+    /// Draws the image file at `path` into `rect`, clipped to `clip`.
+    ///
+    /// Rotated images are drawn rotated around their top left corner, and
+    /// clipped along the transformed outline of `clip`. Grid viewports can
+    /// be neither skewed nor mirrored, so images with such transforms fill
+    /// the bounding box of their transformed rectangle instead.
+    pub(crate) fn draw_image(&mut self, path: &str, format: ImageFormat, rect: &Rect<Length>, clip: &Rect<Length>) {
+        let transform = self.transform();
+        let upright = self.is_axis_aligned() && transform.m11 > 0. && transform.m22 > 0.;
+        if !upright && self.is_conformal() && transform.determinant() > 0. {
+            self.push_clip(clip, &[]);
+            let scale = self.scale();
+            let rotated = Rect {
+                top_left: self.transform_point(&rect.top_left),
+                size: Vec2 {
+                    x: rect.size.x * scale,
+                    y: rect.size.y * scale,
+                },
+            };
+            // counterclockwise in R, like text
+            let rot = -(transform.m12.atan2(transform.m11).to_degrees()) as c_double;
+            self.draw_image_in_device_space(path, format, &rotated, &rotated, rot);
+            return self.pop_clip();
+        }
+        let rect = &self.transform_rect(rect);
+        let clip = &self.transform_rect(clip);
+        self.draw_image_in_device_space(path, format, rect, clip, 0.)
+    }
+
+    fn draw_image_in_device_space(
+        &mut self,
+        path: &str,
+        format: ImageFormat,
+        rect: &Rect<Length>,
+        clip: &Rect<Length>,
+        rot: c_double,
+    ) {
+        let cpath = CString::new(path).unwrap();
+        let cformat = CString::new(match format {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpeg",
+            ImageFormat::Svg => "svg",
+        }).unwrap();
+        // divide by 96.0 to convert px to in
+        let to_in = |l: Length| (l.px as c_double) / 96.0;
+
+        unsafe {
+            rdev_draw_image(
+                self.rdev_ptr, cpath.as_ptr(), cformat.as_ptr(),
+                to_in(rect.top_left.x), to_in(rect.top_left.y), to_in(rect.size.x), to_in(rect.size.y),
+                to_in(clip.top_left.x), to_in(clip.top_left.y), to_in(clip.size.x), to_in(clip.size.y),
+                self.opacity() as c_double, rot,
+            );
+        }
+    }
+
+    /// Clips everything drawn until the matching `pop_clip()` to `clip`,
+    /// intersected with the clip rectangles pushed before. If `path` is not
+    /// empty, it further clips to the polygon with these vertices, which
+    /// must lie within `clip`.
+    pub(crate) fn push_clip(&mut self, clip: &Rect<Length>, path: &[Vec2<Length>]) {
+        // rotated or skewed rectangles clip along their transformed outline
+        let path: Vec<_> = if path.is_empty() && !self.is_axis_aligned() {
+            rect_corners(clip).iter().map(|point| self.transform_point(point)).collect()
+        } else {
+            path.iter().map(|point| self.transform_point(point)).collect()
+        };
+        let clip = &self.transform_rect(clip);
+        // divide by 96.0 to convert px to in
+        let to_in = |l: Length| (l.px as c_double) / 96.0;
+        let (path_x, path_y) = points_to_in(&path);
+
+        unsafe {
+            rdev_push_clip(
+                self.rdev_ptr,
+                to_in(clip.top_left.x), to_in(clip.top_left.y), to_in(clip.size.x), to_in(clip.size.y),
+                path_x.as_ptr(), path_y.as_ptr(), path.len() as c_uint,
+            );
+        }
+    }
+
+    pub(crate) fn pop_clip(&mut self) {
+        unsafe {
+            rdev_pop_clip(self.rdev_ptr);
+        }
+    }
+
     pub(crate) fn record_bbox(&mut self, bbox: &Rect<Length>) {
+        let bbox = &self.transform_rect(bbox);
         let xmin = (bbox.top_left.x.px as c_double) / 96.0;
         let ymin = (bbox.top_left.y.px as c_double) / 96.0;
         let xmax = xmin + (bbox.size.x.px as c_double) / 96.0;
@@ -333,5 +748,57 @@ impl RenderDevice {
 
 }
 
+/// The number of layers approximating a blurred shadow.
+const SHADOW_LAYERS: usize = 8;
+
+/// The number of bands and of polygon vertices approximating an elliptical
+/// radial gradient.
+const ELLIPSE_BANDS: usize = 32;
+const ELLIPSE_SEGMENTS: usize = 64;
+
+/// The color at `offset` along a gradient, interpolated between the color
+/// stops around it.
+pub(crate) fn color_at(stops: &[(f32, RGBA)], offset: f32) -> RGBA {
+    let after = stops.iter().position(|(stop, _)| *stop > offset);
+    let (before, after) = match after {
+        None => return stops[stops.len() - 1].1,
+        Some(0) => return stops[0].1,
+        Some(after) => (&stops[after - 1], &stops[after]),
+    };
+    let t = (offset - before.0) / (after.0 - before.0);
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    let (a, b) = (before.1, after.1);
+    RGBA(mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2), mix(a.3, b.3))
+}
+
+/// The alpha of each of `layers` overlapping layers adding up to `alpha`.
+fn layer_alpha(alpha: u8, layers: usize) -> u8 {
+    let total = alpha as f32 / 255.;
+    let layer = 1. - (1. - total).powf(1. / layers as f32);
+    (layer * 255.).round() as u8
+}
+
+/// The corners of a rectangle, clockwise from the top left.
+fn rect_corners(rect: &Rect<Length>) -> [Vec2<Length>; 4] {
+    let Vec2 { x: left, y: top } = rect.top_left;
+    let right = left + rect.size.x;
+    let bottom = top + rect.size.y;
+    [
+        Vec2 { x: left, y: top },
+        Vec2 { x: right, y: top },
+        Vec2 { x: right, y: bottom },
+        Vec2 { x: left, y: bottom },
+    ]
+}
+
+/// Converts points from px to in, as separate vectors of x and y coordinates.
+fn points_to_in(points: &[Vec2<Length>]) -> (Vec<c_double>, Vec<c_double>) {
+    // divide by 96.0 to convert px to in
+    points
+        .iter()
+        .map(|p| ((p.x.px as c_double) / 96.0, (p.y.px as c_double) / 96.0))
+        .unzip()
+}
+
 // Mark as UnwindSafe so we can catch errors with panic::catch_unwind()
 impl UnwindSafe for RenderDevice {}
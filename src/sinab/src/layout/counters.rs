@@ -0,0 +1,115 @@
+//! CSS counters, updated in document order during box construction.
+//! https://drafts.csswg.org/css-lists-3/#auto-numbering
+
+use super::*;
+
+/// The implicit counter incremented by list items.
+/// https://drafts.csswg.org/css-lists-3/#list-item-counter
+const LIST_ITEM: &str = "list-item";
+
+/// The counters in scope at the current point of the traversal.
+///
+/// A counter instantiated by an element is in scope for the element, its
+/// following siblings, and their descendants, so each instance records the
+/// nesting depth of the element that created it and is dropped once the
+/// traversal leaves the children of that element’s parent.
+#[derive(Default)]
+pub(super) struct Counters {
+    /// Innermost last.
+    instances: Vec<Counter>,
+    depth: usize,
+}
+
+struct Counter {
+    name: String,
+    value: i32,
+    depth: usize,
+}
+
+impl Counters {
+    /// Called before traversing the children and pseudo-elements of an element.
+    pub fn enter_children(&mut self) {
+        self.depth += 1
+    }
+
+    /// Called after traversing the children and pseudo-elements of an element,
+    /// ending the scope of the counters they instantiated.
+    pub fn leave_children(&mut self) {
+        let depth = self.depth;
+        self.instances.retain(|counter| counter.depth < depth);
+        self.depth -= 1
+    }
+
+    /// Applies the `counter-reset`, `counter-increment` and `counter-set`
+    /// properties of an element or pseudo-element, in that order.
+    ///
+    /// List items also increment the `list-item` counter unless
+    /// `counter-increment` mentions it. For `<li>` elements, `ordinal` is the
+    /// value that also numbers their marker, which takes into account the
+    /// `start`, `reversed` and `value` attributes.
+    pub fn update(&mut self, style: &ComputedValues, ordinal: Option<i32>) {
+        let properties = &style.content_reset;
+        for (name, value) in &properties.counter_reset.0 {
+            self.reset(name, *value)
+        }
+        let increments = &properties.counter_increment.0;
+        for (name, increment) in increments {
+            *self.innermost(name) += increment
+        }
+        if style.box_.display.is_list_item() && !increments.iter().any(|(name, _)| name == LIST_ITEM)
+        {
+            match ordinal {
+                Some(ordinal) => *self.innermost(LIST_ITEM) = ordinal,
+                None => *self.innermost(LIST_ITEM) += 1,
+            }
+        }
+        for (name, value) in &properties.counter_set.0 {
+            *self.innermost(name) = *value
+        }
+    }
+
+    /// The value of the innermost counter with this name, as used by `counter()`.
+    pub fn value(&mut self, name: &str) -> i32 {
+        *self.innermost(name)
+    }
+
+    /// The values of all counters with this name in scope, outermost first,
+    /// as used by `counters()`.
+    pub fn values(&mut self, name: &str) -> Vec<i32> {
+        self.innermost(name);
+        self.instances
+            .iter()
+            .filter(|counter| counter.name == name)
+            .map(|counter| counter.value)
+            .collect()
+    }
+
+    /// A counter reset by a preceding sibling is replaced rather than nested.
+    fn reset(&mut self, name: &str, value: i32) {
+        let depth = self.depth;
+        match self.instances.iter_mut().rev().find(|counter| counter.name == name) {
+            Some(counter) if counter.depth == depth => counter.value = value,
+            _ => self.instantiate(name, value),
+        }
+    }
+
+    /// Using a counter that is not in scope instantiates it at zero.
+    fn innermost(&mut self, name: &str) -> &mut i32 {
+        let index = match self.instances.iter().rposition(|counter| counter.name == name) {
+            Some(index) => index,
+            None => {
+                self.instantiate(name, 0);
+                self.instances.len() - 1
+            }
+        };
+        &mut self.instances[index].value
+    }
+
+    fn instantiate(&mut self, name: &str, value: i32) {
+        self.instances.push(Counter {
+            name: name.to_owned(),
+            value,
+            depth: self.depth,
+        })
+    }
+}
@@ -1,11 +1,17 @@
 use super::*;
 use crate::dom::{Document, NodeData, NodeId, ElementData};
-use crate::style::StyleSet;
-use std::cell::{RefMut};
+use crate::style::{style_for_pseudo_element, PseudoElement, StyleSet};
+use std::cell::{Cell, RefCell, RefMut};
 
 pub(super) struct Context<'a> {
     pub document: &'a Document,
     pub author_styles: &'a StyleSet,
+    /// The nesting level of quotes, incremented by `open-quote` and
+    /// decremented by `close-quote` in document order.
+    /// https://drafts.csswg.org/css-content/#quote-values
+    pub quote_depth: Cell<usize>,
+    /// https://drafts.csswg.org/css-lists-3/#auto-numbering
+    pub counters: RefCell<Counters>,
 }
 
 #[derive(Copy, Clone)]
@@ -56,6 +62,7 @@ fn traverse_children_of<'dom>(
     context: &'dom Context,
     handler: &mut impl TraversalHandler<'dom>,
 ) {
+    context.counters.borrow_mut().enter_children();
     traverse_pseudo_element(
         WhichPseudoElement::Before,
         parent_element,
@@ -87,6 +94,7 @@ fn traverse_children_of<'dom>(
         context,
         handler,
     );
+    context.counters.borrow_mut().leave_children();
 }
 
 fn traverse_element<'dom>(
@@ -103,6 +111,14 @@ fn traverse_element<'dom>(
         Some(parent_element_style),
     );
 
+    // Elements that generate no box do not affect counters.
+    // https://drafts.csswg.org/css-lists-3/#counters-without-boxes
+    if style.box_.display != Display::None {
+        let ordinal = (element_data.name.local == local_name!("li"))
+            .then(|| ordinal(context.document, element_id));
+        context.counters.borrow_mut().update(&style, ordinal);
+    }
+
     // br tags need to be handled explicitly
     match &element_data.name.local {
         &local_name!("br") => {
@@ -128,19 +144,30 @@ fn traverse_element<'dom>(
                 // <https://drafts.csswg.org/css-display-3/#valdef-display-contents>
                 context.unset_boxes_in_subtree(element_id)
             } else {
-                context.layout_data_mut(element_id).self_box = Some(LayoutBox::DisplayContents);
+                *context.layout_data(element_id).self_box.borrow_mut() = Some(LayoutBox::DisplayContents);
                 traverse_children_of(element_id, &style, context, handler)
             }
         }
-        Display::GeneratingBox(display) => handler.handle_element(
-            &style,
-            display,
-            match ReplacedContent::for_element(element_id, context) {
-                Some(replaced) => Contents::Replaced(replaced),
-                None => Contents::OfElement(element_id),
-            },
-            context.element_box_slot(element_id),
-        ),
+        Display::GeneratingBox(display) => {
+            let (display, contents) = match ReplacedContent::for_element(element_id, context) {
+                // Replaced elements are not table-internal boxes: they are inline-level
+                // content of an anonymous cell instead.
+                // https://drafts.csswg.org/css-tables-3/#table-internal-element
+                Some(replaced) => match display {
+                    DisplayGeneratingBox::LayoutInternal(_) => (
+                        DisplayGeneratingBox::OutsideInside {
+                            outside: DisplayOutside::Inline,
+                            inside: DisplayInside::Flow,
+                            list_item: false,
+                        },
+                        Contents::Replaced(replaced),
+                    ),
+                    _ => (display, Contents::Replaced(replaced)),
+                },
+                None => (display, Contents::OfElement(element_id)),
+            };
+            handler.handle_element(&style, display, contents, context.element_box_slot(element_id))
+        }
     }
 }
 
@@ -152,6 +179,9 @@ fn traverse_pseudo_element<'dom>(
     handler: &mut impl TraversalHandler<'dom>,
 ) {
     if let Some(style) = pseudo_element_style(which, element, element_style, context) {
+        if style.box_.display != Display::None {
+            context.counters.borrow_mut().update(&style, None);
+        }
         match style.box_.display {
             Display::None => context.unset_pseudo_element_box(element, which),
             Display::Contents => {
@@ -187,6 +217,7 @@ fn traverse_pseudo_element_contents<'dom>(
                 let display_inline = DisplayGeneratingBox::OutsideInside {
                     outside: DisplayOutside::Inline,
                     inside: DisplayInside::Flow,
+                    list_item: false,
                 };
                 // `display` is not inherited, so we get the initial value
                 debug_assert!(item_style.box_.display == Display::GeneratingBox(display_inline));
@@ -241,25 +272,97 @@ impl NonReplacedContents {
     }
 }
 
+/// The style of a `::before` or `::after` pseudo-element, or `None` if it
+/// does not generate a box because of `content: normal` or `content: none`.
+/// https://drafts.csswg.org/css2/generate.html#content
 fn pseudo_element_style(
-    _which: WhichPseudoElement,
-    _element: NodeId,
-    _element_style: &ComputedValues,
-    _context: &Context,
+    which: WhichPseudoElement,
+    element: NodeId,
+    element_style: &ComputedValues,
+    context: &Context,
 ) -> Option<Arc<ComputedValues>> {
-    // FIXME: run the cascade, then return None for `content: normal` or `content: none`
-    // https://drafts.csswg.org/css2/generate.html#content
-    None
+    let pseudo_element = match which {
+        WhichPseudoElement::Before => PseudoElement::Before,
+        WhichPseudoElement::After => PseudoElement::After,
+    };
+    let style = style_for_pseudo_element(
+        context.author_styles,
+        context.document,
+        element,
+        pseudo_element,
+        element_style,
+    );
+    match style.content_reset.content {
+        Content::Normal | Content::None => None,
+        Content::Items(_) => Some(style),
+    }
 }
 
 fn generate_pseudo_element_content(
-    _pseudo_element_style: &ComputedValues,
-    _element: NodeId,
-    _context: &Context,
+    pseudo_element_style: &ComputedValues,
+    element: NodeId,
+    context: &Context,
 ) -> Vec<PseudoElementContentItem> {
-    let _ = PseudoElementContentItem::Text;
-    let _ = PseudoElementContentItem::Replaced;
-    unimplemented!()
+    let items = match &pseudo_element_style.content_reset.content {
+        Content::Items(items) => items,
+        Content::Normal | Content::None => return Vec::new(),
+    };
+    let quotes = &pseudo_element_style.content_inherited.quotes;
+    let depth = &context.quote_depth;
+    let mut contents = Vec::new();
+    for item in items {
+        let text = match item {
+            ContentItem::String(string) => string.clone(),
+            ContentItem::Url(url) => {
+                // An image that fails to load is omitted.
+                if let Some(image) = ReplacedContent::from_url(url) {
+                    contents.push(PseudoElementContentItem::Replaced(image));
+                }
+                continue;
+            }
+            ContentItem::Attr(name) => context.document[element]
+                .as_element()
+                .and_then(|e| e.get_attr(&name.as_str().into()))
+                .unwrap_or("")
+                .to_owned(),
+            ContentItem::Counter(name, style) => {
+                let value = context.counters.borrow_mut().value(name);
+                style.counter_text(value).unwrap_or_default()
+            }
+            ContentItem::Counters(name, separator, style) => {
+                let values = context.counters.borrow_mut().values(name);
+                let texts: Vec<String> = values
+                    .into_iter()
+                    .map(|value| style.counter_text(value).unwrap_or_default())
+                    .collect();
+                texts.join(separator)
+            }
+            ContentItem::OpenQuote => {
+                let (open, _) = quotes.at_depth(depth.get());
+                depth.set(depth.get() + 1);
+                open.to_owned()
+            }
+            ContentItem::CloseQuote => match depth.get() {
+                0 => continue,
+                d => {
+                    depth.set(d - 1);
+                    quotes.at_depth(d - 1).1.to_owned()
+                }
+            },
+            ContentItem::NoOpenQuote => {
+                depth.set(depth.get() + 1);
+                continue;
+            }
+            ContentItem::NoCloseQuote => {
+                depth.set(depth.get().saturating_sub(1));
+                continue;
+            }
+        };
+        if !text.is_empty() {
+            contents.push(PseudoElementContentItem::Text(text))
+        }
+    }
+    contents
 }
 
 /// A `BoxSlot` is a mutable pointer to a `LayoutBox`, corresponding to the
@@ -283,6 +386,11 @@ impl<'dom> BoxSlot<'dom> {
             **slot = Some(box_)
         }
     }
+
+    /// For boxes that are not rendered.
+    pub fn unset(mut self) {
+        self.slot = None
+    }
 }
 
 impl Drop for BoxSlot<'_> {
@@ -294,59 +402,44 @@ impl Drop for BoxSlot<'_> {
 }
 
 impl Context<'_> {
-    fn layout_data_mut(&self, element_id: NodeId) -> RefMut<LayoutDataForElement> {
-        self.document[element_id]
-            .as_element()
-            .unwrap()
-            .layout_data
-            .borrow_mut()
+    fn layout_data(&self, element_id: NodeId) -> &LayoutDataForElement {
+        &self.document[element_id].as_element().unwrap().layout_data
     }
 
     fn element_box_slot(&self, element_id: NodeId) -> BoxSlot {
-        BoxSlot::new(RefMut::map(
-            self.layout_data_mut(element_id),
-            |data| &mut data.self_box,
-        ))
+        BoxSlot::new(self.layout_data(element_id).self_box.borrow_mut())
     }
 
     fn pseudo_element_box_slot(&self, element_id: NodeId, which: WhichPseudoElement) -> BoxSlot {
-        BoxSlot::new(RefMut::map(
-            self.layout_data_mut(element_id),
-            |data| {
-                let pseudos = data.pseudo_elements.get_or_insert_with(Default::default);
-                match which {
-                    WhichPseudoElement::Before => &mut pseudos.before,
-                    WhichPseudoElement::After => &mut pseudos.after,
-                }
-            },
-        ))
+        let pseudos = self.layout_data(element_id).pseudo_elements.get_or_init(Default::default);
+        BoxSlot::new(pseudos.get(which).borrow_mut())
     }
 
     fn unset_pseudo_element_box(&self, element_id: NodeId, which: WhichPseudoElement) {
-        if let Some(pseudos) = &mut self.layout_data_mut(element_id).pseudo_elements {
-            match which {
-                WhichPseudoElement::Before => pseudos.before = None,
-                WhichPseudoElement::After => pseudos.after = None,
-            }
+        if let Some(pseudos) = self.layout_data(element_id).pseudo_elements.get() {
+            *pseudos.get(which).borrow_mut() = None
         }
     }
 
-    /// Removes all layout data by setting `layout_data.pseudo_elements` and
-    /// `layout_data.self_box` to `None` in the entire subtree belonging to
+    /// Removes all layout data by setting the boxes in `layout_data.pseudo_elements`
+    /// and `layout_data.self_box` to `None` in the entire subtree belonging to
     /// `base_element`.
     ///
     /// The traversal algorithm may have problems and should be reviewed carefully.
     /// It has let to crashes in some applications.
-    fn unset_boxes_in_subtree(&self, base_element: NodeId) {
+    pub(super) fn unset_boxes_in_subtree(&self, base_element: NodeId) {
         let mut node_id = base_element;
         loop {
             let node = &self.document[node_id];
             // unset element box and descent into children
             if let Some(element_data) = node.as_element() {
-                let mut layout_data = element_data.layout_data.borrow_mut();
-                layout_data.pseudo_elements = None;
+                let layout_data = &element_data.layout_data;
+                if let Some(pseudos) = layout_data.pseudo_elements.get() {
+                    *pseudos.before.borrow_mut() = None;
+                    *pseudos.after.borrow_mut() = None;
+                }
                 // `take()` removes the `self_box` and replaces it with `None`.
-                if layout_data.self_box.take().is_some() {
+                if layout_data.self_box.borrow_mut().take().is_some() {
                     // Only descend into children if we removed a box.
                     // If there wasn’t one, then descendants don’t have boxes either.
                     if let Some(child) = node.first_child {
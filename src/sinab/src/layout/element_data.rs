@@ -1,15 +1,28 @@
 use super::*;
+use std::cell::{OnceCell, RefCell};
 
+/// The boxes of an element and of its `::before` and `::after`
+/// pseudo-elements, borrowed separately: pseudo-elements are constructed
+/// while the box of their originating element is being constructed.
 #[derive(Debug, Default)]
 pub(crate) struct LayoutDataForElement {
-    pub(super) self_box: Option<LayoutBox>,
-    pub(super) pseudo_elements: Option<Box<PseudoElementBoxes>>,
+    pub(super) self_box: RefCell<Option<LayoutBox>>,
+    pub(super) pseudo_elements: OnceCell<Box<PseudoElementBoxes>>,
 }
 
 #[derive(Debug, Default)]
 pub(super) struct PseudoElementBoxes {
-    pub before: Option<LayoutBox>,
-    pub after: Option<LayoutBox>,
+    pub before: RefCell<Option<LayoutBox>>,
+    pub after: RefCell<Option<LayoutBox>>,
+}
+
+impl PseudoElementBoxes {
+    pub fn get(&self, which: WhichPseudoElement) -> &RefCell<Option<LayoutBox>> {
+        match which {
+            WhichPseudoElement::Before => &self.before,
+            WhichPseudoElement::After => &self.after,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -17,4 +30,8 @@ pub(super) enum LayoutBox {
     DisplayContents,
     BlockLevel(Arc<BlockLevelBox>),
     InlineLevel(Arc<InlineLevelBox>),
+    FlexLevel(Arc<FlexLevelBox>),
+    GridLevel(Arc<GridLevelBox>),
+    /// A box internal to a table, owned by the table box.
+    TablePart,
 }
@@ -0,0 +1,695 @@
+//! Flex layout.
+//! https://drafts.csswg.org/css-flexbox-1/
+
+use super::*;
+
+/// https://drafts.csswg.org/css-flexbox-1/#flex-containers
+#[derive(Debug)]
+pub(super) struct FlexContainer {
+    pub style: Arc<ComputedValues>,
+    pub children: Vec<Arc<FlexLevelBox>>,
+}
+
+#[derive(Debug)]
+pub(in crate::layout) enum FlexLevelBox {
+    FlexItem(ItemBox),
+    OutOfFlowAbsolutelyPositionedBox(AbsolutelyPositionedBox),
+}
+
+impl ItemLevelBox for FlexLevelBox {
+    fn item(box_: ItemBox) -> Self {
+        FlexLevelBox::FlexItem(box_)
+    }
+
+    fn out_of_flow_absolutely_positioned(box_: AbsolutelyPositionedBox) -> Self {
+        FlexLevelBox::OutOfFlowAbsolutelyPositionedBox(box_)
+    }
+
+    fn layout_box(box_: Arc<Self>) -> LayoutBox {
+        LayoutBox::FlexLevel(box_)
+    }
+}
+
+/// The inline or block axis, as the main or cross axis of a flex container.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Axis {
+    Inline,
+    Block,
+}
+
+impl Axis {
+    fn other(self) -> Self {
+        match self {
+            Axis::Inline => Axis::Block,
+            Axis::Block => Axis::Inline,
+        }
+    }
+
+    fn of<T: Clone>(self, vec: &Vec2<T>) -> T {
+        match self {
+            Axis::Inline => vec.inline.clone(),
+            Axis::Block => vec.block.clone(),
+        }
+    }
+
+    /// A vector with the given components along this axis and across it.
+    fn vec2<T>(self, along: T, across: T) -> Vec2<T> {
+        match self {
+            Axis::Inline => Vec2 {
+                inline: along,
+                block: across,
+            },
+            Axis::Block => Vec2 {
+                inline: across,
+                block: along,
+            },
+        }
+    }
+
+    fn sum(self, sides: &Sides<Length>) -> Length {
+        match self {
+            Axis::Inline => sides.inline_sum(),
+            Axis::Block => sides.block_sum(),
+        }
+    }
+}
+
+/// A flex item during layout.
+struct FlexItem<'a> {
+    box_: &'a ItemBox,
+    /// The index of the item among the children of the flex container.
+    index: usize,
+    padding: Sides<Length>,
+    border: Sides<Length>,
+    margin: Sides<Length>,
+    /// The specified size of the content box in each axis, if definite.
+    size: Vec2<Option<Length>>,
+    align: AlignItems,
+    /// https://drafts.csswg.org/css-flexbox-1/#flex-base-size
+    flex_base_size: Length,
+    /// https://drafts.csswg.org/css-flexbox-1/#min-size-auto
+    min_main_size: Length,
+    hypothetical_main_size: Length,
+    target_main_size: Length,
+    frozen: bool,
+    /// The size of the content box in the cross axis, once known.
+    cross_size: Length,
+}
+
+impl FlexItem<'_> {
+    fn pbm(&self) -> Sides<Length> {
+        &(&self.padding + &self.border) + &self.margin
+    }
+
+    fn outer_hypothetical_main_size(&self, main_axis: Axis) -> Length {
+        self.hypothetical_main_size + main_axis.sum(&self.pbm())
+    }
+
+    fn outer_target_main_size(&self, main_axis: Axis) -> Length {
+        self.target_main_size + main_axis.sum(&self.pbm())
+    }
+
+    fn outer_cross_size(&self, main_axis: Axis) -> Length {
+        self.cross_size + main_axis.other().sum(&self.pbm())
+    }
+}
+
+impl FlexContainer {
+    pub(super) fn construct<'a>(
+        context: &'a Context<'a>,
+        style: &Arc<ComputedValues>,
+        contents: NonReplacedContents,
+    ) -> Self {
+        FlexContainer {
+            style: style.clone(),
+            children: construct_items(context, style, contents),
+        }
+    }
+
+    /// The content sizes of the content box of the flex container.
+    pub(super) fn inline_content_sizes(&self) -> ContentSizes {
+        let flex = &self.style.flex;
+        let gap = flex.column_gap.percentage_relative_to(Length::zero(), Length::zero());
+        let items: Vec<ContentSizes> = self
+            .children
+            .iter()
+            .filter_map(|child| match &**child {
+                FlexLevelBox::FlexItem(item) => Some(outer_inline_content_sizes(
+                    &item.style,
+                    || item.contents.inline_content_sizes(&item.style),
+                )),
+                FlexLevelBox::OutOfFlowAbsolutelyPositionedBox(_) => None,
+            })
+            .collect();
+        if flex.flex_direction.is_column() {
+            return items.into_iter().fold(ContentSizes::zero(), ContentSizes::max);
+        }
+        let gaps = gap * items.len().saturating_sub(1) as f32;
+        let sum = items.iter().fold(ContentSizes::zero(), |sum, &item| sum + item);
+        let min_content = match flex.flex_wrap {
+            FlexWrap::Nowrap => sum.min_content + gaps,
+            FlexWrap::Wrap | FlexWrap::WrapReverse => items
+                .iter()
+                .fold(Length::zero(), |max, item| max.max(item.min_content)),
+        };
+        ContentSizes {
+            min_content,
+            max_content: sum.max_content + gaps,
+        }
+    }
+
+    /// https://drafts.csswg.org/css-flexbox-1/#layout-algorithm
+    pub(super) fn layout<'a>(
+        &'a self,
+        containing_block: &ContainingBlock,
+        tree_rank: usize,
+        absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    ) -> FlowChildren {
+        let abspos_so_far = absolutely_positioned_fragments.len();
+        let flex = &self.style.flex;
+        let main_axis = if flex.flex_direction.is_column() {
+            Axis::Block
+        } else {
+            Axis::Inline
+        };
+        let cross_axis = main_axis.other();
+        let container_size = Vec2 {
+            inline: Some(containing_block.inline_size),
+            block: match containing_block.block_size {
+                LengthOrAuto::Length(block_size) => Some(block_size),
+                LengthOrAuto::Auto => None,
+            },
+        };
+        // https://drafts.csswg.org/css-align-3/#column-row-gap
+        let gaps = Vec2 {
+            inline: flex
+                .column_gap
+                .percentage_relative_to(containing_block.inline_size, Length::zero()),
+            block: flex
+                .row_gap
+                .percentage_relative_to(
+                    container_size.block.unwrap_or_else(Length::zero),
+                    Length::zero(),
+                ),
+        };
+        let (main_gap, cross_gap) = (main_axis.of(&gaps), cross_axis.of(&gaps));
+
+        let mut items: Vec<FlexItem> = self
+            .children
+            .iter()
+            .enumerate()
+            .filter_map(|(index, child)| match &**child {
+                FlexLevelBox::FlexItem(box_) => Some(FlexItem::new(
+                    box_,
+                    index,
+                    &self.style,
+                    containing_block,
+                    &container_size,
+                    main_axis,
+                )),
+                FlexLevelBox::OutOfFlowAbsolutelyPositionedBox(_) => None,
+            })
+            .collect();
+
+        // https://drafts.csswg.org/css-flexbox-1/#algo-line-break
+        let available_main_size = main_axis.of(&container_size);
+        let mut lines: Vec<&mut [FlexItem]> = Vec::new();
+        let mut rest = &mut items[..];
+        while !rest.is_empty() {
+            let line_length = match (flex.flex_wrap, available_main_size) {
+                (FlexWrap::Nowrap, _) | (_, None) => rest.len(),
+                (_, Some(available)) => {
+                    let mut outer_sum = Length::zero();
+                    let mut count = 0;
+                    for item in rest.iter() {
+                        let next = outer_sum + item.outer_hypothetical_main_size(main_axis);
+                        if count > 0 && next > available {
+                            break;
+                        }
+                        outer_sum = next + main_gap;
+                        count += 1;
+                    }
+                    count
+                }
+            };
+            let (line, remaining) = rest.split_at_mut(line_length);
+            lines.push(line);
+            rest = remaining;
+        }
+
+        // Without a definite main size, the container is as large as its largest line.
+        let main_size = available_main_size.unwrap_or_else(|| {
+            lines
+                .iter()
+                .map(|line| {
+                    line.iter()
+                        .map(|item| item.outer_hypothetical_main_size(main_axis))
+                        .fold(Length::zero(), |sum, size| sum + size)
+                        + main_gap * (line.len() - 1) as f32
+                })
+                .fold(Length::zero(), Length::max)
+        });
+        for line in &mut lines {
+            resolve_flexible_lengths(line, main_size, main_gap, main_axis);
+        }
+
+        // https://drafts.csswg.org/css-flexbox-1/#cross-sizing
+        let container_cross_size = cross_axis.of(&container_size);
+        let single_line = flex.flex_wrap == FlexWrap::Nowrap;
+        let mut line_cross_sizes = Vec::with_capacity(lines.len());
+        for line in &mut lines {
+            for item in line.iter_mut() {
+                item.cross_size = item.hypothetical_cross_size(main_axis, containing_block);
+            }
+            let line_cross_size = match container_cross_size {
+                Some(size) if single_line => size,
+                // FIXME: baseline alignment
+                _ => line
+                    .iter()
+                    .map(|item| item.outer_cross_size(main_axis))
+                    .fold(Length::zero(), Length::max),
+            };
+            for item in line.iter_mut() {
+                if item.align == AlignItems::Stretch && cross_axis.of(&item.size).is_none() {
+                    item.cross_size = (line_cross_size - cross_axis.sum(&item.pbm()))
+                        .max(Length::zero())
+                }
+            }
+            line_cross_sizes.push(line_cross_size);
+        }
+        let cross_size = container_cross_size.unwrap_or_else(|| {
+            line_cross_sizes
+                .iter()
+                .fold(Length::zero(), |sum, &size| sum + size)
+                + cross_gap * lines.len().saturating_sub(1) as f32
+        });
+
+        let mut fragments: Vec<Option<Fragment>> = self.children.iter().map(|_| None).collect();
+        let mut line_cross_start = Length::zero();
+        for (line, line_cross_size) in lines.iter().zip(line_cross_sizes) {
+            let main_positions = justify(
+                line,
+                main_size,
+                main_gap,
+                main_axis,
+                self.style.flex.justify_content,
+            );
+            for (item, mut main_position) in line.iter().zip(main_positions) {
+                let outer_cross_size = item.outer_cross_size(main_axis);
+                let mut cross_position = line_cross_start
+                    + match item.align {
+                        // FIXME: `start` and `end` differ from `flex-start` and `flex-end`
+                        // with `flex-wrap: wrap-reverse`.
+                        AlignItems::FlexEnd | AlignItems::End => line_cross_size - outer_cross_size,
+                        AlignItems::Center => (line_cross_size - outer_cross_size) / 2.,
+                        AlignItems::Stretch
+                        | AlignItems::Start
+                        | AlignItems::FlexStart
+                        | AlignItems::Baseline => Length::zero(),
+                    };
+                if flex.flex_direction.is_reverse() {
+                    main_position =
+                        main_size - main_position - item.outer_target_main_size(main_axis);
+                }
+                if flex.flex_wrap == FlexWrap::WrapReverse {
+                    cross_position = cross_size - cross_position - outer_cross_size;
+                }
+                let margin_box_start = main_axis.vec2(main_position, cross_position);
+                fragments[item.index] = Some(Fragment::Box(item.layout(
+                    &margin_box_start,
+                    main_axis,
+                    containing_block,
+                    absolutely_positioned_fragments,
+                )));
+            }
+            line_cross_start += line_cross_size + cross_gap;
+        }
+
+        for (index, child) in self.children.iter().enumerate() {
+            if let FlexLevelBox::OutOfFlowAbsolutelyPositionedBox(box_) = &**child {
+                // The static position of an absolutely-positioned child
+                // is the start corner of the flex container.
+                absolutely_positioned_fragments.push(box_.layout(Vec2::zero(), index));
+                fragments[index] = Some(Fragment::Anonymous(AnonymousFragment::no_op(
+                    containing_block.mode,
+                )));
+            }
+        }
+        let mut fragments: Vec<Fragment> = fragments
+            .into_iter()
+            .map(|fragment| fragment.expect("every child has a fragment"))
+            .collect();
+        adjust_static_positions(
+            &mut absolutely_positioned_fragments[abspos_so_far..],
+            &mut fragments,
+            tree_rank,
+        );
+
+        FlowChildren {
+            fragments,
+            block_size: main_axis.vec2(main_size, cross_size).block,
+            collapsible_margins_in_children: CollapsedBlockMargins::zero(),
+        }
+    }
+}
+
+impl<'a> FlexItem<'a> {
+    fn new(
+        box_: &'a ItemBox,
+        index: usize,
+        container_style: &ComputedValues,
+        containing_block: &ContainingBlock,
+        container_size: &Vec2<Option<Length>>,
+        main_axis: Axis,
+    ) -> Self {
+        let style = &box_.style;
+        let cbis = containing_block.inline_size;
+        let padding = style.padding().percentages_relative_to(cbis);
+        let border = style.border_width().percentages_relative_to(cbis);
+        // FIXME: auto margins
+        let margin = style
+            .margin()
+            .percentages_relative_to(cbis)
+            .auto_is(Length::zero);
+        let box_size = style.box_size();
+        let resolve = |size: LengthOrPercentageOrAuto, container_size: Option<Length>| match size {
+            LengthOrPercentageOrAuto::Length(length) => Some(length),
+            LengthOrPercentageOrAuto::Percentage(p) => container_size.map(|size| size * p),
+            LengthOrPercentageOrAuto::Auto => None,
+        };
+        let size = Vec2 {
+            inline: resolve(box_size.inline, container_size.inline),
+            block: resolve(box_size.block, container_size.block),
+        };
+        let mut item = FlexItem {
+            box_,
+            index,
+            padding,
+            border,
+            margin,
+            align: style.flex.align_self.resolve(container_style.flex.align_items),
+            flex_base_size: Length::zero(),
+            min_main_size: Length::zero(),
+            hypothetical_main_size: Length::zero(),
+            target_main_size: Length::zero(),
+            frozen: false,
+            cross_size: Length::zero(),
+            size,
+        };
+
+        // https://drafts.csswg.org/css-flexbox-1/#algo-main-item
+        let main_size = main_axis.of(&item.size);
+        let content_main_size = item.content_main_size(main_axis, containing_block);
+        let flex_basis = resolve(style.flex.flex_basis, main_axis.of(container_size));
+        item.flex_base_size = flex_basis
+            .or(main_size)
+            .unwrap_or(content_main_size.max_content);
+        // The automatic minimum size of flex items is their min-content size,
+        // or their specified size if smaller.
+        item.min_main_size = match main_size {
+            Some(size) => size.min(content_main_size.min_content),
+            None => content_main_size.min_content,
+        };
+        item.hypothetical_main_size = item.flex_base_size.max(item.min_main_size);
+        item
+    }
+
+    /// The min-content and max-content sizes of the content box in the main axis.
+    /// In the block axis, both are the block size of the contents laid out at
+    /// the cross size of the item.
+    fn content_main_size(&self, main_axis: Axis, containing_block: &ContainingBlock) -> ContentSizes {
+        let style = &self.box_.style;
+        match main_axis {
+            Axis::Inline => {
+                if let Ok(replaced) = self.box_.contents.as_replaced() {
+                    let size = replaced.used_size_as_if_inline_element(containing_block, style);
+                    return ContentSizes {
+                        min_content: size.inline,
+                        max_content: size.inline,
+                    };
+                }
+                self.box_.contents.inline_content_sizes(style)
+            }
+            Axis::Block => {
+                let inline_size = self.hypothetical_cross_size(main_axis, containing_block);
+                let block_size = self
+                    .box_
+                    .layout_contents(
+                        inline_size,
+                        LengthOrAuto::Auto,
+                        containing_block,
+                        0,
+                        &mut vec![],
+                    )
+                    .1;
+                ContentSizes {
+                    min_content: block_size,
+                    max_content: block_size,
+                }
+            }
+        }
+    }
+
+    /// The size of the content box in the cross axis, before stretching.
+    /// https://drafts.csswg.org/css-flexbox-1/#algo-cross-item
+    fn hypothetical_cross_size(&self, main_axis: Axis, containing_block: &ContainingBlock) -> Length {
+        let cross_axis = main_axis.other();
+        if let Some(size) = cross_axis.of(&self.size) {
+            return size;
+        }
+        let style = &self.box_.style;
+        match cross_axis {
+            // Rows: lay out the contents at the main size of the item.
+            Axis::Block => {
+                self.box_
+                    .layout_contents(
+                        self.target_main_size,
+                        LengthOrAuto::Auto,
+                        containing_block,
+                        0,
+                        &mut vec![],
+                    )
+                    .1
+            }
+            // Columns: items fit their contents, unless they are stretched anyway.
+            Axis::Inline => {
+                let available = containing_block.inline_size - cross_axis.sum(&self.pbm());
+                if let Ok(replaced) = self.box_.contents.as_replaced() {
+                    return replaced
+                        .used_size_as_if_inline_element(containing_block, style)
+                        .inline;
+                }
+                if self.align == AlignItems::Stretch {
+                    return available.max(Length::zero());
+                }
+                self.box_
+                    .contents
+                    .inline_content_sizes(style)
+                    .shrink_to_fit(available)
+            }
+        }
+    }
+
+    /// Lays out the item at its final size, given the position of its margin box.
+    fn layout(
+        &self,
+        margin_box_start: &Vec2<Length>,
+        main_axis: Axis,
+        containing_block: &ContainingBlock,
+        absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    ) -> BoxFragment {
+        let style = &self.box_.style;
+        let (inline_size, block_size) = match main_axis {
+            Axis::Inline => {
+                let stretched = self.align == AlignItems::Stretch;
+                let block_size = match self.size.block {
+                    Some(size) => LengthOrAuto::Length(size),
+                    None if stretched => LengthOrAuto::Length(self.cross_size),
+                    None => LengthOrAuto::Auto,
+                };
+                (self.target_main_size, block_size)
+            }
+            Axis::Block => (self.cross_size, LengthOrAuto::Length(self.target_main_size)),
+        };
+        let mut nested_abspos = vec![];
+        let relatively_positioned = style.box_.position.is_relatively_positioned();
+        let (mut children, block_size) = self.box_.layout_contents(
+            inline_size,
+            block_size,
+            containing_block,
+            self.index,
+            if relatively_positioned {
+                &mut nested_abspos
+            } else {
+                absolutely_positioned_fragments
+            },
+        );
+        let size = Vec2 {
+            inline: inline_size,
+            block: block_size,
+        };
+        if relatively_positioned {
+            AbsolutelyPositionedFragment::in_positioned_containing_block(
+                &nested_abspos,
+                &mut children,
+                &size,
+                &self.padding,
+                style.writing_mode(),
+            )
+        }
+        let pb = &self.padding + &self.border;
+        let mut start_corner = Vec2 {
+            inline: margin_box_start.inline + self.margin.inline_start + pb.inline_start,
+            block: margin_box_start.block + self.margin.block_start + pb.block_start,
+        };
+        start_corner += &relative_adjustement(
+            style,
+            containing_block.inline_size,
+            containing_block.block_size,
+        );
+        BoxFragment {
+            style: style.clone(),
+            children,
+            content_rect: Rect { start_corner, size },
+            padding: self.padding.clone(),
+            border: self.border.clone(),
+            margin: self.margin.clone(),
+            block_margins_collapsed_with_children: CollapsedBlockMargins::zero(),
+        }
+    }
+}
+
+/// Flexes the items of a line so that their outer sizes fill the main size of the container.
+/// https://drafts.csswg.org/css-flexbox-1/#resolve-flexible-lengths
+fn resolve_flexible_lengths(
+    line: &mut [FlexItem],
+    main_size: Length,
+    gap: Length,
+    main_axis: Axis,
+) {
+    let gaps = gap * (line.len() - 1) as f32;
+    let outer_hypothetical_sum = line
+        .iter()
+        .map(|item| item.outer_hypothetical_main_size(main_axis))
+        .fold(Length::zero(), |sum, size| sum + size);
+    let growing = outer_hypothetical_sum + gaps < main_size;
+    let flex_factor = |item: &FlexItem| {
+        let flex = &item.box_.style.flex;
+        if growing {
+            flex.flex_grow.value
+        } else {
+            flex.flex_shrink.value
+        }
+    };
+
+    // Size inflexible items
+    for item in line.iter_mut() {
+        item.target_main_size = item.hypothetical_main_size;
+        item.frozen = flex_factor(item) <= 0.
+            || (growing && item.flex_base_size > item.hypothetical_main_size)
+            || (!growing && item.flex_base_size < item.hypothetical_main_size);
+    }
+    let remaining_free_space = |line: &[FlexItem]| {
+        main_size
+            - gaps
+            - line
+                .iter()
+                .map(|item| {
+                    let size = if item.frozen {
+                        item.target_main_size
+                    } else {
+                        item.flex_base_size
+                    };
+                    size + main_axis.sum(&item.pbm())
+                })
+                .fold(Length::zero(), |sum, size| sum + size)
+    };
+    let initial_free_space = remaining_free_space(line);
+
+    while line.iter().any(|item| !item.frozen) {
+        let mut free_space = remaining_free_space(line);
+        let factor_sum: f32 = line
+            .iter()
+            .filter(|item| !item.frozen)
+            .map(&flex_factor)
+            .sum();
+        if factor_sum < 1. {
+            let scaled = initial_free_space * factor_sum;
+            if scaled.px.abs() < free_space.px.abs() {
+                free_space = scaled
+            }
+        }
+        let scaled_shrink_sum: f32 = line
+            .iter()
+            .filter(|item| !item.frozen)
+            .map(|item| flex_factor(item) * item.flex_base_size.px)
+            .sum();
+        let mut total_violation = Length::zero();
+        for item in line.iter_mut().filter(|item| !item.frozen) {
+            let factor = flex_factor(item);
+            let target = if growing {
+                item.flex_base_size + free_space * (factor / factor_sum)
+            } else if scaled_shrink_sum > 0. {
+                let ratio = factor * item.flex_base_size.px / scaled_shrink_sum;
+                item.flex_base_size - Length { px: free_space.px.abs() } * ratio
+            } else {
+                item.flex_base_size
+            };
+            // FIXME: max-width and max-height
+            let clamped = target.max(item.min_main_size);
+            total_violation += clamped - target;
+            item.target_main_size = clamped;
+        }
+        for item in line.iter_mut().filter(|item| !item.frozen) {
+            item.frozen = if total_violation > Length::zero() {
+                item.target_main_size == item.min_main_size
+            } else {
+                true
+            };
+        }
+    }
+}
+
+/// The main position of the margin box of each item of a line.
+/// https://drafts.csswg.org/css-flexbox-1/#justify-content-property
+fn justify(
+    line: &[FlexItem],
+    main_size: Length,
+    gap: Length,
+    main_axis: Axis,
+    justify_content: JustifyContent,
+) -> Vec<Length> {
+    let count = line.len() as f32;
+    let used = line
+        .iter()
+        .map(|item| item.outer_target_main_size(main_axis))
+        .fold(Length::zero(), |sum, size| sum + size)
+        + gap * (count - 1.);
+    let free_space = main_size - used;
+    let zero = Length::zero();
+    let (start, between) = match justify_content {
+        JustifyContent::FlexStart => (zero, zero),
+        JustifyContent::FlexEnd => (free_space, zero),
+        JustifyContent::Center => (free_space / 2., zero),
+        // Without free space, these fall back to `flex-start` or `center`.
+        JustifyContent::SpaceBetween if free_space > zero && count > 1. => {
+            (zero, free_space / (count - 1.))
+        }
+        JustifyContent::SpaceBetween => (zero, zero),
+        JustifyContent::SpaceAround if free_space > zero => {
+            (free_space / (2. * count), free_space / count)
+        }
+        JustifyContent::SpaceEvenly if free_space > zero => {
+            (free_space / (count + 1.), free_space / (count + 1.))
+        }
+        JustifyContent::SpaceAround | JustifyContent::SpaceEvenly => (free_space / 2., zero),
+    };
+    let mut position = start;
+    line.iter()
+        .map(|item| {
+            let item_position = position;
+            position += item.outer_target_main_size(main_axis) + gap + between;
+            item_position
+        })
+        .collect()
+}
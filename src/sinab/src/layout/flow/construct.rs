@@ -1,10 +1,11 @@
 use super::*;
 use crate::graphics_engine::font::Font;
+use crate::style::{pseudo_element_has_rules, style_for_pseudo_element, PseudoElement};
 
 impl BlockFormattingContext {
     pub fn construct<'a>(
         context: &'a Context<'a>,
-        style: &'a Arc<ComputedValues>,
+        style: &Arc<ComputedValues>,
         contents: NonReplacedContents,
     ) -> Self {
         let (contents, contains_floats) = BlockContainer::construct(context, style, contents);
@@ -35,6 +36,13 @@ enum IntermediateBlockLevelBox {
         display_inside: DisplayInside,
         contents: Contents,
     },
+    OutsideMarker {
+        style: Arc<ComputedValues>,
+        contents: NonReplacedContents,
+    },
+    AnonymousTable {
+        table: Table,
+    },
 }
 
 /// A block container that may still have to be constructed.
@@ -42,20 +50,32 @@ enum IntermediateBlockLevelBox {
 /// Represents either the inline formatting context of an anonymous block
 /// box or the yet-to-be-computed block container generated from the children
 /// of a given element.
-///
-/// Deferring allows using rayon’s `into_par_iter`.
 enum IntermediateBlockContainer {
     InlineFormattingContext(InlineFormattingContext),
-    Deferred { contents: NonReplacedContents },
+    Deferred {
+        contents: NonReplacedContents,
+        first_formatted_line: FirstFormattedLine,
+    },
+}
+
+/// The elements whose `::first-line` and `::first-letter` pseudo-elements
+/// apply to the first formatted line of a block container, outermost first.
+/// A block container without line boxes before its first in-flow block-level
+/// child passes them down to that child.
+/// https://drafts.csswg.org/css-pseudo-4/#first-formatted-line
+#[derive(Default)]
+struct FirstFormattedLine {
+    first_line_elements: Vec<dom::NodeId>,
+    first_letter_elements: Vec<dom::NodeId>,
 }
 
 /// A builder for a block container.
 ///
 /// This builder starts from the first child of a given DOM node
 /// and does a preorder traversal of all of its inclusive siblings.
-struct BlockContainerBuilder<'a> {
+pub(in crate::layout) struct BlockContainerBuilder<'a> {
     context: &'a Context<'a>,
-    block_container_style: &'a Arc<ComputedValues>,
+    block_container_style: Arc<ComputedValues>,
 
     /// The list of block-level boxes of the final block container.
     ///
@@ -64,13 +84,11 @@ struct BlockContainerBuilder<'a> {
     /// inline formatting context is not empty, the block container establishes
     /// an inline formatting context (see end of `build`).
     ///
-    /// DOM nodes which represent block-level boxes are immediately pushed
-    /// to this list with their style without ever being traversed at this
-    /// point, instead we just move to their next sibling. If the DOM node
-    /// doesn't have a next sibling, we either reached the end of the container
-    /// root or there are ongoing inline-level boxes
-    /// (see `handle_block_level_element`).
-    block_level_boxes: Vec<(IntermediateBlockLevelBox, BoxSlot<'a>)>,
+    /// DOM nodes which represent block-level boxes are constructed as soon
+    /// as they are found, before moving to their next sibling, so that the
+    /// whole tree is traversed in document order as counters require
+    /// (see `push_block_level_box`).
+    block_level_boxes: Vec<Arc<BlockLevelBox>>,
 
     /// The ongoing inline formatting context of the builder.
     ///
@@ -102,74 +120,134 @@ struct BlockContainerBuilder<'a> {
 
     /// Whether the resulting block container contains any float box.
     contains_floats: ContainsFloats,
+
+    /// The anonymous table generated around consecutive table-internal boxes
+    /// found outside of a table, if any.
+    /// https://drafts.csswg.org/css2/tables.html#anonymous-boxes
+    ongoing_anonymous_table: Option<Box<TableBuilder<'a>>>,
+
+    /// The pseudo-elements of the first formatted line, until a block-level
+    /// box ends it. The `::first-letter` ones are dropped once the first
+    /// letter is found.
+    first_formatted_line: FirstFormattedLine,
+
+    /// The styles of the first formatted line, following the fictional tag
+    /// sequence of the spec: the `::first-line` style cascaded against the
+    /// block container style, then the style of each ongoing inline box
+    /// cascaded against the previous one. Empty once the first line ended.
+    /// https://drafts.csswg.org/css-pseudo-4/#first-line-inheritance
+    first_line_styles: Vec<Arc<ComputedValues>>,
 }
 
 impl BlockContainer {
     pub fn construct<'a>(
         context: &'a Context<'a>,
-        block_container_style: &'a Arc<ComputedValues>,
+        block_container_style: &Arc<ComputedValues>,
         contents: NonReplacedContents,
     ) -> (BlockContainer, ContainsFloats) {
-        let mut builder = BlockContainerBuilder {
+        Self::construct_with_first_formatted_line(
+            context,
+            block_container_style,
+            contents,
+            FirstFormattedLine::default(),
+        )
+    }
+
+    fn construct_with_first_formatted_line<'a>(
+        context: &'a Context<'a>,
+        block_container_style: &Arc<ComputedValues>,
+        contents: NonReplacedContents,
+        mut first_formatted_line: FirstFormattedLine,
+    ) -> (BlockContainer, ContainsFloats) {
+        let mut builder = BlockContainerBuilder::new(context, block_container_style.clone());
+
+        if let NonReplacedContents::OfElement(element) = &contents {
+            if let Some((marker_style, marker_contents)) =
+                make_marker(context, *element, block_container_style)
+            {
+                builder.handle_list_item_marker(marker_style, marker_contents)
+            }
+            let has_rules = |pseudo_element| {
+                let author_styles = context.author_styles;
+                pseudo_element_has_rules(author_styles, context.document, *element, pseudo_element)
+            };
+            if has_rules(PseudoElement::FirstLine) {
+                first_formatted_line.first_line_elements.push(*element)
+            }
+            if has_rules(PseudoElement::FirstLetter) {
+                first_formatted_line.first_letter_elements.push(*element)
+            }
+        }
+        builder.start_first_formatted_line(first_formatted_line);
+
+        contents.traverse(block_container_style, context, &mut builder);
+        builder.finish()
+    }
+}
+
+impl<'a> BlockContainerBuilder<'a> {
+    pub(in crate::layout) fn new(
+        context: &'a Context<'a>,
+        block_container_style: Arc<ComputedValues>,
+    ) -> Self {
+        Self {
             context,
+            ongoing_inline_formatting_context: InlineFormattingContext::new(&block_container_style),
             block_container_style,
             block_level_boxes: Default::default(),
-            ongoing_inline_formatting_context: InlineFormattingContext::new(block_container_style),
             ongoing_inline_boxes_stack: Default::default(),
             anonymous_style: Default::default(),
             contains_floats: Default::default(),
-        };
+            ongoing_anonymous_table: None,
+            first_formatted_line: FirstFormattedLine::default(),
+            first_line_styles: vec![],
+        }
+    }
 
-        contents.traverse(block_container_style, context, &mut builder);
+    /// The `::first-line` pseudo-elements of ancestors nest outside of the
+    /// ones of the block container.
+    fn start_first_formatted_line(&mut self, first_formatted_line: FirstFormattedLine) {
+        let context = self.context;
+        let elements = &first_formatted_line.first_line_elements;
+        if !elements.is_empty() {
+            let style = elements.iter().fold(
+                self.block_container_style.clone(),
+                |parent_style, element| {
+                    style_for_pseudo_element(
+                        context.author_styles,
+                        context.document,
+                        *element,
+                        PseudoElement::FirstLine,
+                        &parent_style,
+                    )
+                },
+            );
+            self.first_line_styles.push(style)
+        }
+        self.first_formatted_line = first_formatted_line;
+    }
+
+    pub(in crate::layout) fn finish(mut self) -> (BlockContainer, ContainsFloats) {
+        self.end_ongoing_anonymous_table();
 
-        debug_assert!(builder.ongoing_inline_boxes_stack.is_empty());
+        debug_assert!(self.ongoing_inline_boxes_stack.is_empty());
 
-        if !builder
+        if !self
             .ongoing_inline_formatting_context
             .inline_level_boxes
             .is_empty()
         {
-            if builder.block_level_boxes.is_empty() {
+            if self.block_level_boxes.is_empty() {
                 let container = BlockContainer::InlineFormattingContext(
-                    builder.ongoing_inline_formatting_context,
+                    self.ongoing_inline_formatting_context,
                 );
-                return (container, builder.contains_floats);
+                return (container, self.contains_floats);
             }
-            builder.end_ongoing_inline_formatting_context();
+            self.end_ongoing_inline_formatting_context();
         }
 
-        let mut contains_floats = builder.contains_floats;
-        let container = BlockContainer::BlockLevelBoxes(
-            builder
-                .block_level_boxes
-                .into_iter()
-                .map(|(intermediate, box_slot)| {
-                    let (block_level_box, box_contains_floats) = intermediate.finish(context);
-                    contains_floats |= box_contains_floats;
-                    box_slot.set(LayoutBox::BlockLevel(block_level_box.clone()));
-                    block_level_box
-                })
-                .collect(),
-        );
-        /* // previous, parallelized code
-        let container = BlockContainer::BlockLevelBoxes(
-            builder
-                .block_level_boxes
-                .into_par_iter()
-                .mapfold_reduce_into(
-                    &mut contains_floats,
-                    |contains_floats, (intermediate, box_slot)| {
-                        let (block_level_box, box_contains_floats) = intermediate.finish(context);
-                        *contains_floats |= box_contains_floats;
-                        box_slot.set(LayoutBox::BlockLevel(block_level_box.clone()));
-                        block_level_box
-                    },
-                    |left, right| *left |= right,
-                )
-                .collect(),
-        );
-         */
-        (container, contains_floats)
+        let container = BlockContainer::BlockLevelBoxes(self.block_level_boxes);
+        (container, self.contains_floats)
     }
 }
 
@@ -182,31 +260,59 @@ impl<'a> TraversalHandler<'a> for BlockContainerBuilder<'a> {
         box_slot: BoxSlot<'a>,
     ) {
         match display {
-            DisplayGeneratingBox::OutsideInside { outside, inside } => match outside {
-                DisplayOutside::Inline => box_slot.set(LayoutBox::InlineLevel(
-                    self.handle_inline_level_element(style, inside, contents),
-                )),
-                DisplayOutside::Block => {
-                    // Floats and abspos cause blockification, so they only happen in this case.
-                    // https://drafts.csswg.org/css2/visuren.html#dis-pos-flo
-                    if style.box_.position.is_absolutely_positioned() {
-                        self.handle_absolutely_positioned_element(
-                            style.clone(),
-                            inside,
-                            contents,
-                            box_slot,
-                        )
-                    } else if style.box_.float.is_floating() {
-                        self.handle_float_element(style.clone(), inside, contents, box_slot)
-                    } else {
-                        self.handle_block_level_element(style.clone(), inside, contents, box_slot)
+            DisplayGeneratingBox::OutsideInside { outside, inside, .. } => {
+                self.end_ongoing_anonymous_table();
+                match outside {
+                    DisplayOutside::Inline => box_slot.set(LayoutBox::InlineLevel(
+                        self.handle_inline_level_element(style, inside, contents),
+                    )),
+                    DisplayOutside::Block => {
+                        // Floats and abspos cause blockification, so they only happen in this case.
+                        // https://drafts.csswg.org/css2/visuren.html#dis-pos-flo
+                        if style.box_.position.is_absolutely_positioned() {
+                            self.handle_absolutely_positioned_element(
+                                style.clone(),
+                                inside,
+                                contents,
+                                box_slot,
+                            )
+                        } else if style.box_.float.is_floating() {
+                            self.handle_float_element(style.clone(), inside, contents, box_slot)
+                        } else {
+                            self.handle_block_level_element(style.clone(), inside, contents, box_slot)
+                        }
                     }
                 }
-            },
+            }
+            DisplayGeneratingBox::LayoutInternal(_) => {
+                self.handle_table_internal_element(style, display, contents, box_slot)
+            }
         }
     }
 
     fn handle_text(&mut self, input: &str, parent_style: &Arc<ComputedValues>) {
+        if let Some(table) = &mut self.ongoing_anonymous_table {
+            // Whitespace between table-internal boxes does not end the anonymous table.
+            if input.bytes().all(|b| b.is_ascii_whitespace()) {
+                return table.handle_text(input, parent_style);
+            }
+            self.end_ongoing_anonymous_table();
+        }
+        if !self.first_formatted_line.first_letter_elements.is_empty() {
+            if let Some((leading, letter, rest)) = split_first_letter(input) {
+                let elements = take(&mut self.first_formatted_line.first_letter_elements);
+                self.push_text(leading, parent_style);
+                self.handle_first_letter(&elements, letter, parent_style);
+                return self.push_text(rest, parent_style);
+            }
+        }
+        self.push_text(input, parent_style)
+    }
+
+}
+
+impl<'a> BlockContainerBuilder<'a> {
+    fn push_text(&mut self, input: &str, parent_style: &Arc<ComputedValues>) {
         match parent_style.text_inherited.white_space {
             WhiteSpace::Normal | WhiteSpace::Nowrap => self.handle_text_collapse(input, parent_style),
             WhiteSpace::Pre | WhiteSpace::PreWrap => self.handle_text_preserve(input, parent_style),
@@ -214,9 +320,43 @@ impl<'a> TraversalHandler<'a> for BlockContainerBuilder<'a> {
         };
     }
 
-}
+    /// The first letter is an inline box, or a float with `float` or
+    /// `initial-letter`. It inherits from `::first-line`, if any, and the
+    /// `::first-letter` pseudo-elements of ancestors nest outside of the
+    /// one of the block container.
+    fn handle_first_letter(
+        &mut self,
+        elements: &[dom::NodeId],
+        letter: &str,
+        parent_style: &Arc<ComputedValues>,
+    ) {
+        let context = self.context;
+        let parent_style = self.first_line_styles.last().unwrap_or(parent_style).clone();
+        let style = elements.iter().fold(parent_style, |parent_style, element| {
+            style_for_pseudo_element(
+                context.author_styles,
+                context.document,
+                *element,
+                PseudoElement::FirstLetter,
+                &parent_style,
+            )
+        });
+        let style = style.with_initial_letter(&self.block_container_style);
+        let contents =
+            Contents::OfPseudoElement(vec![PseudoElementContentItem::Text(letter.into())]);
+        if style.box_.float.is_floating() {
+            self.handle_float_element(style, DisplayInside::Flow, contents, BoxSlot::dummy())
+        } else {
+            self.handle_inline_level_element(&style, DisplayInside::Flow, contents);
+        }
+    }
+
+    /// The style and font of a new text run for the first formatted line.
+    fn first_line_style(&self) -> Option<(Arc<ComputedValues>, Font)> {
+        let style = self.first_line_styles.last()?;
+        Some((style.clone(), Font::new_from_computed_values(style)))
+    }
 
-impl<'a> BlockContainerBuilder<'a> {
     /// Handle text while collapsing whitespace.
     fn handle_text_collapse(&mut self, input: &str, parent_style: &Arc<ComputedValues>) {
         let (leading_whitespace, mut input) = self.handle_leading_whitespace(input);
@@ -261,13 +401,17 @@ impl<'a> BlockContainerBuilder<'a> {
 
             // Create new inline-level box if we didn't append to existing text run
             if let Some(text) = new_text_run_contents {
+                let first_line = self.first_line_style();
                 let parent_style = parent_style.clone();
                 let font = Font::new_from_computed_values(&parent_style);
-                inlines.push(Arc::new(InlineLevelBox::TextRun(TextRun {
+                let text_run = TextRun {
                     parent_style,
                     text,
                     font,
-                })))
+                    first_line,
+                };
+                self.current_inline_level_boxes()
+                    .push(Arc::new(InlineLevelBox::TextRun(text_run)))
             }
         }
     }
@@ -299,13 +443,17 @@ impl<'a> BlockContainerBuilder<'a> {
 
             // Create new inline-level box if we didn't append to existing text run
             if let Some(text) = new_text_run_contents {
+                let first_line = self.first_line_style();
                 let parent_style = parent_style.clone();
                 let font = Font::new_from_computed_values(&parent_style);
-                inlines.push(Arc::new(InlineLevelBox::TextRun(TextRun {
+                let text_run = TextRun {
                     parent_style,
                     text,
                     font,
-                })))
+                    first_line,
+                };
+                self.current_inline_level_boxes()
+                    .push(Arc::new(InlineLevelBox::TextRun(text_run)))
             }
         }
     }
@@ -371,12 +519,31 @@ impl<'a> BlockContainerBuilder<'a> {
         contents: Contents,
     ) -> Arc<InlineLevelBox> {
         let box_ = match contents.try_into() {
-            Err(replaced) => Arc::new(InlineLevelBox::Atomic {
+            Err(replaced) => {
+                // The first letter must come first on the line.
+                self.first_formatted_line.first_letter_elements.clear();
+                Arc::new(InlineLevelBox::Atomic {
                 style: style.clone(),
-                contents: replaced,
-            }),
+                    contents: IndependentFormattingContext::Replaced(replaced),
+                })
+            }
             Ok(non_replaced) => match display_inside {
                 DisplayInside::Flow => {
+                    if let Some(parent_style) = self.first_line_styles.last() {
+                        let first_line_style = match &non_replaced {
+                            NonReplacedContents::OfElement(element) => style_for_element(
+                                self.context.author_styles,
+                                self.context.document,
+                                *element,
+                                Some(parent_style),
+                            ),
+                            // FIXME: cascade `::before` and `::after` against `::first-line`.
+                            // The style of a `::first-letter` box already inherits from it.
+                            NonReplacedContents::OfPseudoElement(_) => style.clone(),
+                        };
+                        self.first_line_styles.push(first_line_style)
+                    }
+
                     // Whatever happened before, we just found an inline level element, so
                     // all we need to do is to remember this ongoing inline level box.
                     self.ongoing_inline_boxes_stack.push(InlineBox {
@@ -387,17 +554,32 @@ impl<'a> BlockContainerBuilder<'a> {
                     });
 
                     NonReplacedContents::traverse(non_replaced, &style, self.context, self);
+                    self.end_ongoing_anonymous_table();
 
                     let mut inline_box = self
                         .ongoing_inline_boxes_stack
                         .pop()
                         .expect("no ongoing inline level box found");
+                    self.first_line_styles
+                        .truncate(self.ongoing_inline_boxes_stack.len() + 1);
                     inline_box.last_fragment = true;
                     Arc::new(InlineLevelBox::InlineBox(inline_box))
                 }
-                DisplayInside::FlowRoot => {
-                    // a.k.a. `inline-block`
-                    unimplemented!()
+                // a.k.a. `inline-block`, `inline-table`, `inline-flex` and `inline-grid`
+                DisplayInside::FlowRoot
+                | DisplayInside::Table
+                | DisplayInside::Flex
+                | DisplayInside::Grid => {
+                    self.first_formatted_line.first_letter_elements.clear();
+                    Arc::new(InlineLevelBox::Atomic {
+                        style: style.clone(),
+                        contents: IndependentFormattingContext::construct(
+                            self.context,
+                            style,
+                            display_inside,
+                            non_replaced.into(),
+                        ),
+                    })
                 }
             },
         };
@@ -412,11 +594,93 @@ impl<'a> BlockContainerBuilder<'a> {
         contents: Contents,
         box_slot: BoxSlot<'a>,
     ) {
-        // We just found a block level element, all ongoing inline level boxes
-        // need to be split around it. We iterate on the fragmented inline
-        // level box stack to take their contents and set their first_fragment
-        // field to false, for the fragmented inline level boxes that will
-        // come after the block level element.
+        // Without line boxes before it, the first formatted line is in this box.
+        let first_formatted_line = if self.has_ongoing_inline_formatting_context() {
+            FirstFormattedLine::default()
+        } else {
+            take(&mut self.first_formatted_line)
+        };
+
+        self.split_ongoing_inline_boxes();
+
+        // We found a block level element, so the ongoing inline formatting
+        // context needs to be ended.
+        self.end_ongoing_inline_formatting_context();
+
+        let intermediate_box = match contents.try_into() {
+            Ok(contents) => match display_inside {
+                // Multi-column containers and scroll containers establish
+                // independent formatting contexts.
+                // https://drafts.csswg.org/css-multicol/#the-multi-column-model
+                DisplayInside::Flow
+                    if !style.is_multicol_container() && !style.is_scroll_container() =>
+                {
+                    IntermediateBlockLevelBox::SameFormattingContextBlock {
+                        style,
+                        contents: IntermediateBlockContainer::Deferred {
+                            contents,
+                            first_formatted_line,
+                        },
+                    }
+                }
+                _ => IntermediateBlockLevelBox::Independent {
+                    style,
+                    display_inside,
+                    contents: contents.into(),
+                },
+            },
+            Err(contents) => {
+                let contents = Contents::Replaced(contents);
+                IntermediateBlockLevelBox::Independent {
+                    style,
+                    display_inside,
+                    contents,
+                }
+            }
+        };
+        self.push_block_level_box(intermediate_box, box_slot)
+    }
+
+    /// Table-internal boxes outside of a table are wrapped in an anonymous table,
+    /// which is block-level and inherits from the innermost ongoing inline box, if any.
+    /// https://drafts.csswg.org/css2/tables.html#anonymous-boxes
+    fn handle_table_internal_element(
+        &mut self,
+        style: &Arc<ComputedValues>,
+        display: DisplayGeneratingBox,
+        contents: Contents,
+        box_slot: BoxSlot<'a>,
+    ) {
+        let context = self.context;
+        let parent_style = match self.ongoing_inline_boxes_stack.last() {
+            Some(inline_box) => &inline_box.style,
+            None => &self.block_container_style,
+        };
+        self.ongoing_anonymous_table
+            .get_or_insert_with(|| {
+                let table_style = ComputedValues::anonymous_inheriting_from(Some(parent_style));
+                Box::new(TableBuilder::new(context, table_style))
+            })
+            .handle_element(style, display, contents, box_slot)
+    }
+
+    fn end_ongoing_anonymous_table(&mut self) {
+        if let Some(table) = self.ongoing_anonymous_table.take() {
+            self.split_ongoing_inline_boxes();
+            self.end_ongoing_inline_formatting_context();
+            let box_ = IntermediateBlockLevelBox::AnonymousTable {
+                table: table.finish(),
+            };
+            self.push_block_level_box(box_, BoxSlot::dummy())
+        }
+    }
+
+    /// We just found a block-level box, all ongoing inline level boxes
+    /// need to be split around it. We iterate on the fragmented inline
+    /// level box stack to take their contents and set their first_fragment
+    /// field to false, for the fragmented inline level boxes that will
+    /// come after the block-level box.
+    fn split_ongoing_inline_boxes(&mut self) {
         let mut fragmented_inline_boxes =
             self.ongoing_inline_boxes_stack
                 .iter_mut()
@@ -450,33 +714,30 @@ impl<'a> BlockContainerBuilder<'a> {
                 .inline_level_boxes
                 .push(Arc::new(fragmented_inline));
         }
+    }
 
-        // We found a block level element, so the ongoing inline formatting
-        // context needs to be ended.
-        self.end_ongoing_inline_formatting_context();
-
-        let intermediate_box = match contents.try_into() {
-            Ok(contents) => match display_inside {
-                DisplayInside::Flow => IntermediateBlockLevelBox::SameFormattingContextBlock {
-                    style,
-                    contents: IntermediateBlockContainer::Deferred { contents },
-                },
-                _ => IntermediateBlockLevelBox::Independent {
-                    style,
-                    display_inside,
-                    contents: contents.into(),
-                },
-            },
-            Err(contents) => {
-                let contents = Contents::Replaced(contents);
-                IntermediateBlockLevelBox::Independent {
-                    style,
-                    display_inside,
-                    contents,
-                }
+    /// The `::marker` of a list item comes before any other content, including `::before`.
+    /// https://drafts.csswg.org/css-lists-3/#list-style-position-property
+    fn handle_list_item_marker(
+        &mut self,
+        style: Arc<ComputedValues>,
+        contents: Vec<PseudoElementContentItem>,
+    ) {
+        let contents = NonReplacedContents::OfPseudoElement(contents);
+        match self.block_container_style.list.list_style_position {
+            ListStylePosition::Inside => {
+                let display_inline = DisplayGeneratingBox::OutsideInside {
+                    outside: DisplayOutside::Inline,
+                    inside: DisplayInside::Flow,
+                    list_item: false,
+                };
+                self.handle_element(&style, display_inline, contents.into(), BoxSlot::dummy())
             }
-        };
-        self.block_level_boxes.push((intermediate_box, box_slot))
+            ListStylePosition::Outside => {
+                let box_ = IntermediateBlockLevelBox::OutsideMarker { style, contents };
+                self.push_block_level_box(box_, BoxSlot::dummy())
+            }
+        }
     }
 
     fn handle_absolutely_positioned_element(
@@ -492,7 +753,7 @@ impl<'a> BlockContainerBuilder<'a> {
                 contents,
                 display_inside,
             };
-            self.block_level_boxes.push((box_, box_slot))
+            self.push_block_level_box(box_, box_slot)
         } else {
             let box_ = Arc::new(InlineLevelBox::OutOfFlowAbsolutelyPositionedBox(
                 AbsolutelyPositionedBox {
@@ -525,7 +786,7 @@ impl<'a> BlockContainerBuilder<'a> {
                 contents,
                 display_inside,
             };
-            self.block_level_boxes.push((box_, box_slot));
+            self.push_block_level_box(box_, box_slot);
         } else {
             let box_ = Arc::new(InlineLevelBox::OutOfFlowFloatBox(FloatBox {
                 contents: IndependentFormattingContext::construct(
@@ -547,6 +808,10 @@ impl<'a> BlockContainerBuilder<'a> {
             "there should be no ongoing inline level boxes",
         );
 
+        // A block-level box ends the first formatted line.
+        self.first_formatted_line = FirstFormattedLine::default();
+        self.first_line_styles.clear();
+
         if self
             .ongoing_inline_formatting_context
             .inline_level_boxes
@@ -556,7 +821,7 @@ impl<'a> BlockContainerBuilder<'a> {
             return;
         }
 
-        let block_container_style = self.block_container_style;
+        let block_container_style = &self.block_container_style;
         let anonymous_style = self.anonymous_style.get_or_insert_with(|| {
             // If parent_style is None, the parent is the document node,
             // in which case anonymous inline boxes should inherit their
@@ -569,11 +834,20 @@ impl<'a> BlockContainerBuilder<'a> {
             contents: IntermediateBlockContainer::InlineFormattingContext(
                 std::mem::replace(
                     &mut self.ongoing_inline_formatting_context,
-                    InlineFormattingContext::new(self.block_container_style)
+                    InlineFormattingContext::new(&self.block_container_style)
                 )
             ),
         };
-        self.block_level_boxes.push((box_, BoxSlot::dummy()))
+        self.push_block_level_box(box_, BoxSlot::dummy())
+    }
+
+    /// Constructs the contents of a block-level box right away: counters
+    /// changed in its subtree are in scope for the following siblings.
+    fn push_block_level_box(&mut self, box_: IntermediateBlockLevelBox, box_slot: BoxSlot<'a>) {
+        let (block_level_box, contains_floats) = box_.finish(self.context);
+        self.contains_floats |= contains_floats;
+        box_slot.set(LayoutBox::BlockLevel(block_level_box.clone()));
+        self.block_level_boxes.push(block_level_box)
     }
 
     fn current_inline_level_boxes(&mut self) -> &mut Vec<Arc<InlineLevelBox>> {
@@ -592,6 +866,30 @@ impl<'a> BlockContainerBuilder<'a> {
     }
 }
 
+/// Splits text into its leading whitespace, its first letter with the
+/// punctuation around it, and the rest, or returns `None` if the text is
+/// only whitespace.
+/// https://drafts.csswg.org/css-pseudo-4/#first-letter-pattern
+fn split_first_letter(text: &str) -> Option<(&str, &str, &str)> {
+    let is_punctuation = |c: char| c.is_ascii_punctuation() || "«»‹›“”‘’„‚¡¿".contains(c);
+    let start = text.find(|c: char| !c.is_ascii_whitespace())?;
+    let (leading, text) = text.split_at(start);
+    let mut end = text.len();
+    for (i, c) in text.char_indices() {
+        if c.is_ascii_whitespace() {
+            end = i;
+            break;
+        }
+        if !is_punctuation(c) {
+            end = i + c.len_utf8();
+            end += text[end..].find(|c: char| !is_punctuation(c)).unwrap_or(text.len() - end);
+            break;
+        }
+    }
+    let (letter, rest) = text.split_at(end);
+    Some((leading, letter, rest))
+}
+
 impl IntermediateBlockLevelBox {
     fn finish(self, context: &Context) -> (Arc<BlockLevelBox>, ContainsFloats) {
         match self {
@@ -652,6 +950,18 @@ impl IntermediateBlockLevelBox {
                 }));
                 (block_level_box, ContainsFloats::Yes)
             }
+            IntermediateBlockLevelBox::OutsideMarker { style, contents } => {
+                let (contents, _) = BlockContainer::construct(context, &style, contents);
+                let block_level_box = Arc::new(BlockLevelBox::OutsideMarker { contents });
+                (block_level_box, ContainsFloats::No)
+            }
+            IntermediateBlockLevelBox::AnonymousTable { table } => {
+                let block_level_box = Arc::new(BlockLevelBox::Independent {
+                    style: table.style.clone(),
+                    contents: IndependentFormattingContext::Table(table),
+                });
+                (block_level_box, ContainsFloats::No)
+            }
         }
     }
 }
@@ -663,9 +973,15 @@ impl IntermediateBlockContainer {
         style: &Arc<ComputedValues>,
     ) -> (BlockContainer, ContainsFloats) {
         match self {
-            IntermediateBlockContainer::Deferred { contents } => {
-                BlockContainer::construct(context, style, contents)
-            }
+            IntermediateBlockContainer::Deferred {
+                contents,
+                first_formatted_line,
+            } => BlockContainer::construct_with_first_formatted_line(
+                context,
+                style,
+                contents,
+                first_formatted_line,
+            ),
             IntermediateBlockContainer::InlineFormattingContext(ifc) => {
                 // If that inline formatting context contained any float, those
                 // were already taken into account during the first phase of
@@ -7,12 +7,361 @@ pub(in crate::layout) struct FloatBox {
 }
 
 /// Data kept during layout about the floats in a given block formatting context.
+///
+/// All positions stored here are relative to the content box of the block
+/// formatting context root. Boxes nested inside the formatting context
+/// report their own position through `containing_block_start`, so that floats
+/// can be placed (and line boxes shortened) in a common coordinate system.
 pub(in crate::layout) struct FloatContext {
-    // TODO
+    /// The margin boxes of all floats placed so far, in tree order.
+    floats: Vec<PlacedFloat>,
+
+    /// https://drafts.csswg.org/css2/visuren.html#float-rules, rules 5 and 6:
+    /// the outer top of a float may not be higher than the outer top of any
+    /// float placed earlier.
+    ceiling: Length,
+
+    /// Start corner of the content box of the current containing block.
+    pub containing_block_start: Vec2<Length>,
+
+    /// Inline size of the content box of the current containing block.
+    pub containing_block_inline_size: Length,
+
+    /// Block position (relative to the formatting context root) just after the
+    /// last in-flow box laid out so far, not counting its end margin.
+    pub current_block_position: Length,
+
+    /// Margins that have not been resolved yet at `current_block_position`.
+    pub current_margin: CollapsedMargin,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PlacedFloat {
+    side: Float,
+    inline_start: Length,
+    inline_end: Length,
+    block_start: Length,
+    block_end: Length,
+}
+
+/// The part of the containing block that is not covered by floats
+/// at a given block position.
+#[derive(Clone, Copy, Debug)]
+pub(in crate::layout) struct FloatBand {
+    /// Relative to the start of the current containing block.
+    pub inline_start: Length,
+    pub inline_size: Length,
+}
+
+/// Saved state of a `FloatContext`, restored when leaving a nested block.
+pub(in crate::layout) struct FloatContainingBlock {
+    start: Vec2<Length>,
+    inline_size: Length,
 }
 
 impl FloatContext {
-    pub fn new() -> Self {
-        FloatContext {}
+    pub fn new(containing_block_inline_size: Length) -> Self {
+        FloatContext {
+            floats: Vec::new(),
+            ceiling: Length::zero(),
+            containing_block_start: Vec2::zero(),
+            containing_block_inline_size,
+            current_block_position: Length::zero(),
+            current_margin: CollapsedMargin::zero(),
+        }
+    }
+
+    /// Makes a nested block the current containing block. Returns the
+    /// previous containing block, to be restored with `leave_containing_block`.
+    pub fn enter_containing_block(
+        &mut self,
+        start: Vec2<Length>,
+        inline_size: Length,
+    ) -> FloatContainingBlock {
+        let previous = FloatContainingBlock {
+            start: std::mem::replace(&mut self.containing_block_start, start),
+            inline_size: std::mem::replace(&mut self.containing_block_inline_size, inline_size),
+        };
+        self.current_block_position = self.containing_block_start.block;
+        self.current_margin = CollapsedMargin::zero();
+        previous
+    }
+
+    pub fn leave_containing_block(&mut self, previous: FloatContainingBlock) {
+        self.containing_block_start = previous.start;
+        self.containing_block_inline_size = previous.inline_size;
+    }
+
+    /// The block position at which the border box of the next in-flow box
+    /// would start, given its own block-start margin.
+    pub fn next_block_start(&self, margin_block_start: Length) -> Length {
+        self.current_block_position
+            + self
+                .current_margin
+                .adjoin(&CollapsedMargin::new(margin_block_start))
+                .solve()
+    }
+
+    /// The lowest block end of all floats cleared by `clear`, or `None`
+    /// if there are no such floats.
+    /// https://drafts.csswg.org/css2/visuren.html#flow-control
+    pub fn clearance_position(&self, clear: Clear) -> Option<Length> {
+        self.floats
+            .iter()
+            .filter(|f| match f.side {
+                Float::Left => clear.clears_left(),
+                Float::Right => clear.clears_right(),
+                Float::None => false,
+            })
+            .map(|f| f.block_end)
+            .fold(None, |max: Option<Length>, end| {
+                Some(max.map_or(end, |max| max.max(end)))
+            })
+    }
+
+    /// Clearance is represented as additional block-start margin, large enough
+    /// to move the border box below the floats cleared by `clear`.
+    /// Returns whether the box has clearance.
+    /// https://drafts.csswg.org/css2/visuren.html#clearance
+    pub fn apply_clearance(&self, clear: Clear, margin_block_start: &mut Length) -> bool {
+        match self.clearance_position(clear) {
+            Some(clear_position) if self.next_block_start(*margin_block_start) < clear_position => {
+                *margin_block_start = clear_position
+                    - self.current_block_position
+                    - self.current_margin.min_negative();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The lowest block end of any float placed so far,
+    /// relative to the current containing block.
+    pub fn max_block_end(&self) -> Length {
+        self.floats
+            .iter()
+            .fold(Length::zero(), |max, f| max.max(f.block_end))
+            - self.containing_block_start.block
+    }
+
+    /// The space left by floats for content of the current containing block
+    /// between `block_start` and `block_start + block_size`
+    /// (relative to the formatting context root).
+    pub fn band(&self, block_start: Length, block_size: Length) -> FloatBand {
+        let cb_start = self.containing_block_start.inline;
+        let cb_end = cb_start + self.containing_block_inline_size;
+        let (start, end) = self.limits(block_start, block_size, cb_start, cb_end);
+        FloatBand {
+            inline_start: start - cb_start,
+            inline_size: (end - start).max(Length::zero()),
+        }
+    }
+
+    /// Places a float whose margin box has the given size as high as possible,
+    /// but no higher than `min_block_start`. Returns the start corner of its
+    /// margin box, relative to the current containing block.
+    /// https://drafts.csswg.org/css2/visuren.html#float-position
+    pub fn place_float(
+        &mut self,
+        size: &Vec2<Length>,
+        side: Float,
+        clear: Clear,
+        min_block_start: Length,
+    ) -> Vec2<Length> {
+        let position = self.find_float_position(size, side, clear, min_block_start);
+        self.ceiling = position.block;
+        self.floats.push(PlacedFloat {
+            side,
+            inline_start: position.inline,
+            inline_end: position.inline + size.inline,
+            block_start: position.block,
+            block_end: position.block + size.block,
+        });
+        Vec2 {
+            inline: position.inline - self.containing_block_start.inline,
+            block: position.block - self.containing_block_start.block,
+        }
+    }
+
+    /// Like `place_float`, but does not record the float. The returned position
+    /// is relative to the formatting context root.
+    pub fn find_float_position(
+        &self,
+        size: &Vec2<Length>,
+        side: Float,
+        clear: Clear,
+        min_block_start: Length,
+    ) -> Vec2<Length> {
+        let cb_start = self.containing_block_start.inline;
+        let cb_end = cb_start + self.containing_block_inline_size;
+        let mut block_start = min_block_start.max(self.ceiling);
+        if let Some(clearance) = self.clearance_position(clear) {
+            block_start.max_assign(clearance)
+        }
+        loop {
+            let (start, end) = self.limits(block_start, size.block, cb_start, cb_end);
+            // A float that is wider than the containing block is placed
+            // once it no longer shares a band with other floats.
+            let fits = end - start >= size.inline || (start == cb_start && end == cb_end);
+            if fits {
+                let inline = match side {
+                    Float::Right => end - size.inline,
+                    _ => start,
+                };
+                return Vec2 {
+                    inline,
+                    block: block_start,
+                };
+            }
+            // Move down to the next point where a float ends.
+            match self
+                .floats
+                .iter()
+                .map(|f| f.block_end)
+                .filter(|&end| end > block_start)
+                .fold(None, |min: Option<Length>, end| {
+                    Some(min.map_or(end, |min| min.min(end)))
+                }) {
+                Some(next) => block_start = next,
+                None => {
+                    return Vec2 {
+                        inline: cb_start,
+                        block: block_start,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Inline start and end of the space not covered by floats, in the
+    /// coordinate system of the formatting context root.
+    fn limits(
+        &self,
+        block_start: Length,
+        block_size: Length,
+        mut start: Length,
+        mut end: Length,
+    ) -> (Length, Length) {
+        let block_end = block_start + block_size;
+        for f in &self.floats {
+            let overlaps = if block_size > Length::zero() {
+                f.block_start < block_end && f.block_end > block_start
+            } else {
+                f.block_start <= block_start && f.block_end > block_start
+            };
+            if !overlaps {
+                continue;
+            }
+            match f.side {
+                Float::Left => start.max_assign(f.inline_end),
+                Float::Right => end = end.min(f.inline_start),
+                Float::None => {}
+            }
+        }
+        (start, end)
+    }
+}
+
+impl FloatBox {
+    /// Lays out the contents of the float. The returned fragment is positioned
+    /// so that its margin box starts at the origin; it still needs to be placed
+    /// with `FloatContext::place_float`.
+    /// https://drafts.csswg.org/css2/visudet.html#float-width
+    pub(in crate::layout) fn layout<'a>(
+        &'a self,
+        containing_block: &ContainingBlock,
+        tree_rank: usize,
+        absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    ) -> BoxFragment {
+        let style = &self.style;
+        let cbis = containing_block.inline_size;
+        let padding = style.padding().percentages_relative_to(cbis);
+        let border = style.border_width().percentages_relative_to(cbis);
+        let margin = style
+            .margin()
+            .percentages_relative_to(cbis)
+            .auto_is(Length::zero);
+        let pb = &padding + &border;
+        let content_box_sizes = ContentBoxSizes::new(
+            style,
+            LengthOrAuto::Length(cbis),
+            containing_block.block_size,
+            &pb,
+        );
+        let (inline_size, block_size) = match self.contents.as_replaced() {
+            // https://drafts.csswg.org/css2/visudet.html#float-replaced-width
+            Ok(replaced) => {
+                let size = replaced.used_size_as_if_inline_element(containing_block, style);
+                (size.inline, LengthOrAuto::Length(size.block))
+            }
+            Err(_) => {
+                let inline_size = content_box_sizes.box_size.inline.auto_is(|| {
+                    let available_size = cbis - pb.inline_sum() - margin.inline_sum();
+                    self.contents
+                        .inline_content_sizes(style)
+                        .shrink_to_fit(available_size)
+                });
+                let inline_size = content_box_sizes.clamp_inline(inline_size);
+                let block_size = content_box_sizes
+                    .box_size
+                    .block
+                    .map(|block_size| content_box_sizes.clamp_block(block_size));
+                (inline_size, block_size)
+            }
+        };
+        let containing_block_for_children = ContainingBlock {
+            inline_size,
+            block_size,
+            mode: style.writing_mode(),
+        };
+        // https://drafts.csswg.org/css-writing-modes/#orthogonal-flows
+        assert_eq!(
+            containing_block.mode, containing_block_for_children.mode,
+            "Mixed writing modes are not supported yet"
+        );
+        let flow_children = self.contents.layout(
+            style,
+            &containing_block_for_children,
+            tree_rank,
+            absolutely_positioned_fragments,
+        );
+        let relative_adjustement = relative_adjustement(style, inline_size, block_size);
+        let block_size =
+            block_size.auto_is(|| content_box_sizes.clamp_block(flow_children.block_size));
+        let content_rect = Rect {
+            start_corner: Vec2 {
+                inline: margin.inline_start + pb.inline_start + relative_adjustement.inline,
+                block: margin.block_start + pb.block_start + relative_adjustement.block,
+            },
+            size: Vec2 {
+                inline: inline_size,
+                block: block_size,
+            },
+        };
+        BoxFragment {
+            style: style.clone(),
+            children: flow_children.fragments,
+            content_rect,
+            padding,
+            border,
+            margin,
+            block_margins_collapsed_with_children: CollapsedBlockMargins::zero(),
+        }
+    }
+}
+
+impl BoxFragment {
+    /// Size of the margin box, ignoring relative positioning.
+    pub(in crate::layout) fn margin_box_size(&self) -> Vec2<Length> {
+        Vec2 {
+            inline: self.content_rect.size.inline
+                + self.padding.inline_sum()
+                + self.border.inline_sum()
+                + self.margin.inline_sum(),
+            block: self.content_rect.size.block
+                + self.padding.block_sum()
+                + self.border.block_sum()
+                + self.margin.block_sum(),
+        }
     }
 }
@@ -5,7 +5,7 @@ use crate::graphics_engine::font::Font;
 #[derive(Debug)]
 pub(in crate::layout) struct InlineFormattingContext {
     pub(super) parent_style: Arc<ComputedValues>,
-    pub(super) inline_level_boxes: Vec<Arc<InlineLevelBox>>,
+    pub(in crate::layout) inline_level_boxes: Vec<Arc<InlineLevelBox>>,
 }
 
 #[derive(Debug)]
@@ -16,8 +16,7 @@ pub(in crate::layout) enum InlineLevelBox {
     OutOfFlowFloatBox(FloatBox),
     Atomic {
         style: Arc<ComputedValues>,
-        // FIXME: this should be IndependentFormattingContext:
-        contents: ReplacedContent,
+        contents: IndependentFormattingContext,
     },
 }
 
@@ -35,6 +34,9 @@ pub(in crate::layout) struct TextRun {
     pub parent_style: Arc<ComputedValues>,
     pub text: String,
     pub font: Font,
+    /// The style and font of the part of the text on the first formatted
+    /// line, if `::first-line` rules apply to it.
+    pub first_line: Option<(Arc<ComputedValues>, Font)>,
 }
 
 /// A struct representing the current inline box as it is being assembled.
@@ -77,13 +79,26 @@ struct PartialInlineBoxFragment<'box_tree> {
 
 struct InlineFormattingContextState<'box_tree, 'cb> {
     containing_block: &'cb ContainingBlock,
+    float_context: Option<&'cb mut FloatContext>,
     line_boxes: LinesBoxes,
+    /// Start and size of the part of the current line not covered by floats,
+    /// relative to the containing block.
+    line_inline_start: Length,
+    line_inline_size: Length,
+    /// Block size used to find the space left by floats for a new line.
+    strut_block_size: Length,
+    /// Floats that did not fit on the current line. They are placed
+    /// below it once the line is finished.
+    pending_floats: Vec<(&'box_tree FloatBox, BoxFragment)>,
     /// Alignment setting for text in this inline formatting context.
     text_align: TextAlign,
     /// Current inline position given the boxes remaining on the stack.
     inline_position: Length,
     partial_inline_boxes_stack: Vec<PartialInlineBoxFragment<'box_tree>>,
     current_nesting_level: InlineNestingLevelState<'box_tree>,
+    /// Whether the current line is the first line, where text runs use
+    /// their `::first-line` style.
+    first_line: bool,
 }
 
 struct LinesBoxes {
@@ -104,20 +119,29 @@ impl InlineFormattingContext {
         containing_block: &ContainingBlock,
         tree_rank: usize,
         absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+        float_context: Option<&mut FloatContext>,
     ) -> FlowChildren {
         let font = Font::new_from_computed_values(&self.parent_style);
         let block_ascent = font.get_ascent();
         let block_descent = font.get_descent();
         let block_ex = font.get_ex();
+        let (strut_ascent, strut_descent) =
+            calculate_line_ascent_descent(&self.parent_style, &font);
         let mut ifc = InlineFormattingContextState {
             containing_block,
+            float_context,
             partial_inline_boxes_stack: Vec::new(),
             line_boxes: LinesBoxes {
                 boxes: Vec::new(),
                 next_line_block_position: Length::zero(),
             },
+            line_inline_start: Length::zero(),
+            line_inline_size: containing_block.inline_size,
+            strut_block_size: strut_ascent + strut_descent,
+            pending_floats: Vec::new(),
             text_align: self.parent_style.text_inherited.text_align,
             inline_position: Length::zero(),
+            first_line: true,
             current_nesting_level: InlineNestingLevelState {
                 remaining_boxes: self.inline_level_boxes.iter(),
                 fragments_so_far: Vec::with_capacity(self.inline_level_boxes.len()),
@@ -130,6 +154,7 @@ impl InlineFormattingContext {
                 max_block_descent_of_fragments_so_far: Length::zero(),
             },
         };
+        ifc.update_line_band();
         loop {
             if let Some(child) = ifc.current_nesting_level.remaining_boxes.next() {
                 match &**child {
@@ -138,15 +163,19 @@ impl InlineFormattingContext {
                         ifc.partial_inline_boxes_stack.push(partial)
                     }
                     InlineLevelBox::TextRun(run) => run.layout(&mut ifc),
-                    InlineLevelBox::Atomic { style: _, contents } => {
-                        // FIXME
-                        match *contents {}
+                    InlineLevelBox::Atomic { style, contents } => {
+                        ifc.layout_atomic(
+                            style,
+                            contents,
+                            tree_rank,
+                            absolutely_positioned_fragments,
+                        )
                     }
                     InlineLevelBox::OutOfFlowAbsolutelyPositionedBox(box_) => {
                         let initial_start_corner = match box_.style.specified_display {
                             Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
                                 outside,
-                                inside: _,
+                                ..
                             }) => Vec2 {
                                 inline: match outside {
                                     DisplayOutside::Inline => ifc.inline_position,
@@ -154,6 +183,13 @@ impl InlineFormattingContext {
                                 },
                                 block: ifc.line_boxes.next_line_block_position,
                             },
+                            // Table-internal boxes were originally block-level.
+                            Display::GeneratingBox(DisplayGeneratingBox::LayoutInternal(_)) => {
+                                Vec2 {
+                                    inline: Length::zero(),
+                                    block: ifc.line_boxes.next_line_block_position,
+                                }
+                            }
                             Display::Contents => {
                                 panic!("display:contents does not generate an abspos box")
                             }
@@ -162,9 +198,10 @@ impl InlineFormattingContext {
                         absolutely_positioned_fragments
                             .push(box_.layout(initial_start_corner, tree_rank));
                     }
-                    InlineLevelBox::OutOfFlowFloatBox(_box_) => {
-                        // TODO
-                        continue;
+                    InlineLevelBox::OutOfFlowFloatBox(box_) => {
+                        let fragment =
+                            box_.layout(containing_block, tree_rank, absolutely_positioned_fragments);
+                        ifc.layout_float(box_, fragment);
                     }
                 }
             } else { // no more boxes in the current nesting level
@@ -194,6 +231,204 @@ impl InlineFormattingContext {
 }
 
 impl<'box_tree, 'cb> InlineFormattingContextState<'box_tree, 'cb> {
+    /// Block position of the current line, relative to the formatting context root.
+    fn line_block_start(&self, float_context: &FloatContext) -> Length {
+        float_context.containing_block_start.block + self.line_boxes.next_line_block_position
+    }
+
+    /// Recompute the space left by floats for the current line.
+    fn update_line_band(&mut self) {
+        let band = match &self.float_context {
+            Some(float_context) => {
+                float_context.band(self.line_block_start(float_context), self.strut_block_size)
+            }
+            None => return,
+        };
+        self.line_inline_start = band.inline_start;
+        self.line_inline_size = band.inline_size;
+    }
+
+    /// Place a float encountered in the middle of a line. It goes on the current
+    /// line if it fits there, otherwise it is deferred until the line is finished.
+    /// https://drafts.csswg.org/css2/visuren.html#float-position
+    fn layout_float(&mut self, box_: &'box_tree FloatBox, fragment: BoxFragment) {
+        let float_context = match &self.float_context {
+            Some(float_context) => &**float_context,
+            // Box construction should have created a float context
+            None => return,
+        };
+        let line_block_start = self.line_block_start(float_context);
+        let size = fragment.margin_box_size();
+        let position = float_context.find_float_position(
+            &size,
+            box_.style.box_.float,
+            box_.style.box_.clear,
+            line_block_start,
+        );
+        let fits_on_line = position.block == line_block_start
+            && size.inline <= self.line_inline_size - self.inline_position;
+        if fits_on_line && self.pending_floats.is_empty() {
+            self.place_float(box_, fragment, line_block_start);
+            self.update_line_band();
+        } else {
+            self.pending_floats.push((box_, fragment))
+        }
+    }
+
+    /// Lays out an atomic inline-level box: a replaced element or an `inline-block`.
+    /// https://drafts.csswg.org/css2/visudet.html#inline-replaced-width
+    /// https://drafts.csswg.org/css2/visudet.html#inlineblock-width
+    fn layout_atomic(
+        &mut self,
+        style: &Arc<ComputedValues>,
+        contents: &'box_tree IndependentFormattingContext,
+        tree_rank: usize,
+        absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'box_tree>>,
+    ) {
+        let cbis = self.containing_block.inline_size;
+        let padding = style.padding().percentages_relative_to(cbis);
+        let border = style.border_width().percentages_relative_to(cbis);
+        let margin = style
+            .margin()
+            .percentages_relative_to(cbis)
+            .auto_is(Length::zero);
+        let pb = &padding + &border;
+
+        let (size, children, content_baseline) = match contents.as_replaced() {
+            Ok(replaced) => {
+                let size = replaced.used_size_as_if_inline_element(self.containing_block, style);
+                let children = replaced.make_fragments(style, size.clone());
+                (size, children, None)
+            }
+            Err(_) => {
+                let content_box_sizes = ContentBoxSizes::new(
+                    style,
+                    LengthOrAuto::Length(cbis),
+                    self.containing_block.block_size,
+                    &pb,
+                );
+                let inline_size = content_box_sizes.box_size.inline.auto_is(|| {
+                    let available_size = cbis - pb.inline_sum() - margin.inline_sum();
+                    contents
+                        .inline_content_sizes(style)
+                        .shrink_to_fit(available_size)
+                });
+                let inline_size = content_box_sizes.clamp_inline(inline_size);
+                let block_size = content_box_sizes
+                    .box_size
+                    .block
+                    .map(|block_size| content_box_sizes.clamp_block(block_size));
+                let containing_block_for_children = ContainingBlock {
+                    inline_size,
+                    block_size,
+                    mode: style.writing_mode(),
+                };
+                // https://drafts.csswg.org/css-writing-modes/#orthogonal-flows
+                assert_eq!(
+                    self.containing_block.mode, containing_block_for_children.mode,
+                    "Mixed writing modes are not supported yet"
+                );
+                let mut nested_abspos = vec![];
+                let mut flow_children = contents.layout(
+                    style,
+                    &containing_block_for_children,
+                    tree_rank,
+                    if style.box_.position.is_relatively_positioned() {
+                        &mut nested_abspos
+                    } else {
+                        absolutely_positioned_fragments
+                    },
+                );
+                let size = Vec2 {
+                    inline: inline_size,
+                    block: block_size
+                        .auto_is(|| content_box_sizes.clamp_block(flow_children.block_size)),
+                };
+                let content_baseline = last_baseline(&flow_children.fragments);
+                if style.box_.position.is_relatively_positioned() {
+                    AbsolutelyPositionedFragment::in_positioned_containing_block(
+                        &nested_abspos,
+                        &mut flow_children.fragments,
+                        &size,
+                        &padding,
+                        containing_block_for_children.mode,
+                    )
+                }
+                (size, flow_children.fragments, content_baseline)
+            }
+        };
+
+        let margin_box_size = Vec2 {
+            inline: size.inline + pb.inline_sum() + margin.inline_sum(),
+            block: size.block + pb.block_sum() + margin.block_sum(),
+        };
+        // The baseline of an `inline-block` is that of its last line box,
+        // otherwise (or if its `overflow` is not `visible`) the box sits on
+        // the baseline with its bottom margin edge.
+        // https://drafts.csswg.org/css2/visudet.html#propdef-vertical-align
+        let block_ascent = match content_baseline {
+            Some(baseline) if style.box_.overflow == Overflow::Visible => {
+                margin.block_start + pb.block_start + baseline
+            }
+            _ => margin_box_size.block,
+        };
+        let block_descent = margin_box_size.block - block_ascent;
+
+        // Move to the next line if the box doesn't fit on a line that isn't empty
+        if self.inline_position > Length::zero()
+            && self.inline_position + margin_box_size.inline > self.line_inline_size
+        {
+            self.finish_line();
+        }
+
+        let nesting_level = &mut self.current_nesting_level;
+        let mut start_corner = Vec2 {
+            inline: self.inline_position - nesting_level.inline_start
+                + margin.inline_start
+                + pb.inline_start,
+            block: nesting_level.block_ascent - block_ascent + margin.block_start + pb.block_start,
+        };
+        start_corner += &relative_adjustement(
+            style,
+            self.containing_block.inline_size,
+            self.containing_block.block_size,
+        );
+        nesting_level
+            .max_block_ascent_of_fragments_so_far
+            .max_assign(block_ascent - nesting_level.block_baseline_adjustment);
+        nesting_level
+            .max_block_descent_of_fragments_so_far
+            .max_assign(block_descent + nesting_level.block_baseline_adjustment);
+        nesting_level.fragments_so_far.push(Fragment::Box(BoxFragment {
+            style: style.clone(),
+            children,
+            content_rect: Rect { start_corner, size },
+            padding,
+            border,
+            margin,
+            block_margins_collapsed_with_children: CollapsedBlockMargins::zero(),
+        }));
+        self.inline_position += margin_box_size.inline;
+    }
+
+    fn place_float(
+        &mut self,
+        box_: &FloatBox,
+        mut fragment: BoxFragment,
+        min_block_start: Length,
+    ) {
+        if let Some(float_context) = &mut self.float_context {
+            let position = float_context.place_float(
+                &fragment.margin_box_size(),
+                box_.style.box_.float,
+                box_.style.box_.clear,
+                min_block_start,
+            );
+            fragment.content_rect.start_corner += &position;
+            self.line_boxes.boxes.push(Fragment::Box(fragment))
+        }
+    }
+
     /// Finish off the current line and reset.
     fn finish_line(&mut self) {
         // To complete a line, we need to iterate over all open boxes in reverse
@@ -219,10 +454,20 @@ impl<'box_tree, 'cb> InlineFormattingContextState<'box_tree, 'cb> {
             .finish_line(
                 nesting_level,
                 self.containing_block,
+                self.line_inline_start,
+                self.line_inline_size,
                 &self.inline_position,
                 &self.text_align,
             );
         self.inline_position = Length::zero();
+        self.first_line = false;
+        if let Some(float_context) = &self.float_context {
+            let line_block_start = self.line_block_start(float_context);
+            for (box_, fragment) in take(&mut self.pending_floats) {
+                self.place_float(box_, fragment, line_block_start)
+            }
+        }
+        self.update_line_band();
     }
 }
 
@@ -234,11 +479,13 @@ impl LinesBoxes {
         &mut self,
         top_nesting_level: &mut InlineNestingLevelState,
         containing_block: &ContainingBlock,
+        line_inline_start: Length,
+        line_inline_size: Length,
         inline_position: &Length,
         text_align: &TextAlign,
     ) {
         // available line length minus used line length
-        let inline_delta = containing_block.inline_size - *inline_position;
+        let inline_delta = line_inline_size - *inline_position;
         // fractional shift
         let p = match text_align {
             TextAlign::Left => 0.0,
@@ -250,7 +497,7 @@ impl LinesBoxes {
         };
 
         let start_corner = Vec2 {
-            inline: inline_delta * p,
+            inline: line_inline_start + inline_delta * p,
             block: self.next_line_block_position,
         };
         let max_block_ascent = std::mem::replace(
@@ -262,7 +509,7 @@ impl LinesBoxes {
             Length::zero(),
         );
         let size = Vec2 {
-            inline: containing_block.inline_size,
+            inline: line_inline_size,
             block: max_block_ascent + max_block_descent,
         };
         self.next_line_block_position += size.block;
@@ -279,6 +526,7 @@ impl LinesBoxes {
             children: take(&mut top_nesting_level.fragments_so_far),
             rect: Rect { start_corner, size },
             mode: containing_block.mode,
+            baseline: Some(max_block_ascent),
         }));
     }
 }
@@ -408,14 +656,28 @@ impl TextRun {
         };
     }
 
+    /// The style and font of the text on the current line.
+    fn style_and_font(&self, first_line: bool) -> (&Arc<ComputedValues>, &Font) {
+        match &self.first_line {
+            Some((style, font)) if first_line => (style, font),
+            _ => (&self.parent_style, &self.font),
+        }
+    }
+
     /// Text layout with word wrap.
     fn layout_wrap(&self, ifc: &mut InlineFormattingContextState) {
         let mut chars = self.text.chars();
         let mut shaped = ShapedSegment::new(self.font.clone());
         let mut last_break_opportunity = Some((shaped.save(), chars.clone()));
         loop { // loop over lines
+            let (style, font) = self.style_and_font(ifc.first_line);
+            if shaped.empty() {
+                shaped = ShapedSegment::new(font.clone());
+            }
             let mut newline = false;
-            let available = ifc.containing_block.inline_size - ifc.inline_position;
+            // Allow for rounding errors, so that text laid out at its
+            // max-content size (e.g. in table cells) does not wrap.
+            let available = ifc.line_inline_size - ifc.inline_position + Length { px: 0.01 };
             loop { // loop over text within lines
                 let next = chars.next();
                 if matches!(next, Some(' ') | Some('\n') | Some('-') | None) {
@@ -449,10 +711,11 @@ impl TextRun {
                 }
             }
             let inline_size = shaped.get_advance_width().unwrap().into(); // TODO: handle potential error nicely, don't just unwrap()
-            let line_ascent_descent = calculate_line_ascent_descent(&*self.parent_style, &self.font);
+            let line_ascent_descent = calculate_line_ascent_descent(style, font);
+            // Text in its `::first-line` font sits on the baseline of the nesting level.
             let content_rect = Rect {
                 start_corner: Vec2 {
-                    block: Length::zero(),
+                    block: ifc.current_nesting_level.block_ascent - font.get_ascent(),
                     inline: ifc.inline_position - ifc.current_nesting_level.inline_start,
                 },
                 size: Vec2 {
@@ -480,11 +743,11 @@ impl TextRun {
                 ifc.current_nesting_level
                     .fragments_so_far
                     .push(Fragment::Text(TextFragment {
-                        parent_style: self.parent_style.clone(),
+                        parent_style: style.clone(),
                         content_rect,
                         text: std::mem::replace(
                             &mut shaped,
-                            ShapedSegment::new(self.font.clone())
+                            ShapedSegment::new(font.clone())
                         )
                     }));
             }
@@ -502,8 +765,9 @@ impl TextRun {
     fn layout_nowrap(&self, ifc: &mut InlineFormattingContextState) {
         let mut chars = self.text.chars();
         loop {
+            let (style, font) = self.style_and_font(ifc.first_line);
             let mut newline = false;
-            let mut shaped = ShapedSegment::new( self.font.clone());
+            let mut shaped = ShapedSegment::new(font.clone());
             loop {
                 let next = chars.next();
                 match next {
@@ -518,10 +782,11 @@ impl TextRun {
                 }
             }
             let inline_size = shaped.get_advance_width().unwrap().into(); // TODO: handle potential error nicely, don't just unwrap()
-            let line_ascent_descent = calculate_line_ascent_descent(&*self.parent_style, &self.font);
+            let line_ascent_descent = calculate_line_ascent_descent(style, font);
+            // Text in its `::first-line` font sits on the baseline of the nesting level.
             let content_rect = Rect {
                 start_corner: Vec2 {
-                    block: Length::zero(),
+                    block: ifc.current_nesting_level.block_ascent - font.get_ascent(),
                     inline: ifc.inline_position - ifc.current_nesting_level.inline_start,
                 },
                 size: Vec2 {
@@ -549,7 +814,7 @@ impl TextRun {
                 ifc.current_nesting_level
                     .fragments_so_far
                     .push(Fragment::Text(TextFragment {
-                        parent_style: self.parent_style.clone(),
+                        parent_style: style.clone(),
                         content_rect,
                         text: shaped,
                     }));
@@ -567,6 +832,24 @@ impl TextRun {
 
 // convenience helper functions
 
+/// Block position of the baseline of the last line box among the given in-flow
+/// fragments, relative to their containing block.
+fn last_baseline(fragments: &[Fragment]) -> Option<Length> {
+    fragments.iter().rev().find_map(|fragment| match fragment {
+        Fragment::Box(b) => {
+            if b.style.box_.float.is_floating() || b.style.box_.position.is_absolutely_positioned() {
+                return None;
+            }
+            last_baseline(&b.children).map(|baseline| b.content_rect.start_corner.block + baseline)
+        }
+        Fragment::Anonymous(a) => a
+            .baseline
+            .or_else(|| last_baseline(&a.children))
+            .map(|baseline| a.rect.start_corner.block + baseline),
+        Fragment::Text(_) | Fragment::Image(_) => None,
+    })
+}
+
 /// Calculate the line ascent and descent (font ascent/descent + half leading)
 /// https://drafts.csswg.org/css2/#leading
 fn calculate_line_ascent_descent(style: &ComputedValues, font: &Font) -> (Length, Length) {
@@ -35,6 +35,10 @@ pub(super) enum BlockLevelBox {
         style: Arc<ComputedValues>,
         contents: IndependentFormattingContext,
     },
+    /// The `::marker` of a list item with `list-style-position: outside`.
+    OutsideMarker {
+        contents: BlockContainer,
+    },
 }
 
 pub(super) struct FlowChildren {
@@ -53,10 +57,8 @@ impl BlockFormattingContext {
         tree_rank: usize,
         absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
     ) -> FlowChildren {
-        let mut float_context;
-        let float_context = if self.contains_floats {
-            float_context = FloatContext::new();
-            Some(&mut float_context)
+        let mut float_context = if self.contains_floats {
+            Some(FloatContext::new(containing_block.inline_size))
         } else {
             None
         };
@@ -64,10 +66,16 @@ impl BlockFormattingContext {
             containing_block,
             tree_rank,
             absolutely_positioned_fragments,
-            float_context,
+            float_context.as_mut(),
             CollapsibleWithParentStartMargin(false),
         );
         flow_children.block_size += flow_children.collapsible_margins_in_children.end.solve();
+        if let Some(float_context) = &float_context {
+            // https://drafts.csswg.org/css2/visudet.html#root-height
+            flow_children
+                .block_size
+                .max_assign(float_context.max_block_end());
+        }
         flow_children
             .collapsible_margins_in_children
             .collapsed_through = false;
@@ -94,9 +102,12 @@ impl BlockContainer {
                 float_context,
                 collapsible_with_parent_start_margin,
             ),
-            BlockContainer::InlineFormattingContext(ifc) => {
-                ifc.layout(containing_block, tree_rank, absolutely_positioned_fragments)
-            }
+            BlockContainer::InlineFormattingContext(ifc) => ifc.layout(
+                containing_block,
+                tree_rank,
+                absolutely_positioned_fragments,
+                float_context,
+            ),
         }
     }
 }
@@ -117,6 +128,11 @@ fn layout_block_level_children<'a>(
                     + fragment.border.block_sum()
                     + fragment.content_rect.size.block;
 
+                // Clearance separates the margins of a box from those before it,
+                // including the start margin of its parent.
+                if fragment_block_margins.has_clearance {
+                    placement_state.next_in_flow_margin_collapses_with_parent_start_margin = false;
+                }
                 if placement_state.next_in_flow_margin_collapses_with_parent_start_margin {
                     assert_eq!(placement_state.current_margin.solve(), Length::zero());
                     placement_state
@@ -182,13 +198,23 @@ fn layout_block_level_children<'a>(
             .iter()
             .enumerate()
             .map(|(tree_rank, box_)| {
+                float_context.current_block_position = float_context.containing_block_start.block
+                    + placement_state.current_block_direction_position;
+                float_context.current_margin =
+                    if placement_state.next_in_flow_margin_collapses_with_parent_start_margin {
+                        CollapsedMargin::zero()
+                    } else {
+                        placement_state.current_margin
+                    };
                 let mut fragment = box_.layout(
                     containing_block,
                     tree_rank,
                     absolutely_positioned_fragments,
                     Some(float_context),
                 );
-                place_block_level_fragment(&mut fragment, &mut placement_state);
+                if box_.is_in_flow() {
+                    place_block_level_fragment(&mut fragment, &mut placement_state);
+                }
                 fragment
             })
             .collect()
@@ -205,8 +231,10 @@ fn layout_block_level_children<'a>(
                 )
             })
             .collect();
-        for fragment in &mut fragments {
-            place_block_level_fragment(fragment, &mut placement_state)
+        for (box_, fragment) in child_boxes.iter().zip(&mut fragments) {
+            if box_.is_in_flow() {
+                place_block_level_fragment(fragment, &mut placement_state)
+            }
         }
     }
 
@@ -220,6 +248,7 @@ fn layout_block_level_children<'a>(
         fragments,
         block_size: placement_state.current_block_direction_position,
         collapsible_margins_in_children: CollapsedBlockMargins {
+            has_clearance: false,
             collapsed_through: placement_state
                 .next_in_flow_margin_collapses_with_parent_start_margin,
             start: placement_state.start_margin,
@@ -229,7 +258,16 @@ fn layout_block_level_children<'a>(
 }
 
 impl BlockLevelBox {
-    fn layout<'a>(
+    /// Floats are placed by the float context, and outside markers next to
+    /// the first line of their list item: neither takes space in the flow.
+    fn is_in_flow(&self) -> bool {
+        !matches!(
+            self,
+            BlockLevelBox::OutOfFlowFloatBox(_) | BlockLevelBox::OutsideMarker { .. }
+        )
+    }
+
+    pub(super) fn layout<'a>(
         &'a self,
         containing_block: &ContainingBlock,
         tree_rank: usize,
@@ -241,9 +279,14 @@ impl BlockLevelBox {
                 Fragment::Box(layout_in_flow_non_replaced_block_level(
                     containing_block,
                     absolutely_positioned_fragments,
+                    float_context,
                     style,
                     BlockLevelKind::SameFormattingContextBlock,
-                    |containing_block, nested_abspos, collapsible_with_parent_start_margin| {
+                    None,
+                    |containing_block,
+                     nested_abspos,
+                     float_context,
+                     collapsible_with_parent_start_margin| {
                         contents.layout(
                             containing_block,
                             tree_rank,
@@ -255,16 +298,26 @@ impl BlockLevelBox {
                 ))
             }
             BlockLevelBox::Independent { style, contents } => match contents.as_replaced() {
-                Ok(replaced) => {
-                    // FIXME
-                    match *replaced {}
-                }
+                Ok(replaced) => Fragment::Box(layout_in_flow_replaced_block_level(
+                    containing_block,
+                    float_context,
+                    style,
+                    replaced,
+                )),
                 Err(contents) => Fragment::Box(layout_in_flow_non_replaced_block_level(
                     containing_block,
                     absolutely_positioned_fragments,
+                    float_context,
                     style,
                     BlockLevelKind::EstablishesAnIndependentFormattingContext,
-                    |containing_block, nested_abspos, _| {
+                    match contents {
+                        NonReplacedIFC::Table(table) => Some(table.inline_content_sizes()),
+                        NonReplacedIFC::Flow(_)
+                        | NonReplacedIFC::Flex(_)
+                        | NonReplacedIFC::Grid(_)
+                        | NonReplacedIFC::Multicol(_) => None,
+                    },
+                    |containing_block, nested_abspos, _, _| {
                         contents.layout(containing_block, tree_rank, nested_abspos)
                     },
                 )),
@@ -273,15 +326,70 @@ impl BlockLevelBox {
                 absolutely_positioned_fragments.push(box_.layout(Vec2::zero(), tree_rank));
                 Fragment::Anonymous(AnonymousFragment::no_op(containing_block.mode))
             }
-            BlockLevelBox::OutOfFlowFloatBox(_box_) => {
-                // TODO
-                Fragment::Anonymous(AnonymousFragment::no_op(containing_block.mode))
+            BlockLevelBox::OutOfFlowFloatBox(box_) => match float_context {
+                Some(float_context) => {
+                    let mut fragment =
+                        box_.layout(containing_block, tree_rank, absolutely_positioned_fragments);
+                    let position = float_context.place_float(
+                        &fragment.margin_box_size(),
+                        box_.style.box_.float,
+                        box_.style.box_.clear,
+                        float_context.next_block_start(Length::zero()),
+                    );
+                    fragment.content_rect.start_corner += &position;
+                    Fragment::Box(fragment)
+                }
+                // Box construction should have created a float context
+                None => Fragment::Anonymous(AnonymousFragment::no_op(containing_block.mode)),
+            },
+            BlockLevelBox::OutsideMarker { contents } => {
+                let flow_children = contents.layout(
+                    containing_block,
+                    tree_rank,
+                    absolutely_positioned_fragments,
+                    /* float_context = */ None,
+                    CollapsibleWithParentStartMargin(false),
+                );
+                // The marker box ends where the content box of the list item starts.
+                // https://drafts.csswg.org/css-lists-3/#list-style-position-outside
+                let inline_end = max_inline_end(&flow_children.fragments);
+                Fragment::Anonymous(AnonymousFragment {
+                    rect: Rect {
+                        start_corner: Vec2 {
+                            inline: -inline_end,
+                            block: Length::zero(),
+                        },
+                        size: Vec2 {
+                            inline: inline_end,
+                            block: flow_children.block_size,
+                        },
+                    },
+                    children: flow_children.fragments,
+                    mode: containing_block.mode,
+                    baseline: None,
+                })
             }
         }
     }
 }
 
-#[derive(PartialEq)]
+/// The inline end of the content of `fragments`, ignoring the size of line boxes.
+fn max_inline_end(fragments: &[Fragment]) -> Length {
+    fragments
+        .iter()
+        .map(|fragment| match fragment {
+            Fragment::Box(b) => {
+                let rect = b.margin_rect();
+                rect.start_corner.inline + rect.size.inline
+            }
+            Fragment::Anonymous(a) => a.rect.start_corner.inline + max_inline_end(&a.children),
+            Fragment::Text(t) => t.content_rect.start_corner.inline + t.content_rect.size.inline,
+            Fragment::Image(i) => i.clip_rect.start_corner.inline + i.clip_rect.size.inline,
+        })
+        .fold(Length::zero(), Length::max)
+}
+
+#[derive(Clone, Copy, PartialEq)]
 enum BlockLevelKind {
     SameFormattingContextBlock,
     EstablishesAnIndependentFormattingContext,
@@ -292,11 +400,14 @@ enum BlockLevelKind {
 fn layout_in_flow_non_replaced_block_level<'a>(
     containing_block: &ContainingBlock,
     absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    mut float_context: Option<&mut FloatContext>,
     style: &Arc<ComputedValues>,
     block_level_kind: BlockLevelKind,
+    shrink_to_fit_content_sizes: Option<ContentSizes>,
     layout_contents: impl FnOnce(
         &ContainingBlock,
         &mut Vec<AbsolutelyPositionedFragment<'a>>,
+        Option<&mut FloatContext>,
         CollapsibleWithParentStartMargin,
     ) -> FlowChildren,
 ) -> BoxFragment {
@@ -305,40 +416,78 @@ fn layout_in_flow_non_replaced_block_level<'a>(
     let border = style.border_width().percentages_relative_to(cbis);
     let mut computed_margin = style.margin().percentages_relative_to(cbis);
     let pb = &padding + &border;
-    let box_size = style.box_size();
-    let inline_size = box_size.inline.percentage_relative_to(cbis);
-    if let LengthOrAuto::Length(is) = inline_size {
-        let inline_margins = cbis - is - pb.inline_sum();
-        use LengthOrAuto::*;
-        match (
-            &mut computed_margin.inline_start,
-            &mut computed_margin.inline_end,
-        ) {
-            (s @ &mut Auto, e @ &mut Auto) => {
-                *s = Length(inline_margins / 2.);
-                *e = Length(inline_margins / 2.);
-            }
-            (s @ &mut Auto, _) => {
-                *s = Length(inline_margins);
-            }
-            (_, e @ &mut Auto) => {
-                *e = Length(inline_margins);
-            }
-            (_, e @ _) => {
-                // Either the inline-end margin is auto,
-                // or we’re over-constrained and we do as if it were.
-                *e = Length(inline_margins);
-            }
+    let content_box_sizes = ContentBoxSizes::new(
+        style,
+        LengthOrAuto::Length(cbis),
+        containing_block.block_size,
+        &pb,
+    );
+    let inline_size = content_box_sizes.box_size.inline;
+
+    // The border box of a block establishing an independent formatting context
+    // must not overlap the margin box of any float in the same formatting context.
+    // https://drafts.csswg.org/css2/visuren.html#bfc-next-to-float
+    let band = match &float_context {
+        Some(float_context)
+            if block_level_kind == BlockLevelKind::EstablishesAnIndependentFormattingContext =>
+        {
+            let margin_block_start = computed_margin.block_start.auto_is(Length::zero);
+            float_context.band(
+                float_context.next_block_start(margin_block_start),
+                Length::zero(),
+            )
+        }
+        _ => FloatBand {
+            inline_start: Length::zero(),
+            inline_size: cbis,
+        },
+    };
+    let available_inline_size = band.inline_size;
+
+    // Tables with `width: auto` shrink to fit their contents.
+    // https://drafts.csswg.org/css2/tables.html#auto-table-layout
+    let inline_size = match (inline_size, shrink_to_fit_content_sizes) {
+        (LengthOrAuto::Auto, Some(content_sizes)) => {
+            let margins = computed_margin.inline_start.auto_is(Length::zero)
+                + computed_margin.inline_end.auto_is(Length::zero);
+            LengthOrAuto::Length(
+                content_sizes.shrink_to_fit(available_inline_size - pb.inline_sum() - margins),
+            )
         }
+        (inline_size, _) => inline_size,
+    };
+    // The tentative inline size is clamped between the minimum and maximum,
+    // auto margins then being solved as if the result had been specified.
+    // https://drafts.csswg.org/css2/visudet.html#min-max-widths
+    let inline_size = {
+        let tentative_inline_size = inline_size.auto_is(|| {
+            let margins = computed_margin.inline_start.auto_is(Length::zero)
+                + computed_margin.inline_end.auto_is(Length::zero);
+            available_inline_size - pb.inline_sum() - margins
+        });
+        let clamped_inline_size = content_box_sizes.clamp_inline(tentative_inline_size);
+        if clamped_inline_size != tentative_inline_size {
+            LengthOrAuto::Length(clamped_inline_size)
+        } else {
+            inline_size
+        }
+    };
+    if let LengthOrAuto::Length(is) = inline_size {
+        let inline_margins = available_inline_size - is - pb.inline_sum();
+        solve_auto_inline_margins(&mut computed_margin, inline_margins);
     }
-    let margin = computed_margin.auto_is(Length::zero);
+    let mut margin = computed_margin.auto_is(Length::zero);
+    let has_clearance = float_context.as_ref().is_some_and(|float_context| {
+        float_context.apply_clearance(style.box_.clear, &mut margin.block_start)
+    });
     let mut block_margins_collapsed_with_children = CollapsedBlockMargins::from_margin(&margin);
-    let inline_size = inline_size.auto_is(|| cbis - pb.inline_sum() - margin.inline_sum());
-    let block_size = match box_size.block {
-        LengthOrPercentageOrAuto::Length(l) => LengthOrAuto::Length(l),
-        LengthOrPercentageOrAuto::Percentage(p) => containing_block.block_size.map(|cbbs| cbbs * p),
-        LengthOrPercentageOrAuto::Auto => LengthOrAuto::Auto,
-    };
+    block_margins_collapsed_with_children.has_clearance = has_clearance;
+    let inline_size =
+        inline_size.auto_is(|| available_inline_size - pb.inline_sum() - margin.inline_sum());
+    let block_size = content_box_sizes
+        .box_size
+        .block
+        .map(|block_size| content_box_sizes.clamp_block(block_size));
     let containing_block_for_children = ContainingBlock {
         inline_size,
         block_size,
@@ -358,8 +507,22 @@ fn layout_in_flow_non_replaced_block_level<'a>(
             BlockLevelKind::SameFormattingContextBlock,
             Length::zero(),
             LengthOrAuto::Auto,
-        );
+        )
+        && content_box_sizes.min_box_size.block == Length::zero();
     let mut nested_abspos = vec![];
+    let mut float_containing_block = None;
+    if let Some(float_context) = &mut float_context {
+        if block_level_kind == BlockLevelKind::SameFormattingContextBlock {
+            let start = Vec2 {
+                inline: float_context.containing_block_start.inline
+                    + margin.inline_start
+                    + pb.inline_start,
+                block: float_context.next_block_start(margin.block_start) + pb.block_start,
+            };
+            float_containing_block =
+                Some(float_context.enter_containing_block(start, inline_size));
+        }
+    }
     let mut flow_children = layout_contents(
         &containing_block_for_children,
         if style.box_.position.is_relatively_positioned() {
@@ -367,8 +530,12 @@ fn layout_in_flow_non_replaced_block_level<'a>(
         } else {
             absolutely_positioned_fragments
         },
+        float_context.as_mut().map(|float_context| &mut **float_context),
         this_start_margin_can_collapse_with_children,
     );
+    if let (Some(float_context), Some(previous)) = (float_context, float_containing_block) {
+        float_context.leave_containing_block(previous);
+    }
     if this_start_margin_can_collapse_with_children.0 {
         block_margins_collapsed_with_children
             .start
@@ -399,11 +566,15 @@ fn layout_in_flow_non_replaced_block_level<'a>(
                 .collapsible_margins_in_children
                 .collapsed_through;
     let relative_adjustement = relative_adjustement(style, inline_size, block_size);
-    let block_size = block_size.auto_is(|| flow_children.block_size);
+    let block_size =
+        block_size.auto_is(|| content_box_sizes.clamp_block(flow_children.block_size));
     let content_rect = Rect {
         start_corner: Vec2 {
             block: pb.block_start + relative_adjustement.block,
-            inline: pb.inline_start + relative_adjustement.inline + margin.inline_start,
+            inline: band.inline_start
+                + pb.inline_start
+                + relative_adjustement.inline
+                + margin.inline_start,
         },
         size: Vec2 {
             block: block_size,
@@ -429,3 +600,92 @@ fn layout_in_flow_non_replaced_block_level<'a>(
         block_margins_collapsed_with_children,
     }
 }
+
+/// https://drafts.csswg.org/css2/visudet.html#block-replaced-width
+/// https://drafts.csswg.org/css2/visudet.html#inline-replaced-height
+fn layout_in_flow_replaced_block_level(
+    containing_block: &ContainingBlock,
+    float_context: Option<&mut FloatContext>,
+    style: &Arc<ComputedValues>,
+    replaced: &ReplacedContent,
+) -> BoxFragment {
+    let cbis = containing_block.inline_size;
+    let padding = style.padding().percentages_relative_to(cbis);
+    let border = style.border_width().percentages_relative_to(cbis);
+    let mut computed_margin = style.margin().percentages_relative_to(cbis);
+    let pb = &padding + &border;
+    let size = replaced.used_size_as_if_inline_element(containing_block, style);
+    let mut margin_block_start = computed_margin.block_start.auto_is(Length::zero);
+    let has_clearance = float_context.as_ref().is_some_and(|float_context| {
+        float_context.apply_clearance(style.box_.clear, &mut margin_block_start)
+    });
+    // Like boxes establishing an independent formatting context, the border box
+    // must not overlap the margin box of any float in the same formatting context.
+    // https://drafts.csswg.org/css2/visuren.html#bfc-next-to-float
+    let band = match &float_context {
+        Some(float_context) => float_context.band(
+            float_context.next_block_start(margin_block_start),
+            size.block + pb.block_sum(),
+        ),
+        None => FloatBand {
+            inline_start: Length::zero(),
+            inline_size: cbis,
+        },
+    };
+    solve_auto_inline_margins(
+        &mut computed_margin,
+        band.inline_size - size.inline - pb.inline_sum(),
+    );
+    let mut margin = computed_margin.auto_is(Length::zero);
+    margin.block_start = margin_block_start;
+    let relative_adjustement =
+        relative_adjustement(style, size.inline, LengthOrAuto::Length(size.block));
+    let content_rect = Rect {
+        start_corner: Vec2 {
+            block: pb.block_start + relative_adjustement.block,
+            inline: band.inline_start
+                + pb.inline_start
+                + relative_adjustement.inline
+                + margin.inline_start,
+        },
+        size: size.clone(),
+    };
+    let mut block_margins_collapsed_with_children = CollapsedBlockMargins::from_margin(&margin);
+    block_margins_collapsed_with_children.has_clearance = has_clearance;
+    BoxFragment {
+        style: style.clone(),
+        children: replaced.make_fragments(style, size),
+        content_rect,
+        padding,
+        border,
+        margin,
+        block_margins_collapsed_with_children,
+    }
+}
+
+/// Resolves inline margins such that the margin box fills the containing block,
+/// given the sum of both margins.
+/// https://drafts.csswg.org/css2/visudet.html#blockwidth
+fn solve_auto_inline_margins(computed_margin: &mut Sides<LengthOrAuto>, inline_margins: Length) {
+    use LengthOrAuto::*;
+    match (
+        &mut computed_margin.inline_start,
+        &mut computed_margin.inline_end,
+    ) {
+        (s @ &mut Auto, e @ &mut Auto) => {
+            *s = Length(inline_margins / 2.);
+            *e = Length(inline_margins / 2.);
+        }
+        (s @ &mut Auto, _) => {
+            *s = Length(inline_margins);
+        }
+        (_, e @ &mut Auto) => {
+            *e = Length(inline_margins);
+        }
+        (_, e @ _) => {
+            // Either the inline-end margin is auto,
+            // or we’re over-constrained and we do as if it were.
+            *e = Length(inline_margins);
+        }
+    }
+}
@@ -8,6 +8,21 @@ impl crate::dom::Document {
     ) -> Vec<Fragment> {
         BoxTreeRoot::construct(self, user_css).layout(viewport)
     }
+
+    /// The min-content and max-content inline sizes of the document,
+    /// including the margins, borders and padding of the root element.
+    /// Laying out the document at an inline size between the two
+    /// wraps lines only where necessary.
+    pub(crate) fn inline_content_sizes(&self, user_css: Option<&str>) -> ContentSizes {
+        BoxTreeRoot::construct(self, user_css).0.contents.inline_content_sizes()
+    }
+
+    /// The box tree of the document, whose block container holds the box
+    /// of the root element.
+    #[cfg(test)]
+    pub(in crate::layout) fn box_tree(&self, user_css: Option<&str>) -> BlockFormattingContext {
+        BoxTreeRoot::construct(self, user_css).0
+    }
 }
 
 struct BoxTreeRoot(BlockFormattingContext);
@@ -18,9 +33,14 @@ impl BoxTreeRoot {
         let context = Context {
             document,
             author_styles,
+            quote_depth: Default::default(),
+            counters: Default::default(),
         };
         let root_element = document.root_element();
         let style = style_for_element(context.author_styles, context.document, root_element, None);
+        if style.box_.display != Display::None {
+            context.counters.borrow_mut().update(&style, None);
+        }
         let (contains_floats, boxes) = construct_for_root_element(&context, root_element, style);
         Self(BlockFormattingContext {
             contains_floats: contains_floats == ContainsFloats::Yes,
@@ -46,21 +66,18 @@ fn construct_for_root_element(
         Display::Contents => DisplayInside::Flow,
         // The root element is blockified, ignore DisplayOutside
         Display::GeneratingBox(DisplayGeneratingBox::OutsideInside { inside, .. }) => inside,
+        // Blockification turns table-internal boxes into block boxes.
+        Display::GeneratingBox(DisplayGeneratingBox::LayoutInternal(_)) => DisplayInside::Flow,
     };
 
-    if let Some(replaced) = replaced {
-        let _box = match replaced {};
-        #[allow(unreachable_code)]
-        {
-            return (ContainsFloats::No, vec![Arc::new(_box)]);
-        }
-    }
-
     let contents = IndependentFormattingContext::construct(
         context,
         &style,
         display_inside,
-        Contents::OfElement(root_element),
+        match replaced {
+            Some(replaced) => Contents::Replaced(replaced),
+            None => Contents::OfElement(root_element),
+        },
     );
     if style.box_.position.is_absolutely_positioned() {
         (
@@ -6,6 +6,7 @@ pub(crate) enum Fragment {
     Box(BoxFragment),
     Anonymous(AnonymousFragment),
     Text(TextFragment),
+    Image(ImageFragment),
 }
 
 // debug trait explicitly implmented
@@ -28,6 +29,10 @@ pub(crate) struct BoxFragment {
 #[derive(Debug)]
 pub(crate) struct CollapsedBlockMargins {
     pub collapsed_through: bool,
+    /// Whether the box has clearance, which keeps its block-start margin
+    /// from collapsing with the margins before it.
+    /// https://drafts.csswg.org/css2/box.html#collapsing-margins
+    pub has_clearance: bool,
     pub start: CollapsedMargin,
     pub end: CollapsedMargin,
 }
@@ -44,6 +49,8 @@ pub(crate) struct AnonymousFragment {
     pub rect: Rect<Length>,
     pub children: Vec<Fragment>,
     pub mode: (WritingMode, Direction),
+    /// For a line box, the block position of its baseline relative to `rect`.
+    pub baseline: Option<Length>,
 }
 
 #[derive(Debug)]
@@ -53,12 +60,24 @@ pub(crate) struct TextFragment {
     pub text: ShapedSegment,
 }
 
+#[derive(Debug)]
+pub(crate) struct ImageFragment {
+    pub style: Arc<ComputedValues>,
+    /// Where the image is drawn, after applying `object-fit`.
+    /// This can extend beyond `clip_rect`.
+    pub rect: Rect<Length>,
+    /// The content box of the replaced element.
+    pub clip_rect: Rect<Length>,
+    pub image: Arc<Image>,
+}
+
 impl AnonymousFragment {
     pub fn no_op(mode: (WritingMode, Direction)) -> Self {
         Self {
             children: vec![],
             rect: Rect::zero(),
             mode,
+            baseline: None,
         }
     }
 }
@@ -117,6 +136,7 @@ impl CollapsedBlockMargins {
     pub fn from_margin(margin: &Sides<Length>) -> Self {
         Self {
             collapsed_through: false,
+            has_clearance: false,
             start: CollapsedMargin::new(margin.block_start),
             end: CollapsedMargin::new(margin.block_end),
         }
@@ -125,6 +145,7 @@ impl CollapsedBlockMargins {
     pub fn zero() -> Self {
         Self {
             collapsed_through: false,
+            has_clearance: false,
             start: CollapsedMargin::zero(),
             end: CollapsedMargin::zero(),
         }
@@ -160,6 +181,11 @@ impl CollapsedMargin {
     pub fn solve(&self) -> Length {
         self.max_positive + self.min_negative
     }
+
+    /// The most negative margin collapsed so far (or zero).
+    pub fn min_negative(&self) -> Length {
+        self.min_negative
+    }
 }
 
 
@@ -176,6 +202,10 @@ impl Fragment {
             Fragment::Text(ref mut t) => {
                 t.content_rect.start_corner.block += block;
             },
+            Fragment::Image(ref mut i) => {
+                i.rect.start_corner.block += block;
+                i.clip_rect.start_corner.block += block;
+            },
         }
     }
 }
\ No newline at end of file
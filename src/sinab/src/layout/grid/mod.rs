@@ -0,0 +1,923 @@
+//! Grid layout.
+//! https://drafts.csswg.org/css-grid/
+
+use super::*;
+use std::ops::Range;
+
+/// https://drafts.csswg.org/css-grid/#grid-container
+#[derive(Debug)]
+pub(super) struct GridContainer {
+    pub style: Arc<ComputedValues>,
+    pub children: Vec<Arc<GridLevelBox>>,
+}
+
+#[derive(Debug)]
+pub(in crate::layout) enum GridLevelBox {
+    GridItem(ItemBox),
+    OutOfFlowAbsolutelyPositionedBox(AbsolutelyPositionedBox),
+}
+
+impl ItemLevelBox for GridLevelBox {
+    fn item(box_: ItemBox) -> Self {
+        GridLevelBox::GridItem(box_)
+    }
+
+    fn out_of_flow_absolutely_positioned(box_: AbsolutelyPositionedBox) -> Self {
+        GridLevelBox::OutOfFlowAbsolutelyPositionedBox(box_)
+    }
+
+    fn layout_box(box_: Arc<Self>) -> LayoutBox {
+        LayoutBox::GridLevel(box_)
+    }
+}
+
+/// A grid item during layout.
+struct GridItem<'a> {
+    box_: &'a ItemBox,
+    /// The index of the item among the children of the grid container.
+    index: usize,
+    /// The columns and rows spanned by the item.
+    area: Vec2<Range<usize>>,
+}
+
+/// The result of the placement of grid items.
+/// https://drafts.csswg.org/css-grid/#placement
+struct Placement<'a> {
+    items: Vec<GridItem<'a>>,
+    /// The number of columns and rows of the implicit grid.
+    track_counts: Vec2<usize>,
+}
+
+/// A track being sized.
+/// https://drafts.csswg.org/css-grid/#algo-terms
+struct Track {
+    size: TrackSize,
+    base_size: Length,
+    /// `None` is an infinite growth limit.
+    growth_limit: Option<Length>,
+}
+
+impl GridContainer {
+    pub(super) fn construct<'a>(
+        context: &'a Context<'a>,
+        style: &Arc<ComputedValues>,
+        contents: NonReplacedContents,
+    ) -> Self {
+        GridContainer {
+            style: style.clone(),
+            children: construct_items(context, style, contents),
+        }
+    }
+
+    /// The content sizes of the content box of the grid container.
+    pub(super) fn inline_content_sizes(&self) -> ContentSizes {
+        let placement = self.place_items();
+        let gap = self
+            .style
+            .flex
+            .column_gap
+            .percentage_relative_to(Length::zero(), Length::zero());
+        let contributions: Vec<ContentSizes> = placement
+            .items
+            .iter()
+            .map(|item| item.inline_content_contribution())
+            .collect();
+        let tracks = |under_min_content_constraint: bool| {
+            self.track_sizes(
+                Axis::Inline,
+                placement.track_counts.inline,
+                &placement.items,
+                None,
+                gap,
+                |index| {
+                    let contribution = contributions[index];
+                    if under_min_content_constraint {
+                        ContentSizes {
+                            min_content: contribution.min_content,
+                            max_content: contribution.min_content,
+                        }
+                    } else {
+                        contribution
+                    }
+                },
+            )
+        };
+        let sum = |sizes: Vec<Length>| {
+            let gaps = gap * sizes.len().saturating_sub(1) as f32;
+            sizes.into_iter().fold(gaps, |sum, size| sum + size)
+        };
+        ContentSizes {
+            min_content: sum(tracks(true)),
+            max_content: sum(tracks(false)),
+        }
+    }
+
+    /// https://drafts.csswg.org/css-grid/#layout-algorithm
+    pub(super) fn layout<'a>(
+        &'a self,
+        containing_block: &ContainingBlock,
+        tree_rank: usize,
+        absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    ) -> FlowChildren {
+        let abspos_so_far = absolutely_positioned_fragments.len();
+        let placement = self.place_items();
+        let items = &placement.items;
+        let definite_block_size = match containing_block.block_size {
+            LengthOrAuto::Length(block_size) => Some(block_size),
+            LengthOrAuto::Auto => None,
+        };
+        // https://drafts.csswg.org/css-align-3/#column-row-gap
+        let gaps = Vec2 {
+            inline: self
+                .style
+                .flex
+                .column_gap
+                .percentage_relative_to(containing_block.inline_size, Length::zero()),
+            block: self
+                .style
+                .flex
+                .row_gap
+                .percentage_relative_to(
+                    definite_block_size.unwrap_or_else(Length::zero),
+                    Length::zero(),
+                ),
+        };
+
+        let columns = self.track_sizes(
+            Axis::Inline,
+            placement.track_counts.inline,
+            items,
+            Some(containing_block.inline_size),
+            gaps.inline,
+            |index| items[index].inline_content_contribution(),
+        );
+        let column_offsets = track_offsets(&columns, gaps.inline);
+
+        // The inline sizes of items are known once columns are sized,
+        // so laying them out gives their contributions to the rows.
+        let inline_sizes: Vec<Length> = items
+            .iter()
+            .map(|item| {
+                let area_inline_size = area_size(&column_offsets, &columns, &item.area.inline);
+                item.inline_size(&self.style, area_inline_size, containing_block)
+            })
+            .collect();
+        let rows = self.track_sizes(
+            Axis::Block,
+            placement.track_counts.block,
+            items,
+            definite_block_size,
+            gaps.block,
+            |index| {
+                let item = &items[index];
+                let block_size =
+                    item.outer_block_size(inline_sizes[index], containing_block);
+                ContentSizes {
+                    min_content: block_size,
+                    max_content: block_size,
+                }
+            },
+        );
+        let row_offsets = track_offsets(&rows, gaps.block);
+
+        let mut fragments: Vec<Option<Fragment>> = self.children.iter().map(|_| None).collect();
+        for (item, &inline_size) in items.iter().zip(&inline_sizes) {
+            let area = Rect {
+                start_corner: Vec2 {
+                    inline: column_offsets[item.area.inline.start],
+                    block: row_offsets[item.area.block.start],
+                },
+                size: Vec2 {
+                    inline: area_size(&column_offsets, &columns, &item.area.inline),
+                    block: area_size(&row_offsets, &rows, &item.area.block),
+                },
+            };
+            fragments[item.index] = Some(Fragment::Box(item.layout(
+                &self.style,
+                &area,
+                inline_size,
+                containing_block,
+                absolutely_positioned_fragments,
+            )));
+        }
+
+        for (index, child) in self.children.iter().enumerate() {
+            if let GridLevelBox::OutOfFlowAbsolutelyPositionedBox(box_) = &**child {
+                // The static position of an absolutely-positioned child
+                // is the start corner of the grid container.
+                absolutely_positioned_fragments.push(box_.layout(Vec2::zero(), index));
+                fragments[index] = Some(Fragment::Anonymous(AnonymousFragment::no_op(
+                    containing_block.mode,
+                )));
+            }
+        }
+        let mut fragments: Vec<Fragment> = fragments
+            .into_iter()
+            .map(|fragment| fragment.expect("every child has a fragment"))
+            .collect();
+        adjust_static_positions(
+            &mut absolutely_positioned_fragments[abspos_so_far..],
+            &mut fragments,
+            tree_rank,
+        );
+
+        let gaps = gaps.block * rows.len().saturating_sub(1) as f32;
+        FlowChildren {
+            fragments,
+            block_size: rows.iter().fold(gaps, |sum, &size| sum + size),
+            collapsible_margins_in_children: CollapsedBlockMargins::zero(),
+        }
+    }
+
+    /// Places items in the grid, growing it with implicit tracks as needed.
+    /// Dense packing is not supported.
+    /// https://drafts.csswg.org/css-grid/#auto-placement-algo
+    fn place_items(&self) -> Placement<'_> {
+        let grid = &self.style.grid;
+        let explicit_counts = Vec2 {
+            inline: grid.grid_template_columns.0.len(),
+            block: grid.grid_template_rows.0.len(),
+        };
+        let boxes: Vec<(usize, &ItemBox)> = self
+            .children
+            .iter()
+            .enumerate()
+            .filter_map(|(index, child)| match &**child {
+                GridLevelBox::GridItem(box_) => Some((index, box_)),
+                GridLevelBox::OutOfFlowAbsolutelyPositionedBox(_) => None,
+            })
+            .collect();
+        // Lines resolved against the explicit grid, or the span of auto-placed items.
+        let lines: Vec<Vec2<Result<Range<usize>, usize>>> = boxes
+            .iter()
+            .map(|(_, box_)| {
+                let grid = &box_.style.grid;
+                Vec2 {
+                    inline: resolve_lines(
+                        grid.grid_column_start,
+                        grid.grid_column_end,
+                        explicit_counts.inline,
+                    ),
+                    block: resolve_lines(
+                        grid.grid_row_start,
+                        grid.grid_row_end,
+                        explicit_counts.block,
+                    ),
+                }
+            })
+            .collect();
+
+        // Auto-placement works in terms of the axis along which items are placed,
+        // the minor axis, and the one along which the grid grows, the major axis.
+        let rows_first = grid.grid_auto_flow == GridAutoFlow::Row;
+        let major_minor = |vec: &Vec2<Result<Range<usize>, usize>>| {
+            if rows_first {
+                (vec.block.clone(), vec.inline.clone())
+            } else {
+                (vec.inline.clone(), vec.block.clone())
+            }
+        };
+        let length = |lines: &Result<Range<usize>, usize>| match lines {
+            Ok(range) => range.end,
+            Err(span) => *span,
+        };
+        let mut minor_count = lines
+            .iter()
+            .map(|lines| length(&major_minor(lines).1))
+            .fold(
+                if rows_first {
+                    explicit_counts.inline
+                } else {
+                    explicit_counts.block
+                },
+                usize::max,
+            );
+
+        let mut occupied = Occupancy { rows: vec![] };
+        let mut areas: Vec<Option<(Range<usize>, Range<usize>)>> = lines.iter().map(|_| None).collect();
+        // Items with a definite position in both axes.
+        for (lines, area) in lines.iter().zip(&mut areas) {
+            if let (Ok(major), Ok(minor)) = major_minor(lines) {
+                occupied.mark(&major, &minor);
+                *area = Some((major, minor))
+            }
+        }
+        // Items locked to given tracks of the major axis.
+        let mut cursors: Vec<usize> = vec![];
+        for (lines, area) in lines.iter().zip(&mut areas) {
+            if let (Ok(major), Err(span)) = major_minor(lines) {
+                if cursors.len() <= major.start {
+                    cursors.resize(major.start + 1, 0)
+                }
+                let mut minor = cursors[major.start];
+                while !occupied.fits(&major, &(minor..minor + span)) {
+                    minor += 1
+                }
+                let minor = minor..minor + span;
+                minor_count = minor_count.max(minor.end);
+                cursors[major.start] = minor.end;
+                occupied.mark(&major, &minor);
+                *area = Some((major, minor))
+            }
+        }
+        // Other items, in order.
+        let (mut cursor_major, mut cursor_minor) = (0, 0);
+        for (lines, area) in lines.iter().zip(&mut areas) {
+            let (major_span, minor) = match major_minor(lines) {
+                (Err(major_span), minor) => (major_span, minor),
+                (Ok(_), _) => continue,
+            };
+            let (major, minor) = match minor {
+                Ok(minor) => {
+                    if minor.start < cursor_minor {
+                        cursor_major += 1
+                    }
+                    while !occupied.fits(&(cursor_major..cursor_major + major_span), &minor) {
+                        cursor_major += 1
+                    }
+                    cursor_minor = minor.start;
+                    (cursor_major..cursor_major + major_span, minor)
+                }
+                Err(minor_span) => loop {
+                    if cursor_minor + minor_span > minor_count {
+                        cursor_major += 1;
+                        cursor_minor = 0;
+                        continue;
+                    }
+                    let major = cursor_major..cursor_major + major_span;
+                    let minor = cursor_minor..cursor_minor + minor_span;
+                    if occupied.fits(&major, &minor) {
+                        cursor_minor = minor.end;
+                        break (major, minor);
+                    }
+                    cursor_minor += 1
+                },
+            };
+            occupied.mark(&major, &minor);
+            *area = Some((major, minor))
+        }
+
+        let items: Vec<GridItem> = boxes
+            .into_iter()
+            .zip(areas)
+            .map(|((index, box_), area)| {
+                let (major, minor) = area.expect("every item is placed");
+                let (block, inline) = if rows_first {
+                    (major, minor)
+                } else {
+                    (minor, major)
+                };
+                GridItem {
+                    box_,
+                    index,
+                    area: Vec2 { inline, block },
+                }
+            })
+            .collect();
+        let track_counts = items.iter().fold(explicit_counts, |counts, item| Vec2 {
+            inline: counts.inline.max(item.area.inline.end),
+            block: counts.block.max(item.area.block.end),
+        });
+        Placement {
+            items,
+            track_counts,
+        }
+    }
+
+    /// Sizes the columns or rows of the grid, given the content contribution
+    /// of the item with the given index in the grid items.
+    /// This is a simplified version of the track sizing algorithm.
+    /// https://drafts.csswg.org/css-grid/#algo-track-sizing
+    fn track_sizes(
+        &self,
+        axis: Axis,
+        count: usize,
+        items: &[GridItem],
+        available_size: Option<Length>,
+        gap: Length,
+        contribution: impl Fn(usize) -> ContentSizes,
+    ) -> Vec<Length> {
+        let grid = &self.style.grid;
+        let (explicit, implicit) = match axis {
+            Axis::Inline => (&grid.grid_template_columns, grid.grid_auto_columns),
+            Axis::Block => (&grid.grid_template_rows, grid.grid_auto_rows),
+        };
+        // https://drafts.csswg.org/css-grid/#algo-init
+        let mut tracks: Vec<Track> = (0..count)
+            .map(|index| {
+                let size = explicit.0.get(index).copied().unwrap_or(implicit);
+                let fixed = |breadth| fixed_breadth(breadth, available_size);
+                let base_size = fixed(size.min).unwrap_or_else(Length::zero);
+                Track {
+                    size,
+                    base_size,
+                    growth_limit: fixed(size.max).map(|limit| limit.max(base_size)),
+                }
+            })
+            .collect();
+        let gaps_in = |range: &Range<usize>| gap * (range.len() - 1) as f32;
+        let flex_factor = |track: &Track| match track.size.max {
+            TrackBreadth::Flex(factor) => Some(factor.value),
+            _ => None,
+        };
+        let intrinsic = |breadth| {
+            matches!(
+                breadth,
+                TrackBreadth::Auto | TrackBreadth::MinContent | TrackBreadth::MaxContent
+            )
+        };
+
+        // https://drafts.csswg.org/css-grid/#algo-content
+        let mut items_by_span: Vec<(usize, &GridItem)> = items.iter().enumerate().collect();
+        items_by_span.sort_by_key(|(_, item)| axis.of(&item.area).len());
+        for &(index, item) in &items_by_span {
+            let range = axis.of(&item.area);
+            let contribution = contribution(index);
+            let spanned = &mut tracks[range.clone()];
+            let spans_flexible_track = spanned.iter().any(|track| flex_factor(track).is_some());
+            if spans_flexible_track {
+                // Flexible tracks only grow to fit their items when sized below.
+                continue;
+            }
+            let min_contribution = |track: &Track| match track.size.min {
+                TrackBreadth::MaxContent => contribution.max_content,
+                _ => contribution.min_content,
+            };
+            let max_contribution = |track: &Track| match track.size.max {
+                TrackBreadth::MinContent => contribution.min_content,
+                _ => contribution.max_content,
+            };
+            if let [track] = spanned {
+                if intrinsic(track.size.min) {
+                    track.base_size.max_assign(min_contribution(track))
+                }
+                if intrinsic(track.size.max) {
+                    let limit = track.growth_limit.unwrap_or_else(Length::zero);
+                    track.growth_limit = Some(limit.max(max_contribution(track)))
+                }
+                continue;
+            }
+            // Items spanning several tracks distribute the space they need
+            // beyond the size of those tracks equally among intrinsic tracks.
+            let gaps = gaps_in(&range);
+            let intrinsic_min_count = spanned.iter().filter(|t| intrinsic(t.size.min)).count();
+            let base_sum = spanned.iter().fold(gaps, |sum, track| sum + track.base_size);
+            let extra = spanned
+                .iter()
+                .map(min_contribution)
+                .fold(Length::zero(), Length::max)
+                - base_sum;
+            if intrinsic_min_count > 0 && extra > Length::zero() {
+                let share = extra / intrinsic_min_count as f32;
+                for track in spanned.iter_mut().filter(|t| intrinsic(t.size.min)) {
+                    track.base_size += share
+                }
+            }
+            let intrinsic_max_count = spanned.iter().filter(|t| intrinsic(t.size.max)).count();
+            let limit_sum = spanned.iter().fold(gaps, |sum, track| {
+                sum + track.growth_limit.unwrap_or(track.base_size)
+            });
+            let extra = spanned
+                .iter()
+                .map(max_contribution)
+                .fold(Length::zero(), Length::max)
+                - limit_sum;
+            if intrinsic_max_count > 0 && extra > Length::zero() {
+                let share = extra / intrinsic_max_count as f32;
+                for track in spanned.iter_mut().filter(|t| intrinsic(t.size.max)) {
+                    track.growth_limit = Some(track.growth_limit.unwrap_or(track.base_size) + share)
+                }
+            }
+        }
+        for track in &mut tracks {
+            if flex_factor(track).is_none() {
+                let limit = track.growth_limit.unwrap_or(track.base_size);
+                track.growth_limit = Some(limit.max(track.base_size))
+            }
+        }
+
+        let free_space = |tracks: &[Track]| {
+            available_size.map(|available| {
+                let gaps = gap * count.saturating_sub(1) as f32;
+                tracks
+                    .iter()
+                    .fold(available - gaps, |free, track| free - track.base_size)
+            })
+        };
+
+        // https://drafts.csswg.org/css-grid/#algo-grow-tracks
+        match free_space(&tracks) {
+            Some(mut free_space) => {
+                // Grow tracks equally, freezing those reaching their growth limit.
+                loop {
+                    let growable: Vec<&mut Track> = tracks
+                        .iter_mut()
+                        .filter(|track| {
+                            flex_factor(track).is_none()
+                                && track.growth_limit.is_some_and(|limit| limit > track.base_size)
+                        })
+                        .collect();
+                    if growable.is_empty() || free_space <= Length::zero() {
+                        break;
+                    }
+                    let share = free_space / growable.len() as f32;
+                    for track in growable {
+                        let limit = track.growth_limit.unwrap();
+                        let growth = share.min(limit - track.base_size);
+                        track.base_size += growth;
+                        free_space -= growth;
+                    }
+                }
+            }
+            None => {
+                for track in &mut tracks {
+                    if let Some(limit) = track.growth_limit {
+                        track.base_size = limit
+                    }
+                }
+            }
+        }
+
+        // https://drafts.csswg.org/css-grid/#algo-flex-tracks
+        if tracks.iter().any(|track| flex_factor(track).is_some()) {
+            let flex_fraction = match available_size {
+                Some(available) => {
+                    // https://drafts.csswg.org/css-grid/#algo-find-fr-size
+                    let gaps = gap * count.saturating_sub(1) as f32;
+                    let mut inflexible: Vec<bool> =
+                        tracks.iter().map(|track| flex_factor(track).is_none()).collect();
+                    loop {
+                        let mut leftover = available - gaps;
+                        let mut factor_sum = 0.;
+                        for (track, &inflexible) in tracks.iter().zip(&inflexible) {
+                            match flex_factor(track) {
+                                Some(factor) if !inflexible => factor_sum += factor,
+                                _ => leftover -= track.base_size,
+                            }
+                        }
+                        let fraction = leftover / f32::max(factor_sum, 1.);
+                        let mut changed = false;
+                        for (track, inflexible) in tracks.iter().zip(&mut inflexible) {
+                            if let Some(factor) = flex_factor(track) {
+                                if !*inflexible && fraction * factor < track.base_size {
+                                    *inflexible = true;
+                                    changed = true;
+                                }
+                            }
+                        }
+                        if !changed {
+                            break fraction;
+                        }
+                    }
+                }
+                None => {
+                    // The fraction fitting flexible tracks and the items they contain.
+                    let mut fraction = tracks
+                        .iter()
+                        .filter_map(|track| {
+                            let factor = flex_factor(track)?;
+                            Some(track.base_size / f32::max(factor, 1.))
+                        })
+                        .fold(Length::zero(), Length::max);
+                    for (index, item) in items.iter().enumerate() {
+                        let range = axis.of(&item.area);
+                        let spanned = &tracks[range.clone()];
+                        let factor_sum: f32 = spanned.iter().filter_map(flex_factor).sum();
+                        if factor_sum > 0. {
+                            let inflexible_sum = spanned
+                                .iter()
+                                .filter(|track| flex_factor(track).is_none())
+                                .fold(gaps_in(&range), |sum, track| sum + track.base_size);
+                            let needed = contribution(index).max_content - inflexible_sum;
+                            fraction.max_assign(needed / f32::max(factor_sum, 1.))
+                        }
+                    }
+                    fraction
+                }
+            };
+            for track in &mut tracks {
+                if let Some(factor) = flex_factor(track) {
+                    track.base_size.max_assign(flex_fraction * factor)
+                }
+            }
+        }
+
+        // https://drafts.csswg.org/css-grid/#algo-stretch
+        if let Some(free_space) = free_space(&tracks) {
+            let auto_count = tracks
+                .iter()
+                .filter(|track| matches!(track.size.max, TrackBreadth::Auto))
+                .count();
+            if free_space > Length::zero() && auto_count > 0 {
+                let share = free_space / auto_count as f32;
+                for track in &mut tracks {
+                    if let TrackBreadth::Auto = track.size.max {
+                        track.base_size += share
+                    }
+                }
+            }
+        }
+
+        tracks.into_iter().map(|track| track.base_size).collect()
+    }
+}
+
+impl<'a> GridItem<'a> {
+    fn style(&self) -> &'a ComputedValues {
+        &self.box_.style
+    }
+
+    fn pbm(&self, containing_block: &ContainingBlock) -> Sides<Length> {
+        let style = self.style();
+        let cbis = containing_block.inline_size;
+        let padding = style.padding().percentages_relative_to(cbis);
+        let border = style.border_width().percentages_relative_to(cbis);
+        let margin = style
+            .margin()
+            .percentages_relative_to(cbis)
+            .auto_is(Length::zero);
+        &(&padding + &border) + &margin
+    }
+
+    /// The min-content and max-content contributions of the item to the columns.
+    fn inline_content_contribution(&self) -> ContentSizes {
+        let style = self.style();
+        outer_inline_content_sizes(style, || self.box_.contents.inline_content_sizes(style))
+    }
+
+    /// The used inline size of the content box of the item.
+    fn inline_size(
+        &self,
+        container_style: &ComputedValues,
+        area_inline_size: Length,
+        containing_block: &ContainingBlock,
+    ) -> Length {
+        let style = self.style();
+        if let Ok(replaced) = self.box_.contents.as_replaced() {
+            let area = area_containing_block(area_inline_size, containing_block);
+            return replaced.used_size_as_if_inline_element(&area, style).inline;
+        }
+        let available = area_inline_size - self.pbm(containing_block).inline_sum();
+        match style.box_size().inline {
+            LengthOrPercentageOrAuto::Length(length) => length,
+            LengthOrPercentageOrAuto::Percentage(percentage) => area_inline_size * percentage,
+            LengthOrPercentageOrAuto::Auto => {
+                match style.flex.justify_self.resolve(container_style.flex.justify_items) {
+                    AlignItems::Stretch => available.max(Length::zero()),
+                    _ => self
+                        .box_
+                        .contents
+                        .inline_content_sizes(style)
+                        .shrink_to_fit(available),
+                }
+            }
+        }
+    }
+
+    /// The block size of the margin box of the item, laid out at the given inline size.
+    fn outer_block_size(&self, inline_size: Length, containing_block: &ContainingBlock) -> Length {
+        let block_size = match self.style().box_size().block {
+            LengthOrPercentageOrAuto::Length(length) => length,
+            _ => {
+                self.box_
+                    .layout_contents(
+                        inline_size,
+                        LengthOrAuto::Auto,
+                        &area_containing_block(inline_size, containing_block),
+                        self.index,
+                        &mut vec![],
+                    )
+                    .1
+            }
+        };
+        block_size + self.pbm(containing_block).block_sum()
+    }
+
+    /// Lays out the item in its grid area, aligning it in both axes.
+    /// https://drafts.csswg.org/css-grid/#alignment
+    fn layout(
+        &self,
+        container_style: &ComputedValues,
+        area: &Rect<Length>,
+        inline_size: Length,
+        containing_block: &ContainingBlock,
+        absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    ) -> BoxFragment {
+        let style = &self.box_.style;
+        let cbis = containing_block.inline_size;
+        let padding = style.padding().percentages_relative_to(cbis);
+        let border = style.border_width().percentages_relative_to(cbis);
+        // FIXME: auto margins
+        let margin = style
+            .margin()
+            .percentages_relative_to(cbis)
+            .auto_is(Length::zero);
+        let pbm = self.pbm(containing_block);
+        let align = style.flex.align_self.resolve(container_style.flex.align_items);
+        let block_size = match style.box_size().block {
+            LengthOrPercentageOrAuto::Length(length) => LengthOrAuto::Length(length),
+            LengthOrPercentageOrAuto::Percentage(percentage) => {
+                LengthOrAuto::Length(area.size.block * percentage)
+            }
+            LengthOrPercentageOrAuto::Auto
+                if align == AlignItems::Stretch && self.box_.contents.as_replaced().is_err() =>
+            {
+                LengthOrAuto::Length((area.size.block - pbm.block_sum()).max(Length::zero()))
+            }
+            LengthOrPercentageOrAuto::Auto => LengthOrAuto::Auto,
+        };
+
+        let mut nested_abspos = vec![];
+        let relatively_positioned = style.box_.position.is_relatively_positioned();
+        let (mut children, block_size) = self.box_.layout_contents(
+            inline_size,
+            block_size,
+            &area_containing_block(inline_size, containing_block),
+            self.index,
+            if relatively_positioned {
+                &mut nested_abspos
+            } else {
+                absolutely_positioned_fragments
+            },
+        );
+        let size = Vec2 {
+            inline: inline_size,
+            block: block_size,
+        };
+        if relatively_positioned {
+            AbsolutelyPositionedFragment::in_positioned_containing_block(
+                &nested_abspos,
+                &mut children,
+                &size,
+                &padding,
+                style.writing_mode(),
+            )
+        }
+
+        let offset = |alignment, free_space: Length| match alignment {
+            AlignItems::End | AlignItems::FlexEnd => free_space,
+            AlignItems::Center => free_space / 2.,
+            // FIXME: baseline alignment
+            AlignItems::Stretch
+            | AlignItems::Start
+            | AlignItems::FlexStart
+            | AlignItems::Baseline => Length::zero(),
+        };
+        let justify = style.flex.justify_self.resolve(container_style.flex.justify_items);
+        let mut start_corner = Vec2 {
+            inline: area.start_corner.inline
+                + offset(justify, area.size.inline - inline_size - pbm.inline_sum())
+                + margin.inline_start
+                + padding.inline_start
+                + border.inline_start,
+            block: area.start_corner.block
+                + offset(align, area.size.block - block_size - pbm.block_sum())
+                + margin.block_start
+                + padding.block_start
+                + border.block_start,
+        };
+        start_corner += &relative_adjustement(
+            style,
+            containing_block.inline_size,
+            containing_block.block_size,
+        );
+        BoxFragment {
+            style: style.clone(),
+            children,
+            content_rect: Rect { start_corner, size },
+            padding,
+            border,
+            margin,
+            block_margins_collapsed_with_children: CollapsedBlockMargins::zero(),
+        }
+    }
+}
+
+/// The columns or rows of a grid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Axis {
+    Inline,
+    Block,
+}
+
+impl Axis {
+    fn of<T: Clone>(self, vec: &Vec2<T>) -> T {
+        match self {
+            Axis::Inline => vec.inline.clone(),
+            Axis::Block => vec.block.clone(),
+        }
+    }
+}
+
+/// Which cells of the grid are occupied by items, indexed by major then minor track.
+struct Occupancy {
+    rows: Vec<Vec<bool>>,
+}
+
+impl Occupancy {
+    fn fits(&self, major: &Range<usize>, minor: &Range<usize>) -> bool {
+        self.rows[major.start.min(self.rows.len())..major.end.min(self.rows.len())]
+            .iter()
+            .all(|row| minor.clone().all(|i| !row.get(i).copied().unwrap_or(false)))
+    }
+
+    fn mark(&mut self, major: &Range<usize>, minor: &Range<usize>) {
+        if self.rows.len() < major.end {
+            self.rows.resize(major.end, vec![])
+        }
+        for row in &mut self.rows[major.clone()] {
+            if row.len() < minor.end {
+                row.resize(minor.end, false)
+            }
+            for cell in &mut row[minor.clone()] {
+                *cell = true
+            }
+        }
+    }
+}
+
+/// The tracks between the given start and end lines of an item,
+/// or the number of tracks it spans if it is to be auto-placed.
+/// Lines before the start of the explicit grid are clamped to its start.
+/// https://drafts.csswg.org/css-grid/#line-placement
+fn resolve_lines(start: GridLine, end: GridLine, explicit_count: usize) -> Result<Range<usize>, usize> {
+    // Line numbers and spans are clamped to keep the grid reasonably sized.
+    const MAX: i32 = 1000;
+    let line = |number: i32| -> usize {
+        let number = number.clamp(-MAX, MAX);
+        if number > 0 {
+            number as usize - 1
+        } else {
+            (explicit_count as i32 + 1 + number).max(0) as usize
+        }
+    };
+    let span = |line: GridLine| match line {
+        GridLine::Span(span) => span.min(MAX as u32) as usize,
+        GridLine::Auto | GridLine::Line(_) => 1,
+    };
+    match (start, end) {
+        (GridLine::Line(start), GridLine::Line(end)) => {
+            let (start, end) = (line(start), line(end));
+            match start.cmp(&end) {
+                std::cmp::Ordering::Less => Ok(start..end),
+                std::cmp::Ordering::Equal => Ok(start..start + 1),
+                std::cmp::Ordering::Greater => Ok(end..start),
+            }
+        }
+        (GridLine::Line(start), end) => {
+            let start = line(start);
+            Ok(start..start + span(end))
+        }
+        (start, GridLine::Line(end)) => {
+            let end = line(end).max(1);
+            Ok(end.saturating_sub(span(start))..end)
+        }
+        (GridLine::Auto, end) => Err(span(end)),
+        // If both lines are spans, the end one is ignored.
+        (start, _) => Err(span(start)),
+    }
+}
+
+/// A containing block of the given inline size and an indefinite block size,
+/// in which the size of replaced items is resolved.
+fn area_containing_block(
+    inline_size: Length,
+    containing_block: &ContainingBlock,
+) -> ContainingBlock {
+    ContainingBlock {
+        inline_size,
+        block_size: LengthOrAuto::Auto,
+        mode: containing_block.mode,
+    }
+}
+
+/// The size of a track sizing function, if it is fixed.
+/// Percentages of an indefinite size behave as `auto`.
+fn fixed_breadth(breadth: TrackBreadth, percentage_basis: Option<Length>) -> Option<Length> {
+    match breadth {
+        TrackBreadth::Length(LengthOrPercentage::Length(length)) => Some(length),
+        TrackBreadth::Length(LengthOrPercentage::Percentage(percentage)) => {
+            percentage_basis.map(|basis| basis * percentage)
+        }
+        _ => None,
+    }
+}
+
+/// The start position of each track.
+fn track_offsets(sizes: &[Length], gap: Length) -> Vec<Length> {
+    let mut offset = Length::zero();
+    sizes
+        .iter()
+        .map(|&size| {
+            let start = offset;
+            offset += size + gap;
+            start
+        })
+        .collect()
+}
+
+/// The size of the given tracks, including the gaps between them.
+fn area_size(offsets: &[Length], sizes: &[Length], tracks: &Range<usize>) -> Length {
+    let last = tracks.end - 1;
+    offsets[last] + sizes[last] - offsets[tracks.start]
+}
@@ -0,0 +1,151 @@
+//! The children of flex and grid containers.
+//!
+//! Both containers wrap each in-flow child in an item establishing an
+//! independent formatting context, with consecutive text runs wrapped in an
+//! anonymous item. Only the placement and sizing of the items differ.
+
+use super::*;
+
+/// A flex or grid item.
+/// https://drafts.csswg.org/css-flexbox-1/#flex-items
+/// https://drafts.csswg.org/css-grid/#grid-items
+#[derive(Debug)]
+pub(in crate::layout) struct ItemBox {
+    pub style: Arc<ComputedValues>,
+    pub contents: IndependentFormattingContext,
+}
+
+/// The box generated for a child of a flex or grid container.
+pub(super) trait ItemLevelBox: Sized {
+    fn item(box_: ItemBox) -> Self;
+    fn out_of_flow_absolutely_positioned(box_: AbsolutelyPositionedBox) -> Self;
+    fn layout_box(box_: Arc<Self>) -> LayoutBox;
+}
+
+/// Constructs the boxes for the children of a flex or grid container.
+pub(super) fn construct_items<'a, T: ItemLevelBox>(
+    context: &'a Context<'a>,
+    style: &Arc<ComputedValues>,
+    contents: NonReplacedContents,
+) -> Vec<Arc<T>> {
+    let mut builder = ItemsBuilder {
+        context,
+        style: style.clone(),
+        children: vec![],
+        anonymous_item: None,
+    };
+    contents.traverse(style, context, &mut builder);
+    builder.end_anonymous_item();
+    builder.children
+}
+
+struct ItemsBuilder<'a, T> {
+    context: &'a Context<'a>,
+    style: Arc<ComputedValues>,
+    children: Vec<Arc<T>>,
+    /// The anonymous item wrapping consecutive text runs, if any.
+    anonymous_item: Option<(Arc<ComputedValues>, BlockContainerBuilder<'a>)>,
+}
+
+impl<T: ItemLevelBox> ItemsBuilder<'_, T> {
+    fn end_anonymous_item(&mut self) {
+        if let Some((style, builder)) = self.anonymous_item.take() {
+            let (contents, contains_floats) = builder.finish();
+            let contents = IndependentFormattingContext::Flow(BlockFormattingContext {
+                contents,
+                contains_floats: contains_floats == ContainsFloats::Yes,
+            });
+            self.children
+                .push(Arc::new(T::item(ItemBox { style, contents })))
+        }
+    }
+}
+
+impl<'a, T: ItemLevelBox> TraversalHandler<'a> for ItemsBuilder<'a, T> {
+    fn handle_text(&mut self, text: &str, parent_style: &Arc<ComputedValues>) {
+        // Whitespace-only text runs are not rendered.
+        if self.anonymous_item.is_none() && text.bytes().all(|b| b.is_ascii_whitespace()) {
+            return;
+        }
+        let context = self.context;
+        let container_style = &self.style;
+        let (_, builder) = self.anonymous_item.get_or_insert_with(|| {
+            let style = ComputedValues::anonymous_inheriting_from(Some(container_style));
+            (style.clone(), BlockContainerBuilder::new(context, style))
+        });
+        builder.handle_text(text, parent_style)
+    }
+
+    fn handle_element(
+        &mut self,
+        style: &Arc<ComputedValues>,
+        display: DisplayGeneratingBox,
+        contents: Contents,
+        box_slot: BoxSlot<'a>,
+    ) {
+        self.end_anonymous_item();
+        // The display of flex and grid items is blockified,
+        // so table-internal boxes establish flow roots of their own.
+        let display_inside = match display {
+            DisplayGeneratingBox::OutsideInside { inside, .. } => inside,
+            DisplayGeneratingBox::LayoutInternal(_) => DisplayInside::Flow,
+        };
+        let contents =
+            IndependentFormattingContext::construct(self.context, style, display_inside, contents);
+        let box_ = Arc::new(if style.box_.position.is_absolutely_positioned() {
+            T::out_of_flow_absolutely_positioned(AbsolutelyPositionedBox {
+                style: style.clone(),
+                contents,
+            })
+        } else {
+            T::item(ItemBox {
+                style: style.clone(),
+                contents,
+            })
+        });
+        self.children.push(box_.clone());
+        box_slot.set(T::layout_box(box_))
+    }
+}
+
+impl ItemBox {
+    /// Lays out the contents of the item with the given size of its content box,
+    /// returning the fragments and the block size of the content box.
+    /// The auto block size of replaced contents is resolved in `containing_block`.
+    pub(super) fn layout_contents<'a>(
+        &'a self,
+        inline_size: Length,
+        block_size: LengthOrAuto,
+        containing_block: &ContainingBlock,
+        tree_rank: usize,
+        absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    ) -> (Vec<Fragment>, Length) {
+        let style = &self.style;
+        let block_size = match (block_size, self.contents.as_replaced()) {
+            (LengthOrAuto::Auto, Ok(replaced)) => LengthOrAuto::Length(
+                replaced
+                    .used_size_as_if_inline_element(containing_block, style)
+                    .block,
+            ),
+            (block_size, _) => block_size,
+        };
+        let containing_block_for_children = ContainingBlock {
+            inline_size,
+            block_size,
+            mode: style.writing_mode(),
+        };
+        // https://drafts.csswg.org/css-writing-modes/#orthogonal-flows
+        assert_eq!(
+            containing_block.mode, containing_block_for_children.mode,
+            "Mixed writing modes are not supported yet"
+        );
+        let flow_children = self.contents.layout(
+            style,
+            &containing_block_for_children,
+            tree_rank,
+            absolutely_positioned_fragments,
+        );
+        let block_size = block_size.auto_is(|| flow_children.block_size);
+        (flow_children.fragments, block_size)
+    }
+}
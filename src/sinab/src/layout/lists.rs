@@ -0,0 +1,88 @@
+//! List items and their markers.
+//! https://drafts.csswg.org/css-lists-3/
+
+use super::*;
+use crate::style::{style_for_pseudo_element, PseudoElement};
+
+/// The style and contents of the `::marker` pseudo-element of a list item,
+/// or `None` if `element` is not a list item or its marker is empty.
+pub(super) fn make_marker(
+    context: &Context,
+    element: dom::NodeId,
+    style: &ComputedValues,
+) -> Option<(Arc<ComputedValues>, Vec<PseudoElementContentItem>)> {
+    if !style.box_.display.is_list_item() {
+        return None;
+    }
+    let marker_style = style_for_pseudo_element(
+        context.author_styles,
+        context.document,
+        element,
+        PseudoElement::Marker,
+        style,
+    );
+    // An image that fails to load falls back to `list-style-type`.
+    // https://drafts.csswg.org/css-lists-3/#image-markers
+    let image = match &style.list.list_style_image {
+        ListStyleImage::Url(url) => ReplacedContent::from_url(url),
+        ListStyleImage::None => None,
+    };
+    let contents = match image {
+        Some(image) => vec![
+            PseudoElementContentItem::Replaced(image),
+            PseudoElementContentItem::Text(" ".into()),
+        ],
+        None => {
+            let text = style
+                .list
+                .list_style_type
+                .marker_text(ordinal(context.document, element))?;
+            vec![PseudoElementContentItem::Text(text)]
+        }
+    };
+    Some((marker_style, contents))
+}
+
+/// The number of a list item, taking into account the `start` and `reversed`
+/// attributes of `<ol>` and the `value` attribute of `<li>`.
+/// https://html.spec.whatwg.org/multipage/grouping-content.html#ordinal-value
+pub(super) fn ordinal(document: &dom::Document, element: dom::NodeId) -> i32 {
+    let integer_attr = |node: dom::NodeId, name: &html5ever::LocalName| {
+        document[node].as_element()?.get_attr(name)?.trim().parse::<i32>().ok()
+    };
+    let owner = match document[element].parent {
+        Some(parent) if document[parent].as_element().is_some() => parent,
+        _ => return integer_attr(element, &local_name!("value")).unwrap_or(1),
+    };
+    let items: Vec<dom::NodeId> = document[owner]
+        .first_child
+        .into_iter()
+        .flat_map(|first| document.node_and_following_siblings(first))
+        .filter(|&node| {
+            node == element
+                || document[node]
+                    .as_element()
+                    .is_some_and(|e| e.name.local == local_name!("li"))
+        })
+        .collect();
+    let owner_element = document[owner].as_element().unwrap();
+    let is_ol = owner_element.name.local == local_name!("ol");
+    let reversed = is_ol && owner_element.get_attr(&local_name!("reversed")).is_some();
+    let increment = if reversed { -1 } else { 1 };
+    let start = is_ol
+        .then(|| integer_attr(owner, &local_name!("start")))
+        .flatten()
+        .unwrap_or(if reversed { items.len() as i32 } else { 1 });
+
+    let mut value = start;
+    for item in items {
+        if let Some(item_value) = integer_attr(item, &local_name!("value")) {
+            value = item_value;
+        }
+        if item == element {
+            break;
+        }
+        value += increment;
+    }
+    value
+}
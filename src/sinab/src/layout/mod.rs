@@ -6,20 +6,39 @@ use crate::style::{style_for_element, ComputedValues};
 use std::convert::TryInto;
 use std::sync::Arc;
 
+mod counters;
 mod dom_traversal;
 mod element_data;
+mod flex;
 mod flow;
 mod fragments;
+mod grid;
+mod items;
+mod lists;
+mod multicol;
 mod positioned;
 mod replaced;
+mod sizing;
+mod table;
+mod tests; // geometry of laid out documents
 
+use counters::*;
 use dom_traversal::*;
+use flex::*;
 use flow::*;
+use grid::*;
+use items::*;
+use lists::*;
+use multicol::*;
 use positioned::*;
 use replaced::*;
+use sizing::*;
+use table::*;
 
 pub(crate) use element_data::*;
 pub(crate) use fragments::*;
+pub(crate) use replaced::{Image, ImageFormat, IntrinsicSizes};
+pub(crate) use sizing::ContentSizes;
 
 /// https://drafts.csswg.org/css-display/#independent-formatting-context
 #[derive(Debug)]
@@ -28,22 +47,43 @@ enum IndependentFormattingContext {
 
     // Not called FC in specs, but behaves close enough
     Replaced(ReplacedContent),
+
+    Table(Table),
+
+    Flex(FlexContainer),
+
+    Grid(GridContainer),
+
+    Multicol(MulticolContainer),
     // Other layout modes go here
 }
 
 enum NonReplacedIFC<'a> {
     Flow(&'a BlockFormattingContext),
+    Table(&'a Table),
+    Flex(&'a FlexContainer),
+    Grid(&'a GridContainer),
+    Multicol(&'a MulticolContainer),
 }
 
 impl IndependentFormattingContext {
     fn construct<'a>(
         context: &'a Context<'a>,
-        style: &'a Arc<ComputedValues>,
+        style: &Arc<ComputedValues>,
         display_inside: DisplayInside,
         contents: Contents,
     ) -> Self {
         match contents.try_into() {
             Ok(non_replaced) => match display_inside {
+                DisplayInside::Flow | DisplayInside::FlowRoot
+                    if style.is_multicol_container() =>
+                {
+                    IndependentFormattingContext::Multicol(MulticolContainer::construct(
+                        context,
+                        style,
+                        non_replaced,
+                    ))
+                }
                 DisplayInside::Flow | DisplayInside::FlowRoot => {
                     IndependentFormattingContext::Flow(BlockFormattingContext::construct(
                         context,
@@ -51,6 +91,19 @@ impl IndependentFormattingContext {
                         non_replaced,
                     ))
                 }
+                DisplayInside::Table => {
+                    IndependentFormattingContext::Table(Table::construct(
+                        context,
+                        style,
+                        non_replaced,
+                    ))
+                }
+                DisplayInside::Flex => IndependentFormattingContext::Flex(
+                    FlexContainer::construct(context, style, non_replaced),
+                ),
+                DisplayInside::Grid => IndependentFormattingContext::Grid(
+                    GridContainer::construct(context, style, non_replaced),
+                ),
             },
             Err(replaced) => IndependentFormattingContext::Replaced(replaced),
         }
@@ -60,17 +113,34 @@ impl IndependentFormattingContext {
         match self {
             IndependentFormattingContext::Replaced(r) => Ok(r),
             IndependentFormattingContext::Flow(f) => Err(NonReplacedIFC::Flow(f)),
+            IndependentFormattingContext::Table(t) => Err(NonReplacedIFC::Table(t)),
+            IndependentFormattingContext::Flex(f) => Err(NonReplacedIFC::Flex(f)),
+            IndependentFormattingContext::Grid(g) => Err(NonReplacedIFC::Grid(g)),
+            IndependentFormattingContext::Multicol(m) => Err(NonReplacedIFC::Multicol(m)),
         }
     }
 
+    /// For replaced content, `containing_block` is expected to have
+    /// the used size of the content box.
     fn layout<'a>(
         &'a self,
+        style: &Arc<ComputedValues>,
         containing_block: &ContainingBlock,
         tree_rank: usize,
         absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
     ) -> FlowChildren {
         match self.as_replaced() {
-            Ok(replaced) => match *replaced {},
+            Ok(replaced) => {
+                let size = Vec2 {
+                    inline: containing_block.inline_size,
+                    block: containing_block.block_size.auto_is(Length::zero),
+                };
+                FlowChildren {
+                    block_size: size.block,
+                    fragments: replaced.make_fragments(style, size),
+                    collapsible_margins_in_children: CollapsedBlockMargins::zero(),
+                }
+            }
             Err(ifc) => ifc.layout(containing_block, tree_rank, absolutely_positioned_fragments),
         }
     }
@@ -87,6 +157,18 @@ impl<'a> NonReplacedIFC<'a> {
             NonReplacedIFC::Flow(bfc) => {
                 bfc.layout(containing_block, tree_rank, absolutely_positioned_fragments)
             }
+            NonReplacedIFC::Table(table) => {
+                table.layout(containing_block, tree_rank, absolutely_positioned_fragments)
+            }
+            NonReplacedIFC::Flex(flex) => {
+                flex.layout(containing_block, tree_rank, absolutely_positioned_fragments)
+            }
+            NonReplacedIFC::Grid(grid) => {
+                grid.layout(containing_block, tree_rank, absolutely_positioned_fragments)
+            }
+            NonReplacedIFC::Multicol(multicol) => {
+                multicol.layout(containing_block, tree_rank, absolutely_positioned_fragments)
+            }
         }
     }
 }
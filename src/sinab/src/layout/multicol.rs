@@ -0,0 +1,321 @@
+//! Multi-column layout.
+//!
+//! The contents of a multi-column container are laid out as a single column
+//! at the used column width, then split into columns of equal block size.
+//! Columns are balanced: their block size is the smallest one fitting the
+//! contents in the used column count without breaking lines or atomic boxes.
+//! https://drafts.csswg.org/css-multicol/
+
+use super::*;
+
+/// https://drafts.csswg.org/css-multicol/#multi-column-container
+#[derive(Debug)]
+pub(super) struct MulticolContainer {
+    pub style: Arc<ComputedValues>,
+    pub contents: BlockFormattingContext,
+}
+
+impl MulticolContainer {
+    pub(super) fn construct<'a>(
+        context: &'a Context<'a>,
+        style: &Arc<ComputedValues>,
+        contents: NonReplacedContents,
+    ) -> Self {
+        MulticolContainer {
+            style: style.clone(),
+            contents: BlockFormattingContext::construct(context, style, contents),
+        }
+    }
+
+    /// https://drafts.csswg.org/css-multicol/#cg
+    fn gap(&self, inline_size: Length) -> Length {
+        // `normal` is `1em` in multi-column layout.
+        self.style
+            .flex
+            .column_gap
+            .percentage_relative_to(inline_size, self.style.font.font_size.0)
+    }
+
+    /// The content sizes of the content box of the multi-column container:
+    /// that of a single column, and that of the specified count of columns
+    /// as wide as their contents.
+    pub(super) fn inline_content_sizes(&self) -> ContentSizes {
+        let column = self.contents.contents.inline_content_sizes();
+        let column_width = match self.style.multicol.column_width {
+            LengthOrPercentageOrAuto::Length(width) => width.max(column.min_content),
+            _ => column.max_content,
+        };
+        let count = match self.style.multicol.column_count {
+            ColumnCount::Integer(count) => count as f32,
+            ColumnCount::Auto => 1.,
+        };
+        ContentSizes {
+            min_content: column.min_content,
+            max_content: column_width * count + self.gap(Length::zero()) * (count - 1.),
+        }
+    }
+
+    /// The used count and inline size of columns.
+    /// https://drafts.csswg.org/css-multicol/#pseudo-algorithm
+    fn column_count_and_width(&self, available: Length, gap: Length) -> (usize, Length) {
+        let multicol = &self.style.multicol;
+        let width = match multicol.column_width.percentage_relative_to(available) {
+            LengthOrAuto::Length(width) => Some(width.max(Length { px: 1. })),
+            LengthOrAuto::Auto => None,
+        };
+        let fitting = |width: Length| ((available + gap).px / (width + gap).px).floor().max(1.);
+        let count = match (multicol.column_count, width) {
+            (ColumnCount::Integer(count), None) => count as f32,
+            (ColumnCount::Auto, Some(width)) => fitting(width),
+            (ColumnCount::Integer(count), Some(width)) => fitting(width).min(count as f32),
+            (ColumnCount::Auto, None) => 1.,
+        };
+        let width = ((available + gap) / count - gap).max(Length::zero());
+        (count as usize, width)
+    }
+
+    pub(super) fn layout<'a>(
+        &'a self,
+        containing_block: &ContainingBlock,
+        tree_rank: usize,
+        absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    ) -> FlowChildren {
+        let gap = self.gap(containing_block.inline_size);
+        let (count, column_width) =
+            self.column_count_and_width(containing_block.inline_size, gap);
+        let containing_block_for_columns = ContainingBlock {
+            inline_size: column_width,
+            block_size: LengthOrAuto::Auto,
+            mode: containing_block.mode,
+        };
+        // FIXME: the static positions of absolutely-positioned descendants
+        // do not follow their placeholders into columns.
+        let flow_children = self.contents.layout(
+            &containing_block_for_columns,
+            tree_rank,
+            absolutely_positioned_fragments,
+        );
+
+        let mut pieces = vec![];
+        unbreakable_pieces(&flow_children.fragments, Length::zero(), &mut pieces);
+        let column_block_size = match containing_block.block_size {
+            LengthOrAuto::Length(block_size) => block_size,
+            LengthOrAuto::Auto => balanced_block_size(&pieces, count, flow_children.block_size),
+        };
+        let column_starts = column_starts(&pieces, column_block_size);
+
+        let mut columns: Vec<Vec<Fragment>> = column_starts.iter().map(|_| vec![]).collect();
+        for fragment in flow_children.fragments {
+            for (column, mut fragment) in split_into_columns(fragment, &column_starts) {
+                fragment.translate_block(-column_starts[column]);
+                columns[column].push(fragment)
+            }
+        }
+        let fragments = columns
+            .into_iter()
+            .enumerate()
+            .map(|(index, children)| {
+                Fragment::Anonymous(AnonymousFragment {
+                    rect: Rect {
+                        start_corner: Vec2 {
+                            inline: (column_width + gap) * index as f32,
+                            block: Length::zero(),
+                        },
+                        size: Vec2 {
+                            inline: column_width,
+                            block: column_block_size,
+                        },
+                    },
+                    children,
+                    mode: containing_block.mode,
+                    baseline: None,
+                })
+            })
+            .collect();
+        FlowChildren {
+            fragments,
+            block_size: column_block_size,
+            collapsible_margins_in_children: CollapsedBlockMargins::zero(),
+        }
+    }
+}
+
+/// A range of block positions that cannot be split across columns.
+struct Piece {
+    start: Length,
+    end: Length,
+}
+
+/// Block containers can be split between their children.
+/// Lines, atomic boxes and other formatting contexts cannot.
+fn is_breakable(fragment: &BoxFragment) -> bool {
+    let flow = matches!(
+        fragment.style.box_.display,
+        Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+            inside: DisplayInside::Flow,
+            ..
+        })
+    );
+    flow && !fragment.children.is_empty() && !fragment.style.is_multicol_container()
+}
+
+/// Collects the unbreakable pieces of the given fragments, in document order.
+fn unbreakable_pieces(fragments: &[Fragment], offset: Length, pieces: &mut Vec<Piece>) {
+    for fragment in fragments {
+        let rect = match fragment {
+            Fragment::Box(b) if is_breakable(b) => {
+                let offset = offset + b.content_rect.start_corner.block;
+                unbreakable_pieces(&b.children, offset, pieces);
+                continue;
+            }
+            Fragment::Box(b) => b.border_rect(),
+            Fragment::Anonymous(a) => a.rect.clone(),
+            Fragment::Text(t) => t.content_rect.clone(),
+            Fragment::Image(i) => i.clip_rect.clone(),
+        };
+        if rect.size.block > Length::zero() {
+            pieces.push(Piece {
+                start: offset + rect.start_corner.block,
+                end: offset + rect.start_corner.block + rect.size.block,
+            })
+        }
+    }
+}
+
+/// The block positions at which columns start, when filling columns
+/// of the given block size. There is always at least one column.
+fn column_starts(pieces: &[Piece], column_block_size: Length) -> Vec<Length> {
+    // Tolerance for rounding errors in block positions.
+    let epsilon = Length { px: 0.01 };
+    let mut starts = vec![Length::zero()];
+    let mut column_start = Length::zero();
+    for piece in pieces {
+        if piece.end - column_start > column_block_size + epsilon
+            && piece.start > column_start + epsilon
+        {
+            column_start = piece.start;
+            starts.push(column_start)
+        }
+    }
+    starts
+}
+
+/// The smallest column block size fitting the pieces in the given count of columns.
+/// https://drafts.csswg.org/css-multicol/#cf
+fn balanced_block_size(pieces: &[Piece], count: usize, total_block_size: Length) -> Length {
+    let tallest_piece = pieces
+        .iter()
+        .map(|piece| piece.end - piece.start)
+        .fold(Length::zero(), Length::max);
+    let (mut low, mut high) = (tallest_piece.min(total_block_size), total_block_size);
+    if count <= 1 {
+        return high;
+    }
+    let precision = Length { px: 0.1 };
+    while high - low > precision {
+        let middle = (low + high) / 2.;
+        if column_starts(pieces, middle).len() <= count {
+            high = middle
+        } else {
+            low = middle
+        }
+    }
+    // The bisection only approximates the block size of the tallest column.
+    let epsilon = Length { px: 0.01 };
+    let starts = column_starts(pieces, high);
+    pieces
+        .iter()
+        .map(|piece| {
+            let column = starts.iter().rposition(|&start| start <= piece.start + epsilon);
+            piece.end - starts[column.unwrap_or(0)]
+        })
+        .fold(Length::zero(), Length::max)
+}
+
+/// Splits a fragment between the columns starting at the given block positions,
+/// which are in the same coordinate space as the fragment.
+/// Boxes broken across columns are sliced, with their block-start and block-end
+/// borders, padding and margins on the first and last slices only.
+/// https://drafts.csswg.org/css-break/#box-splitting
+fn split_into_columns(fragment: Fragment, column_starts: &[Length]) -> Vec<(usize, Fragment)> {
+    let epsilon = Length { px: 0.01 };
+    let column_at = |block: Length| {
+        column_starts
+            .iter()
+            .rposition(|&start| start <= block + epsilon)
+            .unwrap_or(0)
+    };
+    let mut b = match fragment {
+        Fragment::Box(b) if is_breakable(&b) => b,
+        Fragment::Box(b) => return vec![(column_at(b.border_rect().start_corner.block), Fragment::Box(b))],
+        Fragment::Anonymous(ref a) => return vec![(column_at(a.rect.start_corner.block), fragment)],
+        Fragment::Text(ref t) => return vec![(column_at(t.content_rect.start_corner.block), fragment)],
+        Fragment::Image(ref i) => return vec![(column_at(i.clip_rect.start_corner.block), fragment)],
+    };
+
+    let content_start = b.content_rect.start_corner.block;
+    let content_end = content_start + b.content_rect.size.block;
+    let child_column_starts: Vec<Length> = column_starts
+        .iter()
+        .map(|&start| start - content_start)
+        .collect();
+    let first = column_at(b.border_rect().start_corner.block);
+    let mut children: Vec<(usize, Fragment)> = vec![];
+    for child in std::mem::take(&mut b.children) {
+        children.extend(split_into_columns(child, &child_column_starts))
+    }
+    let last = children.iter().map(|(column, _)| *column).fold(first, usize::max);
+    if first == last {
+        let children = children.into_iter().map(|(_, child)| child).collect();
+        return vec![(first, Fragment::Box(BoxFragment { children, ..b }))];
+    }
+
+    let mut slices: Vec<(usize, BoxFragment)> = (first..=last)
+        .map(|column| {
+            let start = if column == first {
+                content_start
+            } else {
+                column_starts[column]
+            };
+            let end = if column == last {
+                content_end
+            } else {
+                content_end.min(column_starts[column + 1])
+            };
+            let mut rect = b.content_rect.clone();
+            rect.start_corner.block = start;
+            rect.size.block = (end - start).max(Length::zero());
+            let (mut padding, mut border, mut margin) =
+                (b.padding.clone(), b.border.clone(), b.margin.clone());
+            if column != first {
+                padding.block_start = Length::zero();
+                border.block_start = Length::zero();
+                margin.block_start = Length::zero();
+            }
+            if column != last {
+                padding.block_end = Length::zero();
+                border.block_end = Length::zero();
+                margin.block_end = Length::zero();
+            }
+            let slice = BoxFragment {
+                style: b.style.clone(),
+                children: vec![],
+                content_rect: rect,
+                padding,
+                border,
+                margin,
+                block_margins_collapsed_with_children: CollapsedBlockMargins::zero(),
+            };
+            (column, slice)
+        })
+        .collect();
+    for (column, mut child) in children {
+        let (_, slice) = &mut slices[column - first];
+        child.translate_block(content_start - slice.content_rect.start_corner.block);
+        slice.children.push(child)
+    }
+    slices
+        .into_iter()
+        .map(|(column, slice)| (column, Fragment::Box(slice)))
+        .collect()
+}
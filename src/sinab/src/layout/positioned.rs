@@ -16,10 +16,7 @@ pub(super) struct AbsolutelyPositionedFragment<'box_> {
     pub(super) tree_rank: usize,
 
     pub(super) inline_start: AbsoluteBoxOffsets<LengthOrPercentage>,
-    inline_size: LengthOrPercentageOrAuto,
-
     pub(super) block_start: AbsoluteBoxOffsets<LengthOrPercentage>,
-    block_size: LengthOrPercentageOrAuto,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -38,10 +35,6 @@ impl AbsolutelyPositionedBox {
     ) -> AbsolutelyPositionedFragment {
         let style = &self.style;
         let box_offsets = style.box_offsets();
-        let box_size = style.box_size();
-
-        let inline_size = box_size.inline;
-        let block_size = box_size.block;
 
         fn absolute_box_offsets(
             initial_static_start: Length,
@@ -73,9 +66,7 @@ impl AbsolutelyPositionedBox {
             absolutely_positioned_box: self,
             tree_rank,
             inline_start,
-            inline_size,
             block_start,
-            block_size,
         }
     }
 }
@@ -108,6 +99,7 @@ impl<'a> AbsolutelyPositionedFragment<'a> {
                 .collect(),
             rect: padding_rect,
             mode,
+            baseline: None,
         }))
     }
 
@@ -120,12 +112,19 @@ impl<'a> AbsolutelyPositionedFragment<'a> {
         let border = style.border_width().percentages_relative_to(cbis);
         let computed_margin = style.margin().percentages_relative_to(cbis);
         let pb = &padding + &border;
+        let content_box_sizes = ContentBoxSizes::new(
+            style,
+            LengthOrAuto::Length(cbis),
+            LengthOrAuto::Length(cbbs),
+            &pb,
+        );
 
         enum Anchor {
             Start(Length),
             End(Length),
         }
 
+        #[allow(clippy::too_many_arguments)]
         fn solve_axis(
             containing_size: Length,
             padding_border_sum: Length,
@@ -133,9 +132,10 @@ impl<'a> AbsolutelyPositionedFragment<'a> {
             computed_margin_end: LengthOrAuto,
             solve_margins: impl FnOnce(Length) -> (Length, Length),
             box_offsets: AbsoluteBoxOffsets<LengthOrPercentage>,
-            size: LengthOrPercentageOrAuto,
+            size: LengthOrAuto,
+            clamp: impl Fn(Length) -> Length,
         ) -> (Anchor, LengthOrAuto, Length, Length) {
-            let size = size.percentage_relative_to(containing_size);
+            let size = size.map(&clamp);
             match box_offsets {
                 AbsoluteBoxOffsets::StaticStart { start } => (
                     Anchor::Start(start),
@@ -162,32 +162,41 @@ impl<'a> AbsolutelyPositionedFragment<'a> {
                     let mut margin_start = computed_margin_start.auto_is(Length::zero);
                     let mut margin_end = computed_margin_end.auto_is(Length::zero);
 
-                    let size = if let LengthOrAuto::Length(size) = size {
-                        use LengthOrAuto::Auto;
-                        let margins = containing_size - start - end - padding_border_sum - size;
-                        match (computed_margin_start, computed_margin_end) {
-                            (Auto, Auto) => {
-                                let (s, e) = solve_margins(margins);
-                                margin_start = s;
-                                margin_end = e;
-                            }
-                            (Auto, LengthOrAuto::Length(end)) => {
-                                margin_start = margins - end;
-                            }
-                            (LengthOrAuto::Length(start), Auto) => {
-                                margin_end = margins - start;
+                    // The used size is never negative: when the offsets leave
+                    // no room for the box, it overflows at the end.
+                    let fill = (containing_size
+                        - start
+                        - end
+                        - padding_border_sum
+                        - margin_start
+                        - margin_end)
+                        .max(Length::zero());
+                    // An auto size fills the space between the offsets, unless
+                    // that violates its minimum or maximum size: it is then
+                    // solved for as if specified.
+                    // https://drafts.csswg.org/css2/visudet.html#min-max-widths
+                    let size = match size {
+                        LengthOrAuto::Auto if clamp(fill) == fill => fill,
+                        size => {
+                            use LengthOrAuto::Auto;
+                            let size = size.auto_is(|| clamp(fill));
+                            let margins = containing_size - start - end - padding_border_sum - size;
+                            match (computed_margin_start, computed_margin_end) {
+                                (Auto, Auto) => {
+                                    let (s, e) = solve_margins(margins);
+                                    margin_start = s;
+                                    margin_end = e;
+                                }
+                                (Auto, LengthOrAuto::Length(end)) => {
+                                    margin_start = margins - end;
+                                }
+                                (LengthOrAuto::Length(start), Auto) => {
+                                    margin_end = margins - start;
+                                }
+                                (LengthOrAuto::Length(_), LengthOrAuto::Length(_)) => {}
                             }
-                            (LengthOrAuto::Length(_), LengthOrAuto::Length(_)) => {}
+                            size
                         }
-                        size
-                    } else {
-                        // FIXME(nox): What happens if that is negative?
-                        containing_size
-                            - start
-                            - end
-                            - padding_border_sum
-                            - margin_start
-                            - margin_end
                     };
                     (
                         Anchor::Start(start),
@@ -199,6 +208,26 @@ impl<'a> AbsolutelyPositionedFragment<'a> {
             }
         }
 
+        let (inline_size, block_size) = match self.absolutely_positioned_box.contents.as_replaced() {
+            // https://drafts.csswg.org/css2/visudet.html#abs-replaced-width
+            // https://drafts.csswg.org/css2/visudet.html#abs-replaced-height
+            Ok(replaced) => {
+                let used_size = replaced.used_size_as_if_inline_element(
+                    &ContainingBlock {
+                        inline_size: cbis,
+                        block_size: LengthOrAuto::Length(cbbs),
+                        mode: containing_block.mode,
+                    },
+                    style,
+                );
+                (
+                    LengthOrAuto::Length(used_size.inline),
+                    LengthOrAuto::Length(used_size.block),
+                )
+            }
+            Err(_) => (content_box_sizes.box_size.inline, content_box_sizes.box_size.block),
+        };
+
         let (inline_anchor, inline_size, margin_inline_start, margin_inline_end) = solve_axis(
             cbis,
             pb.inline_sum(),
@@ -212,17 +241,19 @@ impl<'a> AbsolutelyPositionedFragment<'a> {
                 }
             },
             self.inline_start,
-            self.inline_size,
+            inline_size,
+            |size| content_box_sizes.clamp_inline(size),
         );
 
         let (block_anchor, block_size, margin_block_start, margin_block_end) = solve_axis(
-            cbis,
+            cbbs,
             pb.block_sum(),
             computed_margin.block_start,
             computed_margin.block_end,
             |margins| (margins / 2., margins / 2.),
             self.block_start,
-            self.block_size,
+            block_size,
+            |size| content_box_sizes.clamp_block(size),
         );
 
         let margin = Sides {
@@ -232,14 +263,18 @@ impl<'a> AbsolutelyPositionedFragment<'a> {
             block_end: margin_block_end,
         };
 
+        // https://drafts.csswg.org/css2/visudet.html#abs-non-replaced-width
         let inline_size = inline_size.auto_is(|| {
             let available_size = match inline_anchor {
                 Anchor::Start(start) => cbis - start - pb.inline_sum() - margin.inline_sum(),
                 Anchor::End(end) => cbis - end - pb.inline_sum() - margin.inline_sum(),
             };
-
-            // FIXME(nox): shrink-to-fit.
-            available_size
+            let inline_size = self
+                .absolutely_positioned_box
+                .contents
+                .inline_content_sizes(style)
+                .shrink_to_fit(available_size);
+            content_box_sizes.clamp_inline(inline_size)
         });
 
         let containing_block_for_children = ContainingBlock {
@@ -255,6 +290,7 @@ impl<'a> AbsolutelyPositionedFragment<'a> {
         let dummy_tree_rank = 0;
         let mut absolutely_positioned_fragments = vec![];
         let mut flow_children = self.absolutely_positioned_box.contents.layout(
+            style,
             &containing_block_for_children,
             dummy_tree_rank,
             &mut absolutely_positioned_fragments,
@@ -262,10 +298,11 @@ impl<'a> AbsolutelyPositionedFragment<'a> {
 
         let inline_start = match inline_anchor {
             Anchor::Start(start) => start + pb.inline_start + margin.inline_start,
-            Anchor::End(end) => cbbs - end - pb.inline_end - margin.inline_end - inline_size,
+            Anchor::End(end) => cbis - end - pb.inline_end - margin.inline_end - inline_size,
         };
 
-        let block_size = block_size.auto_is(|| flow_children.block_size);
+        let block_size =
+            block_size.auto_is(|| content_box_sizes.clamp_block(flow_children.block_size));
         let block_start = match block_anchor {
             Anchor::Start(start) => start + pb.block_start + margin.block_start,
             Anchor::End(end) => cbbs - end - pb.block_end - margin.block_end - block_size,
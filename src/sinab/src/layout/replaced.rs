@@ -1,14 +1,444 @@
 use super::*;
-use crate::dom::NodeId;
+use crate::dom::{Document, NodeId};
+use crate::geom::physical;
+use html5ever::LocalName;
+
+#[cfg(test)]
+mod tests; // image files and sizing
+
+/// Content of a replaced element. Only `<img>` pointing to a local PNG, JPEG,
+/// or SVG file is supported for now.
+/// https://drafts.csswg.org/css2/conform.html#replaced-element
+#[derive(Debug)]
+pub(super) struct ReplacedContent {
+    pub kind: ReplacedContentKind,
+    pub intrinsic: IntrinsicSizes,
+    /// Values of the `width` and `height` attributes. These are presentational
+    /// hints and are only used where the corresponding CSS property is `auto`.
+    /// https://html.spec.whatwg.org/multipage/#dimRendering
+    attribute_size: physical::Vec2<LengthOrPercentageOrAuto>,
+}
+
+/// https://drafts.csswg.org/css-images/#intrinsic-dimensions
+#[derive(Debug)]
+pub(crate) struct IntrinsicSizes {
+    pub width: Option<Length>,
+    pub height: Option<Length>,
+    /// Width divided by height
+    pub ratio: Option<f32>,
+}
 
 #[derive(Debug)]
-pub(super) enum ReplacedContent {
-    // Not implemented yet
+pub(super) enum ReplacedContentKind {
+    Image(Arc<Image>),
+}
+
+#[derive(Debug)]
+pub(crate) struct Image {
+    pub path: String,
+    pub format: ImageFormat,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ImageFormat {
+    Png,
+    Jpeg,
+    Svg,
 }
 
 impl ReplacedContent {
-    pub fn for_element(_element: NodeId, _context: &Context) -> Option<Self> {
-        // FIXME: implement <img> etc.
-        None
+    pub fn for_element(element: NodeId, context: &Context) -> Option<Self> {
+        let element = context.document[element].as_element()?;
+        if element.name.local != local_name!("img") {
+            return None;
+        }
+        let src = element.get_attr(&local_name!("src"))?;
+        let mut replaced = Self::from_url(src)?;
+        replaced.attribute_size = physical::Vec2 {
+            x: parse_dimension_value(element.get_attr(&local_name!("width"))),
+            y: parse_dimension_value(element.get_attr(&local_name!("height"))),
+        };
+        Some(replaced)
+    }
+
+    /// An image referenced from CSS, such as by `list-style-image`.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let (image, intrinsic) = Image::load(url)?;
+        Some(Self {
+            kind: ReplacedContentKind::Image(Arc::new(image)),
+            intrinsic,
+            attribute_size: physical::Vec2 {
+                x: LengthOrPercentageOrAuto::Auto,
+                y: LengthOrPercentageOrAuto::Auto,
+            },
+        })
+    }
+
+    fn flow_relative_intrinsic_size(&self, style: &ComputedValues) -> Vec2<Option<Length>> {
+        physical::Vec2 {
+            x: self.intrinsic.width,
+            y: self.intrinsic.height,
+        }
+        .size_to_flow_relative(style.writing_mode())
+    }
+
+    /// Inline size divided by block size
+    fn inline_size_over_block_size_intrinsic_ratio(&self, style: &ComputedValues) -> Option<f32> {
+        self.intrinsic.ratio.map(|width_over_height| {
+            if let (WritingMode::HorizontalTb, _) = style.writing_mode() {
+                width_over_height
+            } else {
+                1. / width_over_height
+            }
+        })
+    }
+
+    /// https://drafts.csswg.org/css2/visudet.html#inline-replaced-width
+    /// https://drafts.csswg.org/css2/visudet.html#inline-replaced-height
+    pub fn used_size_as_if_inline_element(
+        &self,
+        containing_block: &ContainingBlock,
+        style: &ComputedValues,
+    ) -> Vec2<Length> {
+        self.used_size(
+            LengthOrAuto::Length(containing_block.inline_size),
+            containing_block.block_size,
+            style,
+        )
+    }
+
+    /// The used size in a containing block of unknown size, where percentages
+    /// behave as `auto` sizes and `none` maximum sizes.
+    pub fn used_size_in_indefinite_containing_block(&self, style: &ComputedValues) -> Vec2<Length> {
+        self.used_size(LengthOrAuto::Auto, LengthOrAuto::Auto, style)
+    }
+
+    fn used_size(
+        &self,
+        containing_block_inline_size: LengthOrAuto,
+        containing_block_block_size: LengthOrAuto,
+        style: &ComputedValues,
+    ) -> Vec2<Length> {
+        let mode = style.writing_mode();
+        let cbis = containing_block_inline_size;
+        let cbbs = containing_block_block_size;
+        let padding = style.padding().percentages_relative_to(cbis.auto_is(Length::zero));
+        let border = style.border_width().percentages_relative_to(cbis.auto_is(Length::zero));
+        let content_box_sizes = ContentBoxSizes::new(style, cbis, cbbs, &(&padding + &border));
+        // The `width` and `height` attributes size the content box.
+        let attribute_size = self.attribute_size.size_to_flow_relative(mode);
+        let resolve = |size: LengthOrPercentageOrAuto, containing_size: LengthOrAuto| {
+            match size {
+                LengthOrPercentageOrAuto::Length(l) => LengthOrAuto::Length(l),
+                LengthOrPercentageOrAuto::Percentage(p) => containing_size.map(|size| size * p),
+                LengthOrPercentageOrAuto::Auto => LengthOrAuto::Auto,
+            }
+        };
+        let inline_size = match content_box_sizes.box_size.inline {
+            LengthOrAuto::Auto => resolve(attribute_size.inline, cbis),
+            inline_size => inline_size,
+        };
+        let block_size = match content_box_sizes.box_size.block {
+            LengthOrAuto::Auto => resolve(attribute_size.block, cbbs),
+            block_size => block_size,
+        };
+        let inline_size = inline_size.map(|inline| content_box_sizes.clamp_inline(inline));
+        let block_size = block_size.map(|block| content_box_sizes.clamp_block(block));
+
+        let intrinsic_size = self.flow_relative_intrinsic_size(style);
+        let intrinsic_ratio = self.inline_size_over_block_size_intrinsic_ratio(style);
+        // Fallback sizes for content without intrinsic dimensions
+        let default_object_size = Vec2 {
+            inline: Length { px: 300. },
+            block: Length { px: 150. },
+        };
+
+        let size = match (inline_size, block_size) {
+            (LengthOrAuto::Length(inline), LengthOrAuto::Length(block)) => Vec2 { inline, block },
+            (LengthOrAuto::Length(inline), LengthOrAuto::Auto) => {
+                let block = if let Some(i_over_b) = intrinsic_ratio {
+                    inline / i_over_b
+                } else {
+                    intrinsic_size.block.unwrap_or(default_object_size.block)
+                };
+                Vec2 { inline, block }
+            }
+            (LengthOrAuto::Auto, LengthOrAuto::Length(block)) => {
+                let inline = if let Some(i_over_b) = intrinsic_ratio {
+                    block * i_over_b
+                } else {
+                    intrinsic_size.inline.unwrap_or(default_object_size.inline)
+                };
+                Vec2 { inline, block }
+            }
+            (LengthOrAuto::Auto, LengthOrAuto::Auto) => {
+                match (intrinsic_size.inline, intrinsic_size.block, intrinsic_ratio) {
+                    (Some(inline), Some(block), _) => Vec2 { inline, block },
+                    (Some(inline), None, Some(i_over_b)) => Vec2 {
+                        inline,
+                        block: inline / i_over_b,
+                    },
+                    (None, Some(block), Some(i_over_b)) => Vec2 {
+                        inline: block * i_over_b,
+                        block,
+                    },
+                    (None, None, Some(i_over_b)) => {
+                        // FIXME: this should be undefined if the containing block’s
+                        // inline size itself depends on this box.
+                        let inline = cbis.auto_is(|| default_object_size.inline);
+                        Vec2 {
+                            inline,
+                            block: inline / i_over_b,
+                        }
+                    }
+                    (inline, block, None) => Vec2 {
+                        inline: inline.unwrap_or(default_object_size.inline),
+                        block: block.unwrap_or(default_object_size.block),
+                    },
+                }
+            }
+        };
+        // A size resolved from one specified dimension is clamped in the other,
+        // one resolved from neither preserves its ratio where possible.
+        if let (LengthOrAuto::Auto, LengthOrAuto::Auto) = (inline_size, block_size) {
+            content_box_sizes.clamp_preserving_ratio(size)
+        } else {
+            Vec2 {
+                inline: content_box_sizes.clamp_inline(size.inline),
+                block: content_box_sizes.clamp_block(size.block),
+            }
+        }
+    }
+
+    /// Creates the fragments for content laid out in a content box of the
+    /// given size. The image is sized and positioned according to `object-fit`,
+    /// and clipped to the content box.
+    /// https://drafts.csswg.org/css-images-3/#the-object-fit
+    pub fn make_fragments(&self, style: &Arc<ComputedValues>, size: Vec2<Length>) -> Vec<Fragment> {
+        let intrinsic_size = self.flow_relative_intrinsic_size(style);
+        let intrinsic_ratio = self
+            .inline_size_over_block_size_intrinsic_ratio(style)
+            .or_else(|| match (intrinsic_size.inline, intrinsic_size.block) {
+                (Some(inline), Some(block)) if block.px > 0. => Some(inline.px / block.px),
+                _ => None,
+            });
+
+        let contain_or_cover = |cover: bool| match intrinsic_ratio {
+            Some(i_over_b) => {
+                let box_is_wider = size.inline.px > size.block.px * i_over_b;
+                if box_is_wider != cover {
+                    Vec2 {
+                        inline: size.block * i_over_b,
+                        block: size.block,
+                    }
+                } else {
+                    Vec2 {
+                        inline: size.inline,
+                        block: size.inline / i_over_b,
+                    }
+                }
+            }
+            None => size.clone(),
+        };
+        let none = || Vec2 {
+            inline: intrinsic_size.inline.unwrap_or(size.inline),
+            block: intrinsic_size.block.unwrap_or(size.block),
+        };
+        let object_size = match style.box_.object_fit {
+            ObjectFit::Fill => size.clone(),
+            ObjectFit::Contain => contain_or_cover(false),
+            ObjectFit::Cover => contain_or_cover(true),
+            ObjectFit::None => none(),
+            ObjectFit::ScaleDown => {
+                let contain = contain_or_cover(false);
+                let none = none();
+                if none.inline < contain.inline {
+                    none
+                } else {
+                    contain
+                }
+            }
+        };
+
+        // FIXME: `object-position`, for now always centered
+        let rect = Rect {
+            start_corner: Vec2 {
+                inline: (size.inline - object_size.inline) / 2.,
+                block: (size.block - object_size.block) / 2.,
+            },
+            size: object_size,
+        };
+        let clip_rect = Rect {
+            start_corner: Vec2::zero(),
+            size,
+        };
+        match &self.kind {
+            ReplacedContentKind::Image(image) => vec![Fragment::Image(ImageFragment {
+                style: style.clone(),
+                rect,
+                clip_rect,
+                image: image.clone(),
+            })],
+        }
+    }
+}
+
+impl Image {
+    /// Reads the intrinsic dimensions of an image file. Returns `None` if the
+    /// file can’t be read or is not in a supported format.
+    pub(crate) fn load(src: &str) -> Option<(Self, IntrinsicSizes)> {
+        let path = src.strip_prefix("file://").unwrap_or(src);
+        let bytes = std::fs::read(path).ok()?;
+        let (format, intrinsic) = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            (ImageFormat::Png, raster_intrinsic_sizes(png_size(&bytes)?))
+        } else if bytes.starts_with(&[0xFF, 0xD8]) {
+            (ImageFormat::Jpeg, raster_intrinsic_sizes(jpeg_size(&bytes)?))
+        } else {
+            (ImageFormat::Svg, svg_intrinsic_sizes(&bytes)?)
+        };
+        Some((
+            Image {
+                path: path.to_owned(),
+                format,
+            },
+            intrinsic,
+        ))
+    }
+}
+
+/// One image pixel is one CSS px.
+fn raster_intrinsic_sizes((width, height): (u32, u32)) -> IntrinsicSizes {
+    IntrinsicSizes {
+        width: Some(Length { px: width as f32 }),
+        height: Some(Length { px: height as f32 }),
+        ratio: if height > 0 {
+            Some(width as f32 / height as f32)
+        } else {
+            None
+        },
+    }
+}
+
+fn read_u16_be(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([*bytes.get(at)?, *bytes.get(at + 1)?]))
+}
+
+fn read_u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes([
+        *bytes.get(at)?,
+        *bytes.get(at + 1)?,
+        *bytes.get(at + 2)?,
+        *bytes.get(at + 3)?,
+    ]))
+}
+
+/// The IHDR chunk always comes first, right after the 8-byte signature.
+/// https://www.w3.org/TR/PNG/#11IHDR
+fn png_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((read_u32_be(bytes, 16)?, read_u32_be(bytes, 20)?))
+}
+
+/// Walks the marker segments until the first start-of-frame segment,
+/// which holds the image dimensions.
+fn jpeg_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut position = 2;
+    loop {
+        if *bytes.get(position)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(position + 1)?;
+        match marker {
+            // Fill bytes
+            0xFF => position += 1,
+            // Markers without a payload
+            0x01 | 0xD0..=0xD7 => position += 2,
+            // SOF0 to SOF15, except DHT, JPG and DAC
+            0xC0..=0xCF if marker != 0xC4 && marker != 0xC8 && marker != 0xCC => {
+                let height = read_u16_be(bytes, position + 5)?;
+                let width = read_u16_be(bytes, position + 7)?;
+                return Some((width as u32, height as u32));
+            }
+            _ => position += 2 + read_u16_be(bytes, position + 2)? as usize,
+        }
+    }
+}
+
+/// Reads `width`, `height`, and `viewBox` from the root `<svg>` element.
+/// https://svgwg.org/svg2-draft/coords.html#SizingSVGInCSS
+fn svg_intrinsic_sizes(bytes: &[u8]) -> Option<IntrinsicSizes> {
+    let document = Document::parse_html(bytes);
+    let svg = document.nodes().find_map(|id| {
+        document[id]
+            .as_element()
+            .filter(|e| e.name.local == local_name!("svg"))
+    })?;
+    let width = svg.get_attr(&local_name!("width")).and_then(parse_svg_length);
+    let height = svg.get_attr(&local_name!("height")).and_then(parse_svg_length);
+    let view_box_ratio = svg
+        .get_attr(&LocalName::from("viewBox"))
+        .and_then(|view_box| {
+            let values: Vec<f32> = view_box
+                .split(|c: char| c == ',' || c.is_ascii_whitespace())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse().ok())
+                .collect::<Option<_>>()?;
+            match values[..] {
+                [_, _, w, h] if w > 0. && h > 0. => Some(w / h),
+                _ => None,
+            }
+        });
+    let ratio = match (width, height) {
+        (Some(w), Some(h)) if h.px > 0. => Some(w.px / h.px),
+        _ => view_box_ratio,
+    };
+    Some(IntrinsicSizes {
+        width,
+        height,
+        ratio,
+    })
+}
+
+/// Parses an absolute SVG length. Percentages don’t give an intrinsic size.
+fn parse_svg_length(value: &str) -> Option<Length> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+' || c == 'e'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f32 = number.parse().ok()?;
+    let px_per_unit = match unit.trim() {
+        "" | "px" => 1.,
+        "in" => 96.,
+        "cm" => 96. / 2.54,
+        "mm" => 96. / 25.4,
+        "pt" => 96. / 72.,
+        "pc" => 96. / 6.,
+        _ => return None,
+    };
+    Some(Length {
+        px: number * px_per_unit,
+    })
+}
+
+/// https://html.spec.whatwg.org/multipage/#rules-for-parsing-dimension-values
+fn parse_dimension_value(value: Option<&str>) -> LengthOrPercentageOrAuto {
+    let value = match value {
+        Some(value) => value.trim_start(),
+        None => return LengthOrPercentageOrAuto::Auto,
+    };
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    match value[..split].parse::<f32>() {
+        Ok(number) if value[split..].starts_with('%') => {
+            LengthOrPercentageOrAuto::Percentage(Percentage {
+                unit_value: number / 100.,
+            })
+        }
+        Ok(number) => LengthOrPercentageOrAuto::Length(Length { px: number }),
+        Err(_) => LengthOrPercentageOrAuto::Auto,
     }
 }
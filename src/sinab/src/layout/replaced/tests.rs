@@ -0,0 +1,152 @@
+use super::*;
+
+/// The signature and IHDR chunk of a PNG file, which is all sizing reads.
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    bytes.extend_from_slice(&width.to_be_bytes());
+    bytes.extend_from_slice(&height.to_be_bytes());
+    bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+    bytes
+}
+
+/// An image of the given intrinsic size, with `width` and `height` attributes.
+fn image(width: f32, height: f32, attributes: [Option<&str>; 2]) -> ReplacedContent {
+    ReplacedContent {
+        kind: ReplacedContentKind::Image(Arc::new(Image {
+            path: "image.png".into(),
+            format: ImageFormat::Png,
+        })),
+        intrinsic: raster_intrinsic_sizes((width as u32, height as u32)),
+        attribute_size: physical::Vec2 {
+            x: parse_dimension_value(attributes[0]),
+            y: parse_dimension_value(attributes[1]),
+        },
+    }
+}
+
+/// The computed style of an element with the given declarations.
+fn style(declarations: &str) -> Arc<ComputedValues> {
+    let html = format!(r#"<p style="{}">"#, declarations);
+    let document = Document::parse_html(html.as_bytes());
+    let author_styles = document.parse_stylesheets(None);
+    let p = document
+        .nodes()
+        .find(|&id| matches!(document[id].as_element(), Some(e) if e.name.local == local_name!("p")))
+        .unwrap();
+    style_for_element(&author_styles, &document, p, None)
+}
+
+/// The used size in a 400px by 300px containing block, as `[width, height]`.
+fn used_size(image: &ReplacedContent, declarations: &str) -> [f32; 2] {
+    let containing_block = ContainingBlock {
+        inline_size: Length { px: 400. },
+        block_size: LengthOrAuto::Length(Length { px: 300. }),
+        mode: (WritingMode::HorizontalTb, Direction::Ltr),
+    };
+    let size = image.used_size_as_if_inline_element(&containing_block, &style(declarations));
+    [size.inline.px, size.block.px]
+}
+
+/// The rectangle of the image drawn in a 100px square content box,
+/// as `[x, y, width, height]`.
+fn object_rect(image: &ReplacedContent, object_fit: &str) -> [f32; 4] {
+    let style = style(&format!("object-fit: {}", object_fit));
+    let size = Vec2 {
+        inline: Length { px: 100. },
+        block: Length { px: 100. },
+    };
+    match &image.make_fragments(&style, size)[..] {
+        [Fragment::Image(fragment)] => {
+            let rect = &fragment.rect;
+            [
+                rect.start_corner.inline.px,
+                rect.start_corner.block.px,
+                rect.size.inline.px,
+                rect.size.block.px,
+            ]
+        }
+        fragments => panic!("expected an image fragment, got {:?}", fragments),
+    }
+}
+
+#[test]
+fn png_and_jpeg_sizes() {
+    assert_eq!(png_size(&png(200, 100)), Some((200, 100)));
+    assert_eq!(png_size(&png(200, 100)[..20]), None);
+
+    let jpeg = [
+        &[0xFF, 0xD8][..],
+        // APP0 segment, with a two-byte payload
+        &[0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00],
+        // fill byte, then DHT, which is not a start-of-frame segment
+        &[0xFF, 0xFF, 0xC4, 0x00, 0x03, 0x00],
+        // SOF2 segment: precision, height, width
+        &[0xFF, 0xC2, 0x00, 0x0B, 0x08, 0x00, 0x64, 0x00, 0xC8, 0x01, 0x01, 0x11, 0x00],
+    ]
+    .concat();
+    assert_eq!(jpeg_size(&jpeg), Some((200, 100)));
+    assert_eq!(jpeg_size(&jpeg[..14]), None);
+    assert_eq!(jpeg_size(&[0xFF, 0xD8, 0x00]), None);
+}
+
+#[test]
+fn svg_lengths() {
+    assert_eq!(parse_svg_length("10"), Some(Length { px: 10. }));
+    assert_eq!(parse_svg_length(" 2.5px "), Some(Length { px: 2.5 }));
+    assert_eq!(parse_svg_length("1in"), Some(Length { px: 96. }));
+    assert_eq!(parse_svg_length("3pt"), Some(Length { px: 4. }));
+    assert_eq!(parse_svg_length("50%"), None);
+    assert_eq!(parse_svg_length("1em"), None);
+    assert_eq!(parse_svg_length("px"), None);
+}
+
+#[test]
+fn dimension_values() {
+    let dimension = |value| match parse_dimension_value(value) {
+        LengthOrPercentageOrAuto::Length(length) => Some((length.px, "px")),
+        LengthOrPercentageOrAuto::Percentage(percentage) => Some((percentage.unit_value, "%")),
+        LengthOrPercentageOrAuto::Auto => None,
+    };
+    assert_eq!(dimension(None), None);
+    assert_eq!(dimension(Some(" 120")), Some((120., "px")));
+    // trailing garbage is ignored
+    assert_eq!(dimension(Some("12.5px")), Some((12.5, "px")));
+    assert_eq!(dimension(Some("50%")), Some((0.5, "%")));
+    assert_eq!(dimension(Some("auto")), None);
+}
+
+#[test]
+fn width_and_height_attributes() {
+    assert_eq!(used_size(&image(200., 100., [None, None]), ""), [200., 100.]);
+    // one attribute keeps the intrinsic ratio
+    assert_eq!(used_size(&image(200., 100., [Some("50"), None]), ""), [50., 25.]);
+    assert_eq!(used_size(&image(200., 100., [None, Some("50")]), ""), [100., 50.]);
+    assert_eq!(used_size(&image(200., 100., [Some("50"), Some("50")]), ""), [50., 50.]);
+    // percentages refer to the containing block
+    assert_eq!(used_size(&image(200., 100., [Some("50%"), None]), ""), [200., 100.]);
+    assert_eq!(used_size(&image(200., 100., [None, Some("10%")]), ""), [60., 30.]);
+    // CSS sizes win over the attributes
+    assert_eq!(
+        used_size(&image(200., 100., [Some("50"), Some("50")]), "width: 80px"),
+        [80., 50.]
+    );
+    // min and max sizes clamp them before the ratio applies
+    assert_eq!(
+        used_size(&image(200., 100., [Some("50"), None]), "min-width: 60px"),
+        [60., 30.]
+    );
+}
+
+#[test]
+fn object_fit() {
+    let large = image(200., 100., [None, None]);
+    assert_eq!(object_rect(&large, "fill"), [0., 0., 100., 100.]);
+    assert_eq!(object_rect(&large, "contain"), [0., 25., 100., 50.]);
+    assert_eq!(object_rect(&large, "cover"), [-50., 0., 200., 100.]);
+    assert_eq!(object_rect(&large, "none"), [-50., 0., 200., 100.]);
+    assert_eq!(object_rect(&large, "scale-down"), [0., 25., 100., 50.]);
+
+    let small = image(20., 10., [None, None]);
+    assert_eq!(object_rect(&small, "none"), [40., 45., 20., 10.]);
+    assert_eq!(object_rect(&small, "scale-down"), [40., 45., 20., 10.]);
+}
@@ -0,0 +1,384 @@
+//! Intrinsic inline sizes of boxes, used where the inline size of a box
+//! depends on its contents, such as for the columns of a table.
+//! https://drafts.csswg.org/css-sizing/#intrinsic-sizes
+
+use super::*;
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ContentSizes {
+    pub min_content: Length,
+    pub max_content: Length,
+}
+
+impl ContentSizes {
+    pub fn zero() -> Self {
+        Self {
+            min_content: Length::zero(),
+            max_content: Length::zero(),
+        }
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            min_content: self.min_content.max(other.min_content),
+            max_content: self.max_content.max(other.max_content),
+        }
+    }
+
+    pub fn max_assign(&mut self, other: Self) {
+        *self = self.max(other)
+    }
+
+    /// https://drafts.csswg.org/css2/visudet.html#shrink-to-fit-float
+    pub fn shrink_to_fit(&self, available_size: Length) -> Length {
+        available_size
+            .max(self.min_content)
+            .min(self.max_content)
+    }
+}
+
+impl std::ops::Add for ContentSizes {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            min_content: self.min_content + other.min_content,
+            max_content: self.max_content + other.max_content,
+        }
+    }
+}
+
+/// The content sizes of the margin box of a box with the given style,
+/// given a function computing those of its content box.
+///
+/// Percentages are resolved against a zero containing block,
+/// since the size of the containing block is not known yet,
+/// except that percentage sizes and maximum sizes behave as `auto` and `none`.
+/// https://drafts.csswg.org/css-sizing/#intrinsic-contribution
+pub(super) fn outer_inline_content_sizes(
+    style: &ComputedValues,
+    inner_content_sizes: impl FnOnce() -> ContentSizes,
+) -> ContentSizes {
+    let padding = style.padding().percentages_relative_to(Length::zero());
+    let border = style.border_width().percentages_relative_to(Length::zero());
+    let margin = style
+        .margin()
+        .percentages_relative_to(Length::zero())
+        .auto_is(Length::zero);
+    let pb = padding.inline_sum() + border.inline_sum();
+    let content_box = |size: Length| match style.box_.box_sizing {
+        BoxSizing::ContentBox => size,
+        BoxSizing::BorderBox => (size - pb).max(Length::zero()),
+    };
+    let min_size = match style.min_box_size().inline {
+        LengthOrPercentageOrAuto::Length(length) => content_box(length),
+        _ => Length::zero(),
+    };
+    let max_size = match style.max_box_size().inline {
+        LengthOrPercentageOrNone::Length(length) => Some(content_box(length)),
+        _ => None,
+    };
+    let clamp = |size: Length| clamp_between_extremums(size, min_size, max_size);
+    let inner = match style.box_size().inline {
+        LengthOrPercentageOrAuto::Length(length) => ContentSizes {
+            min_content: content_box(length),
+            max_content: content_box(length),
+        },
+        _ => inner_content_sizes(),
+    };
+    let pbm = pb + margin.inline_sum();
+    ContentSizes {
+        min_content: clamp(inner.min_content) + pbm,
+        max_content: clamp(inner.max_content) + pbm,
+    }
+}
+
+/// The used constraints on the content box size of a box: its `width` and `height`,
+/// and their minimums and maximums, with `box-sizing` applied.
+///
+/// Percentages of an indefinite containing block size behave as `auto`,
+/// zero, or `none` respectively.
+/// https://drafts.csswg.org/css-sizing/#box-sizing
+/// https://drafts.csswg.org/css2/visudet.html#min-max-widths
+/// https://drafts.csswg.org/css2/visudet.html#min-max-heights
+pub(super) struct ContentBoxSizes {
+    pub box_size: Vec2<LengthOrAuto>,
+    pub min_box_size: Vec2<Length>,
+    pub max_box_size: Vec2<Option<Length>>,
+}
+
+impl ContentBoxSizes {
+    pub fn new(
+        style: &ComputedValues,
+        containing_block_inline_size: LengthOrAuto,
+        containing_block_block_size: LengthOrAuto,
+        padding_border: &Sides<Length>,
+    ) -> Self {
+        let (inline_pb, block_pb) = match style.box_.box_sizing {
+            BoxSizing::ContentBox => (Length::zero(), Length::zero()),
+            BoxSizing::BorderBox => (padding_border.inline_sum(), padding_border.block_sum()),
+        };
+        let size = |size: LengthOrPercentageOrAuto, containing_size: LengthOrAuto, pb: Length| {
+            match (size, containing_size) {
+                (LengthOrPercentageOrAuto::Percentage(_), LengthOrAuto::Auto) => LengthOrAuto::Auto,
+                (size, containing_size) => size
+                    .percentage_relative_to(containing_size.auto_is(Length::zero))
+                    .map(|size| (size - pb).max(Length::zero())),
+            }
+        };
+        let max_size = |size: LengthOrPercentageOrNone, containing_size: LengthOrAuto, pb: Length| {
+            match (size, containing_size) {
+                (LengthOrPercentageOrNone::Percentage(_), LengthOrAuto::Auto) => None,
+                (size, containing_size) => size
+                    .percentage_relative_to(containing_size.auto_is(Length::zero))
+                    .map(|size| (size - pb).max(Length::zero())),
+            }
+        };
+
+        let cbis = containing_block_inline_size;
+        let cbbs = containing_block_block_size;
+        let box_size = style.box_size();
+        let min_box_size = style.min_box_size();
+        let max_box_size = style.max_box_size();
+        ContentBoxSizes {
+            box_size: Vec2 {
+                inline: size(box_size.inline, cbis, inline_pb),
+                block: size(box_size.block, cbbs, block_pb),
+            },
+            min_box_size: Vec2 {
+                inline: size(min_box_size.inline, cbis, inline_pb).auto_is(Length::zero),
+                block: size(min_box_size.block, cbbs, block_pb).auto_is(Length::zero),
+            },
+            max_box_size: Vec2 {
+                inline: max_size(max_box_size.inline, cbis, inline_pb),
+                block: max_size(max_box_size.block, cbbs, block_pb),
+            },
+        }
+    }
+
+    pub fn clamp_inline(&self, inline_size: Length) -> Length {
+        clamp_between_extremums(inline_size, self.min_box_size.inline, self.max_box_size.inline)
+    }
+
+    pub fn clamp_block(&self, block_size: Length) -> Length {
+        clamp_between_extremums(block_size, self.min_box_size.block, self.max_box_size.block)
+    }
+
+    /// Clamps the size of a replaced element with `auto` width and height,
+    /// preserving its ratio as far as the constraints allow.
+    /// https://drafts.csswg.org/css2/visudet.html#min-max-widths
+    pub fn clamp_preserving_ratio(&self, size: Vec2<Length>) -> Vec2<Length> {
+        let (w, h) = (size.inline, size.block);
+        if w.px <= 0. || h.px <= 0. {
+            return Vec2 {
+                inline: self.clamp_inline(w),
+                block: self.clamp_block(h),
+            };
+        }
+        let (min_w, min_h) = (self.min_box_size.inline, self.min_box_size.block);
+        let infinite = Length { px: f32::INFINITY };
+        let max_w = self.max_box_size.inline.map_or(infinite, |max| max.max(min_w));
+        let max_h = self.max_box_size.block.map_or(infinite, |max| max.max(min_h));
+        let (w_over_h, h_over_w) = (w.px / h.px, h.px / w.px);
+        let (inline, block) = if w > max_w && h > max_h {
+            if max_w.px / w.px <= max_h.px / h.px {
+                (max_w, min_h.max(max_w * h_over_w))
+            } else {
+                (min_w.max(max_h * w_over_h), max_h)
+            }
+        } else if w < min_w && h < min_h {
+            if min_w.px / w.px <= min_h.px / h.px {
+                (max_w.min(min_h * w_over_h), min_h)
+            } else {
+                (min_w, max_h.min(min_w * h_over_w))
+            }
+        } else if w < min_w && h > max_h {
+            (min_w, max_h)
+        } else if w > max_w && h < min_h {
+            (max_w, min_h)
+        } else if w > max_w {
+            (max_w, min_h.max(max_w * h_over_w))
+        } else if w < min_w {
+            (min_w, max_h.min(min_w * h_over_w))
+        } else if h > max_h {
+            (min_w.max(max_h * w_over_h), max_h)
+        } else if h < min_h {
+            (max_w.min(min_h * w_over_h), min_h)
+        } else {
+            (w, h)
+        };
+        Vec2 { inline, block }
+    }
+}
+
+/// The minimum size wins over the maximum size.
+fn clamp_between_extremums(size: Length, min: Length, max: Option<Length>) -> Length {
+    max.map_or(size, |max| size.min(max)).max(min)
+}
+
+impl IndependentFormattingContext {
+    pub(super) fn inline_content_sizes(&self, style: &ComputedValues) -> ContentSizes {
+        match self {
+            IndependentFormattingContext::Flow(bfc) => bfc.contents.inline_content_sizes(),
+            IndependentFormattingContext::Replaced(replaced) => {
+                // Percentage sizes make replaced elements compressible: they resolve
+                // against zero for the min-content size, and behave as `auto` sizes
+                // and `none` maximum sizes for the max-content size.
+                // https://drafts.csswg.org/css-sizing/#min-content-zero
+                let containing_block = ContainingBlock {
+                    inline_size: Length::zero(),
+                    block_size: LengthOrAuto::Auto,
+                    mode: style.writing_mode(),
+                };
+                ContentSizes {
+                    min_content: replaced
+                        .used_size_as_if_inline_element(&containing_block, style)
+                        .inline,
+                    max_content: replaced
+                        .used_size_in_indefinite_containing_block(style)
+                        .inline,
+                }
+            }
+            IndependentFormattingContext::Table(table) => table.inline_content_sizes(),
+            IndependentFormattingContext::Flex(flex) => flex.inline_content_sizes(),
+            IndependentFormattingContext::Grid(grid) => grid.inline_content_sizes(),
+            IndependentFormattingContext::Multicol(multicol) => multicol.inline_content_sizes(),
+        }
+    }
+}
+
+impl BlockContainer {
+    pub(super) fn inline_content_sizes(&self) -> ContentSizes {
+        match self {
+            BlockContainer::BlockLevelBoxes(boxes) => boxes
+                .iter()
+                .map(|box_| match &**box_ {
+                    BlockLevelBox::SameFormattingContextBlock { style, contents } => {
+                        outer_inline_content_sizes(style, || contents.inline_content_sizes())
+                    }
+                    BlockLevelBox::Independent { style, contents }
+                    | BlockLevelBox::OutOfFlowFloatBox(FloatBox { style, contents }) => {
+                        outer_inline_content_sizes(style, || contents.inline_content_sizes(style))
+                    }
+                    BlockLevelBox::OutOfFlowAbsolutelyPositionedBox(_)
+                    | BlockLevelBox::OutsideMarker { .. } => ContentSizes::zero(),
+                })
+                .fold(ContentSizes::zero(), ContentSizes::max),
+            BlockContainer::InlineFormattingContext(ifc) => ifc.inline_content_sizes(),
+        }
+    }
+}
+
+impl InlineFormattingContext {
+    /// The min-content size is that of the widest unbreakable part of the
+    /// content, the max-content size that of the widest line when only
+    /// breaking at forced line breaks. Soft wrap opportunities are the same
+    /// as those used by inline layout.
+    pub(super) fn inline_content_sizes(&self) -> ContentSizes {
+        let mut computation = InlineContentSizesComputation {
+            sizes: ContentSizes::zero(),
+            current_line: Length::zero(),
+            current_unbreakable: Length::zero(),
+        };
+        computation.traverse(&self.inline_level_boxes);
+        computation.break_line();
+        computation.sizes
+    }
+}
+
+struct InlineContentSizesComputation {
+    sizes: ContentSizes,
+    /// Inline size of the current line, without soft wrapping.
+    current_line: Length,
+    /// Inline size of the content since the last soft wrap opportunity.
+    current_unbreakable: Length,
+}
+
+impl InlineContentSizesComputation {
+    fn traverse(&mut self, inline_level_boxes: &[Arc<InlineLevelBox>]) {
+        for box_ in inline_level_boxes {
+            match &**box_ {
+                InlineLevelBox::InlineBox(inline_box) => {
+                    let pbm = inline_pbm(&inline_box.style);
+                    if inline_box.first_fragment {
+                        self.current_line += pbm.inline_start;
+                        self.add_unbreakable(pbm.inline_start);
+                    }
+                    self.traverse(&inline_box.children);
+                    if inline_box.last_fragment {
+                        self.current_line += pbm.inline_end;
+                        self.add_unbreakable(pbm.inline_end);
+                    }
+                }
+                InlineLevelBox::TextRun(run) => self.add_text_run(run),
+                InlineLevelBox::Atomic { style, contents }
+                | InlineLevelBox::OutOfFlowFloatBox(FloatBox { style, contents }) => {
+                    let outer = outer_inline_content_sizes(style, || {
+                        contents.inline_content_sizes(style)
+                    });
+                    self.soft_wrap_opportunity();
+                    self.sizes.min_content.max_assign(outer.min_content);
+                    self.current_line += outer.max_content;
+                }
+                InlineLevelBox::OutOfFlowAbsolutelyPositionedBox(_) => {}
+            }
+        }
+    }
+
+    fn add_text_run(&mut self, run: &TextRun) {
+        let wraps = !matches!(
+            run.parent_style.text_inherited.white_space,
+            WhiteSpace::Nowrap | WhiteSpace::Pre
+        );
+        let measure = |text: &str| -> Length { run.font.string_metrics(text).width };
+        for (i, line) in run.text.split('\n').enumerate() {
+            if i > 0 {
+                self.break_line();
+            }
+            self.current_line += measure(line);
+            if !wraps {
+                self.add_unbreakable(measure(line));
+                continue;
+            }
+            // Inline layout may break at the start of a text run,
+            // before a space, and after a hyphen.
+            self.soft_wrap_opportunity();
+            for word in line.split(' ') {
+                let mut parts = word.split_inclusive('-').peekable();
+                while let Some(part) = parts.next() {
+                    self.add_unbreakable(measure(part));
+                    if parts.peek().is_some() {
+                        self.soft_wrap_opportunity();
+                    }
+                }
+                self.soft_wrap_opportunity();
+            }
+        }
+    }
+
+    fn add_unbreakable(&mut self, inline_size: Length) {
+        self.current_unbreakable += inline_size;
+        self.sizes.min_content.max_assign(self.current_unbreakable);
+    }
+
+    fn soft_wrap_opportunity(&mut self) {
+        self.current_unbreakable = Length::zero();
+    }
+
+    fn break_line(&mut self) {
+        self.sizes.max_content.max_assign(self.current_line);
+        self.current_line = Length::zero();
+        self.soft_wrap_opportunity();
+    }
+}
+
+/// The inline-axis padding, border, and margin of an inline box.
+fn inline_pbm(style: &ComputedValues) -> Sides<Length> {
+    let padding = style.padding().percentages_relative_to(Length::zero());
+    let border = style.border_width().percentages_relative_to(Length::zero());
+    let margin = style
+        .margin()
+        .percentages_relative_to(Length::zero())
+        .auto_is(Length::zero);
+    &(&padding + &border) + &margin
+}
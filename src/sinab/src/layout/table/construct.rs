@@ -0,0 +1,451 @@
+use super::*;
+use crate::dom::NodeId;
+use html5ever::LocalName;
+
+impl Table {
+    pub(in crate::layout) fn construct<'a>(
+        context: &'a Context<'a>,
+        style: &Arc<ComputedValues>,
+        contents: NonReplacedContents,
+    ) -> Self {
+        let mut builder = TableBuilder::new(context, style.clone());
+        contents.traverse(style, context, &mut builder);
+        builder.finish()
+    }
+}
+
+/// A builder for a table box, also used for anonymous tables generated
+/// around misparented table-internal boxes.
+/// https://drafts.csswg.org/css2/tables.html#anonymous-boxes
+pub(in crate::layout) struct TableBuilder<'a> {
+    context: &'a Context<'a>,
+    pub style: Arc<ComputedValues>,
+    captions: Vec<Arc<BlockLevelBox>>,
+    columns: Vec<TableColumn>,
+    row_groups: Vec<IntermediateRowGroup>,
+
+    /// The kind of box whose children are currently being traversed.
+    level: Level,
+
+    /// The row being built, which may be anonymous if it was generated for
+    /// cells found directly in a row group or in the table.
+    ongoing_row: Option<OngoingRow<'a>>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Level {
+    Table,
+    RowGroup,
+    Row,
+    ColumnGroup,
+}
+
+struct IntermediateRowGroup {
+    style: Arc<ComputedValues>,
+    /// `None` for an anonymous row group wrapping rows found directly in the table.
+    kind: Option<DisplayLayoutInternal>,
+    rows: Vec<IntermediateRow>,
+}
+
+struct IntermediateRow {
+    style: Arc<ComputedValues>,
+    cells: Vec<IntermediateCell>,
+}
+
+struct OngoingRow<'a> {
+    style: Arc<ComputedValues>,
+    cells: Vec<IntermediateCell>,
+    /// The anonymous cell wrapping consecutive non-cell children of the row, if any.
+    anonymous_cell: Option<(Arc<ComputedValues>, BlockContainerBuilder<'a>)>,
+}
+
+struct IntermediateCell {
+    style: Arc<ComputedValues>,
+    contents: BlockFormattingContext,
+    colspan: usize,
+    /// Zero spans the rest of the row group.
+    rowspan: usize,
+}
+
+impl<'a> TableBuilder<'a> {
+    pub(in crate::layout) fn new(context: &'a Context<'a>, style: Arc<ComputedValues>) -> Self {
+        Self {
+            context,
+            style,
+            captions: vec![],
+            columns: vec![],
+            row_groups: vec![],
+            level: Level::Table,
+            ongoing_row: None,
+        }
+    }
+
+    pub(in crate::layout) fn finish(mut self) -> Table {
+        self.end_ongoing_row();
+
+        // The first header group is displayed first, and the first footer group last.
+        // https://drafts.csswg.org/css2/tables.html#table-display
+        let position = |groups: &[IntermediateRowGroup], kind| {
+            groups
+                .iter()
+                .position(|group| group.kind == Some(kind))
+        };
+        if let Some(index) = position(&self.row_groups, DisplayLayoutInternal::TableHeaderGroup) {
+            let header = self.row_groups.remove(index);
+            self.row_groups.insert(0, header);
+        }
+        if let Some(index) = position(&self.row_groups, DisplayLayoutInternal::TableFooterGroup) {
+            let footer = self.row_groups.remove(index);
+            self.row_groups.push(footer);
+        }
+
+        let mut row_groups = Vec::with_capacity(self.row_groups.len());
+        let mut rows = Vec::new();
+        let mut column_count = self.columns.len();
+        for group in self.row_groups {
+            let group_start = rows.len();
+            let group_end = group_start + group.rows.len();
+            // How many more rows each column is occupied by a cell from a previous row.
+            let mut occupied: Vec<usize> = Vec::new();
+            for (row_index, row) in (group_start..).zip(group.rows) {
+                let mut column = 0;
+                let cells = row
+                    .cells
+                    .into_iter()
+                    .map(|cell| {
+                        while occupied.get(column).is_some_and(|&rows| rows > 0) {
+                            column += 1
+                        }
+                        let rowspan = match cell.rowspan {
+                            0 => group_end - row_index,
+                            rowspan => rowspan.min(group_end - row_index),
+                        };
+                        let end = column + cell.colspan;
+                        if occupied.len() < end {
+                            occupied.resize(end, 0)
+                        }
+                        for rows in &mut occupied[column..end] {
+                            *rows = rowspan
+                        }
+                        let cell = TableCell {
+                            style: cell.style,
+                            contents: cell.contents,
+                            column,
+                            colspan: cell.colspan,
+                            rowspan,
+                        };
+                        column = end;
+                        cell
+                    })
+                    .collect();
+                column_count = column_count.max(occupied.len());
+                for rows in &mut occupied {
+                    *rows = rows.saturating_sub(1)
+                }
+                rows.push(TableRow {
+                    style: row.style,
+                    cells,
+                });
+            }
+            row_groups.push(TableRowGroup {
+                style: group.style,
+                rows: group_start..rows.len(),
+            });
+        }
+
+        let mut columns = self.columns;
+        columns.resize_with(column_count, || TableColumn { style: None });
+        let mut table = Table {
+            style: self.style,
+            captions: self.captions,
+            columns,
+            row_groups,
+            rows,
+        };
+        if table.style.table_inherited.border_collapse == BorderCollapse::Collapse {
+            table.collapse_borders()
+        }
+        table
+    }
+
+    fn handle_row_group(
+        &mut self,
+        style: &Arc<ComputedValues>,
+        kind: DisplayLayoutInternal,
+        contents: NonReplacedContents,
+    ) {
+        self.end_ongoing_row();
+        self.row_groups.push(IntermediateRowGroup {
+            style: style.clone(),
+            kind: Some(kind),
+            rows: vec![],
+        });
+        self.level = Level::RowGroup;
+        contents.traverse(style, self.context, self);
+        self.end_ongoing_row();
+        self.level = Level::Table;
+    }
+
+    fn handle_row(&mut self, style: &Arc<ComputedValues>, contents: NonReplacedContents) {
+        self.end_ongoing_row();
+        self.ongoing_row = Some(OngoingRow {
+            style: style.clone(),
+            cells: vec![],
+            anonymous_cell: None,
+        });
+        let parent_level = std::mem::replace(&mut self.level, Level::Row);
+        contents.traverse(style, self.context, self);
+        self.level = parent_level;
+        self.end_ongoing_row();
+    }
+
+    fn handle_cell(
+        &mut self,
+        style: &Arc<ComputedValues>,
+        contents: NonReplacedContents,
+        (colspan, rowspan): (usize, usize),
+    ) {
+        let contents = BlockFormattingContext::construct(self.context, style, contents);
+        let row = self.ongoing_row();
+        row.end_anonymous_cell();
+        row.cells.push(IntermediateCell {
+            style: style.clone(),
+            contents,
+            colspan,
+            rowspan,
+        })
+    }
+
+    fn handle_columns(&mut self, style: &Arc<ComputedValues>, span: usize) {
+        self.columns.extend((0..span).map(|_| TableColumn {
+            style: Some(style.clone()),
+        }))
+    }
+
+    /// The row that cells are added to, generating an anonymous row
+    /// for cells found directly in a row group or in the table.
+    fn ongoing_row(&mut self) -> &mut OngoingRow<'a> {
+        let parent_style = match self.level {
+            Level::RowGroup => &self.row_groups.last().unwrap().style,
+            _ => &self.style,
+        };
+        self.ongoing_row.get_or_insert_with(|| OngoingRow {
+            style: ComputedValues::anonymous_inheriting_from(Some(parent_style)),
+            cells: vec![],
+            anonymous_cell: None,
+        })
+    }
+
+    /// The builder for the anonymous cell wrapping content that is not a table-internal box.
+    fn anonymous_cell(&mut self) -> &mut BlockContainerBuilder<'a> {
+        let context = self.context;
+        let row = self.ongoing_row();
+        let row_style = &row.style;
+        let (_, builder) = row.anonymous_cell.get_or_insert_with(|| {
+            let style = ComputedValues::anonymous_inheriting_from(Some(row_style));
+            (style.clone(), BlockContainerBuilder::new(context, style))
+        });
+        builder
+    }
+
+    fn end_ongoing_row(&mut self) {
+        let mut row = match self.ongoing_row.take() {
+            Some(row) => row,
+            None => return,
+        };
+        row.end_anonymous_cell();
+        let row = IntermediateRow {
+            style: row.style,
+            cells: row.cells,
+        };
+        // Rows found directly in the table are wrapped in an anonymous row group.
+        let group = match self.row_groups.last_mut() {
+            Some(group) if self.level != Level::Table || group.kind.is_none() => group,
+            _ => {
+                self.row_groups.push(IntermediateRowGroup {
+                    style: ComputedValues::anonymous_inheriting_from(Some(&self.style)),
+                    kind: None,
+                    rows: vec![],
+                });
+                self.row_groups.last_mut().unwrap()
+            }
+        };
+        group.rows.push(row)
+    }
+}
+
+impl OngoingRow<'_> {
+    fn end_anonymous_cell(&mut self) {
+        if let Some((style, builder)) = self.anonymous_cell.take() {
+            let (contents, contains_floats) = builder.finish();
+            self.cells.push(IntermediateCell {
+                style,
+                contents: BlockFormattingContext {
+                    contents,
+                    contains_floats: contains_floats == ContainsFloats::Yes,
+                },
+                colspan: 1,
+                rowspan: 1,
+            })
+        }
+    }
+}
+
+impl<'a> TraversalHandler<'a> for TableBuilder<'a> {
+    fn handle_text(&mut self, text: &str, parent_style: &Arc<ComputedValues>) {
+        let in_anonymous_cell = self
+            .ongoing_row
+            .as_ref()
+            .is_some_and(|row| row.anonymous_cell.is_some());
+        let whitespace_only = text.bytes().all(|b| b.is_ascii_whitespace());
+        match self.level {
+            Level::ColumnGroup => {}
+            _ if whitespace_only && !in_anonymous_cell => {}
+            _ => self.anonymous_cell().handle_text(text, parent_style),
+        }
+    }
+
+    fn handle_element(
+        &mut self,
+        style: &Arc<ComputedValues>,
+        display: DisplayGeneratingBox,
+        contents: Contents,
+        box_slot: BoxSlot<'a>,
+    ) {
+        use DisplayLayoutInternal::*;
+        let internal = match display {
+            DisplayGeneratingBox::LayoutInternal(internal) => internal,
+            DisplayGeneratingBox::OutsideInside { .. } => {
+                return match self.level {
+                    // Children of column groups other than columns are not rendered.
+                    Level::ColumnGroup => ignore(self.context, contents, box_slot),
+                    _ => self
+                        .anonymous_cell()
+                        .handle_element(style, display, contents, box_slot),
+                };
+            }
+        };
+        let element = match &contents {
+            Contents::OfElement(element) => Some(*element),
+            _ => None,
+        };
+        let contents: NonReplacedContents = match contents.try_into() {
+            Ok(contents) => contents,
+            // Replaced elements are not table-internal boxes
+            // (see `traverse_element`), so this is not expected.
+            Err(replaced) => {
+                let display_inline = DisplayGeneratingBox::OutsideInside {
+                    outside: DisplayOutside::Inline,
+                    inside: DisplayInside::Flow,
+                    list_item: false,
+                };
+                return self.anonymous_cell().handle_element(
+                    style,
+                    display_inline,
+                    Contents::Replaced(replaced),
+                    box_slot,
+                );
+            }
+        };
+        match (self.level, internal) {
+            (Level::ColumnGroup, TableColumn) => {
+                self.handle_columns(style, span(self.context, element));
+                box_slot.set(LayoutBox::TablePart)
+            }
+            (Level::ColumnGroup, _) => ignore(self.context, contents.into(), box_slot),
+            (Level::Table, TableColumn) => {
+                self.handle_columns(style, span(self.context, element));
+                box_slot.set(LayoutBox::TablePart)
+            }
+            (Level::Table, TableColumnGroup) => {
+                let columns_before = self.columns.len();
+                self.level = Level::ColumnGroup;
+                contents.traverse(style, self.context, self);
+                self.level = Level::Table;
+                // A column group without columns spans its own columns.
+                if self.columns.len() == columns_before {
+                    self.handle_columns(style, span(self.context, element))
+                }
+                box_slot.set(LayoutBox::TablePart)
+            }
+            (Level::Table, TableCaption) => {
+                #[allow(clippy::arc_with_non_send_sync)]
+                let caption = Arc::new(BlockLevelBox::Independent {
+                    style: style.clone(),
+                    contents: IndependentFormattingContext::Flow(
+                        BlockFormattingContext::construct(self.context, style, contents),
+                    ),
+                });
+                self.captions.push(caption.clone());
+                box_slot.set(LayoutBox::BlockLevel(caption))
+            }
+            (Level::Table, TableRowGroup)
+            | (Level::Table, TableHeaderGroup)
+            | (Level::Table, TableFooterGroup) => {
+                self.handle_row_group(style, internal, contents);
+                box_slot.set(LayoutBox::TablePart)
+            }
+            (Level::Table, TableRow) | (Level::RowGroup, TableRow) => {
+                self.handle_row(style, contents);
+                box_slot.set(LayoutBox::TablePart)
+            }
+            (_, TableCell) => {
+                self.handle_cell(style, contents, cell_spans(self.context, element));
+                box_slot.set(LayoutBox::TablePart)
+            }
+            // Misparented table-internal boxes end up in an anonymous table,
+            // in an anonymous cell.
+            _ => self.anonymous_cell().handle_element(
+                style,
+                display,
+                contents.into(),
+                box_slot,
+            ),
+        }
+    }
+}
+
+/// Drops the boxes of content that is not rendered.
+fn ignore(context: &Context, contents: Contents, box_slot: BoxSlot) {
+    if let Contents::OfElement(element) = contents {
+        context.unset_boxes_in_subtree(element)
+    }
+    box_slot.unset()
+}
+
+fn integer_attribute(context: &Context, element: NodeId, name: &LocalName) -> Option<usize> {
+    context.document[element]
+        .as_element()?
+        .get_attr(name)?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// The number of columns of a `<col>` or `<colgroup>` element.
+/// https://html.spec.whatwg.org/multipage/tables.html#forming-a-table
+fn span(context: &Context, element: Option<NodeId>) -> usize {
+    element
+        .and_then(|element| integer_attribute(context, element, &local_name!("span")))
+        .map_or(1, |span| span.clamp(1, 1000))
+}
+
+/// The `colspan` and `rowspan` of a `<td>` or `<th>` element.
+/// https://html.spec.whatwg.org/multipage/tables.html#forming-a-table
+fn cell_spans(context: &Context, element: Option<NodeId>) -> (usize, usize) {
+    let element = match element {
+        Some(element) => element,
+        None => return (1, 1),
+    };
+    let is_cell = context.document[element].as_element().is_some_and(|data| {
+        matches!(data.name.local, local_name!("td") | local_name!("th"))
+    });
+    if !is_cell {
+        return (1, 1);
+    }
+    let colspan = integer_attribute(context, element, &local_name!("colspan"))
+        .map_or(1, |colspan| colspan.clamp(1, 1000));
+    let rowspan = integer_attribute(context, element, &local_name!("rowspan"))
+        .map_or(1, |rowspan| rowspan.min(65534));
+    (colspan, rowspan)
+}
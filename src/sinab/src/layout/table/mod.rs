@@ -0,0 +1,931 @@
+//! Table layout.
+//! https://drafts.csswg.org/css2/tables.html
+
+use super::*;
+use crate::geom::physical;
+use cssparser::Color;
+
+mod construct;
+
+pub(super) use construct::*;
+
+/// A table box, with its captions. Rows and cells are in layout order,
+/// after moving the header and footer groups to the start and end of the table.
+/// https://drafts.csswg.org/css2/tables.html#table-display
+#[derive(Debug)]
+pub(super) struct Table {
+    pub style: Arc<ComputedValues>,
+    pub captions: Vec<Arc<BlockLevelBox>>,
+    /// One entry per column of the grid, including columns
+    /// that are only generated by cells.
+    pub columns: Vec<TableColumn>,
+    pub row_groups: Vec<TableRowGroup>,
+    pub rows: Vec<TableRow>,
+}
+
+#[derive(Debug)]
+pub(super) struct TableColumn {
+    /// The style of the `table-column` box, or of the `table-column-group`
+    /// box for a group without columns, if any.
+    pub style: Option<Arc<ComputedValues>>,
+}
+
+#[derive(Debug)]
+pub(super) struct TableRowGroup {
+    pub style: Arc<ComputedValues>,
+    pub rows: std::ops::Range<usize>,
+}
+
+#[derive(Debug)]
+pub(super) struct TableRow {
+    pub style: Arc<ComputedValues>,
+    pub cells: Vec<TableCell>,
+}
+
+#[derive(Debug)]
+pub(super) struct TableCell {
+    pub style: Arc<ComputedValues>,
+    pub contents: BlockFormattingContext,
+    /// The first column spanned by the cell.
+    pub column: usize,
+    pub colspan: usize,
+    /// Never extends beyond the row group of the cell.
+    pub rowspan: usize,
+}
+
+/// Intrinsic sizes of a column, for the automatic table layout.
+#[derive(Clone, Copy, Debug)]
+struct ColumnSizes {
+    sizes: ContentSizes,
+    /// Whether the column or one of its cells has a specified width.
+    has_specified_width: bool,
+}
+
+impl Table {
+    /// The content sizes of the content box of the table, including border spacing.
+    pub(super) fn inline_content_sizes(&self) -> ContentSizes {
+        let spacing = self.border_spacing();
+        let grid_spacing = spacing_sum(spacing.inline, self.columns.len());
+        let grid = self
+            .column_sizes()
+            .iter()
+            .fold(ContentSizes::zero(), |sum, column| sum + column.sizes);
+        self.captions
+            .iter()
+            .map(|caption| match &**caption {
+                BlockLevelBox::Independent { style, contents } => {
+                    outer_inline_content_sizes(style, || contents.inline_content_sizes(style))
+                }
+                _ => ContentSizes::zero(),
+            })
+            .fold(
+                ContentSizes {
+                    min_content: grid.min_content + grid_spacing,
+                    max_content: grid.max_content + grid_spacing,
+                },
+                ContentSizes::max,
+            )
+    }
+
+    /// https://drafts.csswg.org/css2/tables.html#separated-borders
+    fn border_spacing(&self) -> Vec2<Length> {
+        match self.style.table_inherited.border_collapse {
+            BorderCollapse::Separate => {
+                let spacing = &self.style.table_inherited.border_spacing;
+                physical::Vec2 {
+                    x: spacing.horizontal,
+                    y: spacing.vertical,
+                }
+                .size_to_flow_relative(self.style.writing_mode())
+            }
+            BorderCollapse::Collapse => Vec2::zero(),
+        }
+    }
+
+    /// https://drafts.csswg.org/css2/tables.html#auto-table-layout
+    fn column_sizes(&self) -> Vec<ColumnSizes> {
+        let mut columns: Vec<ColumnSizes> = self
+            .columns
+            .iter()
+            .map(|column| {
+                let width = column.style.as_ref().and_then(|style| {
+                    match style.box_size().inline {
+                        LengthOrPercentageOrAuto::Length(width) => Some(width),
+                        // FIXME: percentage widths of columns
+                        _ => None,
+                    }
+                });
+                ColumnSizes {
+                    sizes: ContentSizes {
+                        min_content: width.unwrap_or_else(Length::zero),
+                        max_content: width.unwrap_or_else(Length::zero),
+                    },
+                    has_specified_width: width.is_some(),
+                }
+            })
+            .collect();
+
+        let mut spanning_cells = Vec::new();
+        for cell in self.rows.iter().flat_map(|row| &row.cells) {
+            if cell.colspan == 1 {
+                let column = &mut columns[cell.column];
+                column.sizes.max_assign(cell.outer_inline_content_sizes());
+                column.has_specified_width |= cell.has_specified_width();
+            } else {
+                spanning_cells.push(cell)
+            }
+        }
+        for column in &mut columns {
+            if column.has_specified_width {
+                // The max-content size of a column with a specified width is that width.
+                column.sizes.max_content = column.sizes.min_content
+            }
+        }
+
+        // Cells spanning several columns widen them as needed, narrower spans first.
+        let spacing = self.border_spacing().inline;
+        spanning_cells.sort_by_key(|cell| cell.colspan);
+        for cell in spanning_cells {
+            let sizes = cell.outer_inline_content_sizes();
+            let spanned = &mut columns[cell.column..cell.column + cell.colspan];
+            let spanned_spacing = spacing * (cell.colspan - 1) as f32;
+            let max_contents: Vec<Length> = spanned.iter().map(|c| c.sizes.max_content).collect();
+            let mut min_contents: Vec<Length> =
+                spanned.iter().map(|c| c.sizes.min_content).collect();
+            let mut new_max_contents = max_contents.clone();
+            distribute(
+                &mut min_contents,
+                &max_contents,
+                sizes.min_content - spanned_spacing,
+            );
+            distribute(
+                &mut new_max_contents,
+                &max_contents,
+                sizes.max_content - spanned_spacing,
+            );
+            for ((column, min_content), max_content) in
+                spanned.iter_mut().zip(min_contents).zip(new_max_contents)
+            {
+                column.sizes.min_content = min_content;
+                column.sizes.max_content = max_content.max(min_content);
+            }
+        }
+        columns
+    }
+
+    /// The widths of the columns for the given width of the table content box,
+    /// excluding border spacing.
+    /// https://drafts.csswg.org/css2/tables.html#auto-table-layout
+    fn auto_column_widths(&self, available: Length) -> Vec<Length> {
+        let columns = self.column_sizes();
+        let sum = columns
+            .iter()
+            .fold(ContentSizes::zero(), |sum, column| sum + column.sizes);
+        let min_contents = columns.iter().map(|column| column.sizes.min_content);
+        let max_contents = columns.iter().map(|column| column.sizes.max_content);
+        if available <= sum.min_content {
+            return min_contents.collect();
+        }
+        if available < sum.max_content {
+            // Grow all columns from their min-content to their max-content width,
+            // in proportion to the difference between the two.
+            let ratio = (available - sum.min_content).px
+                / (sum.max_content - sum.min_content).px;
+            return min_contents
+                .zip(max_contents)
+                .map(|(min, max)| min + (max - min) * ratio)
+                .collect();
+        }
+        // Wider than needed: the extra space goes to the columns
+        // without a specified width, if there are any.
+        let mut widths: Vec<Length> = max_contents.collect();
+        let weights: Vec<Length> = if columns.iter().all(|column| column.has_specified_width) {
+            widths.clone()
+        } else {
+            columns
+                .iter()
+                .map(|column| {
+                    if column.has_specified_width {
+                        Length::zero()
+                    } else {
+                        // Empty columns without a specified width still get a share.
+                        column.sizes.max_content.max(Length { px: f32::MIN_POSITIVE })
+                    }
+                })
+                .collect()
+        };
+        distribute(&mut widths, &weights, available);
+        widths
+    }
+
+    /// Column widths are given by columns and the cells of the first row,
+    /// independently of the contents of the table.
+    /// https://drafts.csswg.org/css2/tables.html#fixed-table-layout
+    fn fixed_column_widths(&self, available: Length) -> Vec<Length> {
+        let mut widths: Vec<Option<Length>> = self
+            .columns
+            .iter()
+            .map(|column| match column.style.as_ref()?.box_size().inline {
+                LengthOrPercentageOrAuto::Length(width) => Some(width),
+                LengthOrPercentageOrAuto::Percentage(p) => Some(available * p),
+                LengthOrPercentageOrAuto::Auto => None,
+            })
+            .collect();
+        let spacing = self.border_spacing().inline;
+        for cell in self.rows.first().iter().flat_map(|row| &row.cells) {
+            let spanned = &mut widths[cell.column..cell.column + cell.colspan];
+            if spanned.iter().any(Option::is_some) {
+                continue;
+            }
+            let pb = cell.inline_padding_border();
+            let width = match cell.style.box_size().inline {
+                LengthOrPercentageOrAuto::Length(width) => width + pb,
+                LengthOrPercentageOrAuto::Percentage(p) => available * p + pb,
+                LengthOrPercentageOrAuto::Auto => continue,
+            };
+            let spanned_spacing = spacing * (cell.colspan - 1) as f32;
+            let width = (width - spanned_spacing) / cell.colspan as f32;
+            for column in spanned {
+                *column = Some(width.max(Length::zero()))
+            }
+        }
+
+        let specified = widths
+            .iter()
+            .flatten()
+            .fold(Length::zero(), |sum, &width| sum + width);
+        let auto_columns = widths.iter().filter(|width| width.is_none()).count();
+        let remaining = (available - specified).max(Length::zero());
+        if auto_columns > 0 {
+            let auto_width = remaining / auto_columns as f32;
+            return widths
+                .into_iter()
+                .map(|width| width.unwrap_or(auto_width))
+                .collect();
+        }
+        let mut widths: Vec<Length> = widths.into_iter().flatten().collect();
+        let weights = widths.clone();
+        distribute(&mut widths, &weights, available);
+        widths
+    }
+
+    pub(super) fn layout<'a>(
+        &'a self,
+        containing_block: &ContainingBlock,
+        tree_rank: usize,
+        absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    ) -> FlowChildren {
+        let abspos_so_far = absolutely_positioned_fragments.len();
+        let spacing = self.border_spacing();
+        let column_count = self.columns.len();
+        let available = containing_block.inline_size - spacing_sum(spacing.inline, column_count);
+        let widths = match (
+            self.style.table_reset.table_layout,
+            self.style.box_size().inline,
+        ) {
+            (TableLayout::Fixed, LengthOrPercentageOrAuto::Length(_))
+            | (TableLayout::Fixed, LengthOrPercentageOrAuto::Percentage(_)) => {
+                self.fixed_column_widths(available)
+            }
+            _ => self.auto_column_widths(available),
+        };
+        let mut column_starts = Vec::with_capacity(column_count);
+        let mut position = spacing.inline;
+        for width in &widths {
+            column_starts.push(position);
+            position += *width + spacing.inline;
+        }
+        let columns_inline_range = |start: usize, span: usize| {
+            let end = start + span - 1;
+            (
+                column_starts[start],
+                column_starts[end] + widths[end] - column_starts[start],
+            )
+        };
+
+        // Fragments are, in order: captions above the table, row groups,
+        // rows, cells, and captions below the table.
+        let (top_captions, bottom_captions): (Vec<_>, Vec<_>) =
+            self.captions.iter().partition(|caption| match &***caption {
+                BlockLevelBox::Independent { style, .. } => {
+                    style.table_inherited.caption_side == CaptionSide::Top
+                }
+                _ => true,
+            });
+        let cell_count: usize = self.rows.iter().map(|row| row.cells.len()).sum();
+        let first_cell_rank = top_captions.len() + self.row_groups.len() + self.rows.len();
+        let mut fragments = Vec::with_capacity(first_cell_rank + cell_count + bottom_captions.len());
+        let mut block_position = Length::zero();
+        for caption in top_captions {
+            let rank = fragments.len();
+            fragments.push(layout_caption(
+                caption,
+                containing_block,
+                rank,
+                absolutely_positioned_fragments,
+                &mut block_position,
+            ));
+        }
+
+        // Lay out cells at their column widths to find the height of rows.
+        let mut cell_fragments = Vec::with_capacity(cell_count);
+        let mut row_heights: Vec<Length> = self
+            .rows
+            .iter()
+            .map(|row| match row.style.box_size().block {
+                LengthOrPercentageOrAuto::Length(height) => height,
+                _ => Length::zero(),
+            })
+            .collect();
+        let mut spanning_cells = Vec::new();
+        for (row_index, row) in self.rows.iter().enumerate() {
+            for cell in &row.cells {
+                let rank = first_cell_rank + cell_fragments.len();
+                let (inline_start, inline_size) = columns_inline_range(cell.column, cell.colspan);
+                let fragment = cell.layout(
+                    containing_block,
+                    inline_start,
+                    inline_size,
+                    rank,
+                    absolutely_positioned_fragments,
+                );
+                let height = fragment.border_rect().size.block;
+                if cell.rowspan == 1 {
+                    row_heights[row_index].max_assign(height)
+                } else {
+                    spanning_cells.push((row_index, cell.rowspan, height))
+                }
+                cell_fragments.push((row_index, cell, fragment));
+            }
+        }
+        spanning_cells.sort_by_key(|&(_, rowspan, _)| rowspan);
+        for (row_index, rowspan, height) in spanning_cells {
+            let spanned = &mut row_heights[row_index..row_index + rowspan];
+            let weights = spanned.to_vec();
+            let spanned_spacing = spacing.block * (rowspan - 1) as f32;
+            distribute(spanned, &weights, height - spanned_spacing);
+        }
+
+        // Collapsed rows are removed, along with their spacing.
+        // https://drafts.csswg.org/css2/tables.html#dynamic-effects
+        let grid_block_start = block_position;
+        let mut row_starts = Vec::with_capacity(self.rows.len());
+        let mut position = grid_block_start + spacing.block;
+        for (row, height) in self.rows.iter().zip(&mut row_heights) {
+            row_starts.push(position);
+            if row.style.inherited_box.visibility == Visibility::Collapse {
+                *height = Length::zero();
+                continue;
+            }
+            position += *height + spacing.block;
+        }
+        block_position = if self.rows.is_empty() {
+            grid_block_start
+        } else {
+            position
+        };
+        let rows_block_range = |start: usize, span: usize| {
+            let end = start + span - 1;
+            (
+                row_starts[start],
+                row_starts[end] + row_heights[end] - row_starts[start],
+            )
+        };
+
+        // Row groups and rows only paint their background, behind the cells.
+        let (grid_inline_start, grid_inline_size) = if column_count == 0 {
+            (Length::zero(), Length::zero())
+        } else {
+            columns_inline_range(0, column_count)
+        };
+        for group in &self.row_groups {
+            let (block_start, block_size) = if group.rows.is_empty() {
+                (block_position, Length::zero())
+            } else {
+                rows_block_range(group.rows.start, group.rows.len())
+            };
+            fragments.push(background_fragment(
+                &group.style,
+                grid_inline_start,
+                grid_inline_size,
+                block_start,
+                block_size,
+            ));
+        }
+        for (row_index, row) in self.rows.iter().enumerate() {
+            fragments.push(background_fragment(
+                &row.style,
+                grid_inline_start,
+                grid_inline_size,
+                row_starts[row_index],
+                row_heights[row_index],
+            ));
+        }
+        for (row_index, cell, mut fragment) in cell_fragments {
+            let (block_start, block_size) = rows_block_range(row_index, cell.rowspan);
+            cell.align(&mut fragment, block_start, block_size);
+            fragments.push(Fragment::Box(fragment));
+        }
+
+        for caption in bottom_captions {
+            let rank = fragments.len();
+            fragments.push(layout_caption(
+                caption,
+                containing_block,
+                rank,
+                absolutely_positioned_fragments,
+                &mut block_position,
+            ));
+        }
+
+        adjust_static_positions(
+            &mut absolutely_positioned_fragments[abspos_so_far..],
+            &mut fragments,
+            tree_rank,
+        );
+
+        FlowChildren {
+            fragments,
+            block_size: block_position,
+            collapsible_margins_in_children: CollapsedBlockMargins::zero(),
+        }
+    }
+}
+
+impl TableCell {
+    fn inline_padding_border(&self) -> Length {
+        let padding = self.style.padding().percentages_relative_to(Length::zero());
+        let border = self.style.border_width().percentages_relative_to(Length::zero());
+        padding.inline_sum() + border.inline_sum()
+    }
+
+    fn has_specified_width(&self) -> bool {
+        matches!(
+            self.style.box_size().inline,
+            LengthOrPercentageOrAuto::Length(_)
+        )
+    }
+
+    /// A specified width does not make a cell narrower than its min-content size.
+    /// Margins do not apply to table cells.
+    fn outer_inline_content_sizes(&self) -> ContentSizes {
+        let content = self.contents.contents.inline_content_sizes();
+        let sizes = match self.style.box_size().inline {
+            LengthOrPercentageOrAuto::Length(width) => {
+                let width = width.max(content.min_content);
+                ContentSizes {
+                    min_content: width,
+                    max_content: width,
+                }
+            }
+            // FIXME: percentage widths of cells
+            _ => content,
+        };
+        let pb = self.inline_padding_border();
+        ContentSizes {
+            min_content: sizes.min_content + pb,
+            max_content: sizes.max_content + pb,
+        }
+    }
+
+    /// Lays out the contents of the cell, given the inline position and size of its
+    /// border box. The block position of the cell is set later by `align`.
+    fn layout<'a>(
+        &'a self,
+        containing_block: &ContainingBlock,
+        inline_start: Length,
+        inline_size: Length,
+        tree_rank: usize,
+        absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    ) -> BoxFragment {
+        let cbis = containing_block.inline_size;
+        let padding = self.style.padding().percentages_relative_to(cbis);
+        let border = self.style.border_width().percentages_relative_to(cbis);
+        let pb = &padding + &border;
+        let block_size = match self.style.box_size().block {
+            LengthOrPercentageOrAuto::Length(l) => LengthOrAuto::Length(l),
+            // FIXME: percentage heights of cells
+            _ => LengthOrAuto::Auto,
+        };
+        let containing_block_for_children = ContainingBlock {
+            inline_size: (inline_size - pb.inline_sum()).max(Length::zero()),
+            block_size,
+            mode: self.style.writing_mode(),
+        };
+        // https://drafts.csswg.org/css-writing-modes/#orthogonal-flows
+        assert_eq!(
+            containing_block.mode, containing_block_for_children.mode,
+            "Mixed writing modes are not supported yet"
+        );
+        let mut nested_abspos = vec![];
+        let mut flow_children = self.contents.layout(
+            &containing_block_for_children,
+            tree_rank,
+            if self.style.box_.position.is_relatively_positioned() {
+                &mut nested_abspos
+            } else {
+                absolutely_positioned_fragments
+            },
+        );
+        let content_rect = Rect {
+            start_corner: Vec2 {
+                inline: inline_start + pb.inline_start,
+                block: pb.block_start,
+            },
+            size: Vec2 {
+                inline: containing_block_for_children.inline_size,
+                block: flow_children.block_size.max(block_size.auto_is(Length::zero)),
+            },
+        };
+        if self.style.box_.position.is_relatively_positioned() {
+            AbsolutelyPositionedFragment::in_positioned_containing_block(
+                &nested_abspos,
+                &mut flow_children.fragments,
+                &content_rect.size,
+                &padding,
+                containing_block_for_children.mode,
+            )
+        }
+        BoxFragment {
+            style: self.style.clone(),
+            children: flow_children.fragments,
+            content_rect,
+            padding,
+            border,
+            margin: Sides::zero(),
+            block_margins_collapsed_with_children: CollapsedBlockMargins::zero(),
+        }
+    }
+
+    /// Stretches the border box of the cell to the rows it spans, placing its contents
+    /// according to `vertical-align`. The extra space is added to the padding.
+    /// https://drafts.csswg.org/css2/tables.html#height-layout
+    fn align(&self, fragment: &mut BoxFragment, block_start: Length, block_size: Length) {
+        let border_box_size = fragment.border_rect().size.block;
+        let extra = (block_size - border_box_size).max(Length::zero());
+        let before = match self.style.line_reset.vertical_align {
+            VerticalAlign::Middle => extra / 2.,
+            VerticalAlign::Bottom => extra,
+            // FIXME: align the first line of cells with `vertical-align: baseline`
+            _ => Length::zero(),
+        };
+        fragment.padding.block_start += before;
+        fragment.padding.block_end += extra - before;
+        fragment.content_rect.start_corner.block += block_start + before;
+    }
+}
+
+/// Lays out a caption as a block-level box, below the given block position,
+/// and moves that position past the margin box of the caption.
+// FIXME: captions belong in the table wrapper box, outside of the border of the table.
+fn layout_caption<'a>(
+    caption: &'a BlockLevelBox,
+    containing_block: &ContainingBlock,
+    tree_rank: usize,
+    absolutely_positioned_fragments: &mut Vec<AbsolutelyPositionedFragment<'a>>,
+    block_position: &mut Length,
+) -> Fragment {
+    let mut fragment = caption.layout(
+        containing_block,
+        tree_rank,
+        absolutely_positioned_fragments,
+        /* float_context = */ None,
+    );
+    if let Fragment::Box(b) = &mut fragment {
+        b.content_rect.start_corner.block += *block_position + b.margin.block_start;
+        *block_position += b.margin_rect().size.block;
+    }
+    fragment
+}
+
+/// A fragment for a row or row group, which paints its background behind the cells.
+fn background_fragment(
+    style: &Arc<ComputedValues>,
+    inline_start: Length,
+    inline_size: Length,
+    block_start: Length,
+    block_size: Length,
+) -> Fragment {
+    Fragment::Box(BoxFragment {
+        style: style.clone(),
+        children: vec![],
+        content_rect: Rect {
+            start_corner: Vec2 {
+                inline: inline_start,
+                block: block_start,
+            },
+            size: Vec2 {
+                inline: inline_size,
+                block: block_size,
+            },
+        },
+        padding: Sides::zero(),
+        border: Sides::zero(),
+        margin: Sides::zero(),
+        block_margins_collapsed_with_children: CollapsedBlockMargins::zero(),
+    })
+}
+
+/// The border spacing around and between the given number of columns or rows.
+fn spacing_sum(spacing: Length, count: usize) -> Length {
+    if count == 0 {
+        Length::zero()
+    } else {
+        spacing * (count + 1) as f32
+    }
+}
+
+/// Grows `sizes` so that their sum is at least `total`, in proportion to `weights`,
+/// or equally if all weights are zero.
+fn distribute(sizes: &mut [Length], weights: &[Length], total: Length) {
+    let sum = sizes.iter().fold(Length::zero(), |sum, &size| sum + size);
+    let extra = total - sum;
+    if extra <= Length::zero() || sizes.is_empty() {
+        return;
+    }
+    let weight_sum = weights.iter().fold(Length::zero(), |sum, &weight| sum + weight);
+    for (size, weight) in sizes.iter_mut().zip(weights) {
+        *size += if weight_sum > Length::zero() {
+            extra * (weight.px / weight_sum.px)
+        } else {
+            extra / weights.len() as f32
+        }
+    }
+}
+
+/// A border taking part in border conflict resolution.
+/// https://drafts.csswg.org/css2/tables.html#border-conflict-resolution
+#[derive(Clone, Copy, Debug)]
+struct CollapsedBorder {
+    width: Length,
+    style: LineStyle,
+    color: Color,
+}
+
+impl CollapsedBorder {
+    const NONE: Self = CollapsedBorder {
+        width: Length { px: 0. },
+        style: LineStyle::None,
+        color: Color::CurrentColor,
+    };
+
+    fn from_style(style: &ComputedValues, side: PhysicalSide) -> Self {
+        let border = &style.border;
+        let (width, line_style, color) = match side {
+            PhysicalSide::Top => (
+                border.border_top_width,
+                border.border_top_style,
+                border.border_top_color,
+            ),
+            PhysicalSide::Right => (
+                border.border_right_width,
+                border.border_right_style,
+                border.border_right_color,
+            ),
+            PhysicalSide::Bottom => (
+                border.border_bottom_width,
+                border.border_bottom_style,
+                border.border_bottom_color,
+            ),
+            PhysicalSide::Left => (
+                border.border_left_width,
+                border.border_left_style,
+                border.border_left_color,
+            ),
+        };
+        CollapsedBorder {
+            width: width.0.percentage_relative_to(Length::zero()),
+            style: line_style,
+            // `currentcolor` refers to the color of the box the border comes from.
+            color: Color::RGBA(style.to_rgba(color)),
+        }
+    }
+
+    /// A hidden border suppresses all others. Otherwise, wider borders win,
+    /// then borders with a more prominent style.
+    /// On a tie, the border that comes first wins.
+    fn resolve(candidates: impl IntoIterator<Item = Self>) -> Self {
+        fn style_rank(style: LineStyle) -> u8 {
+            match style {
+                LineStyle::None | LineStyle::Hidden => 0,
+                LineStyle::Inset => 1,
+                LineStyle::Groove => 2,
+                LineStyle::Outset => 3,
+                LineStyle::Ridge => 4,
+                LineStyle::Dotted => 5,
+                LineStyle::Dashed => 6,
+                LineStyle::Solid => 7,
+                LineStyle::Double => 8,
+            }
+        }
+        let candidates: Vec<Self> = candidates.into_iter().collect();
+        if candidates.iter().any(|border| border.style == LineStyle::Hidden) {
+            return Self::NONE;
+        }
+        candidates
+            .into_iter()
+            .filter(|border| border.style != LineStyle::None && border.width > Length::zero())
+            .fold(Self::NONE, |winner, border| {
+                let wins = border.width > winner.width
+                    || (border.width == winner.width
+                        && style_rank(border.style) > style_rank(winner.style));
+                if wins {
+                    border
+                } else {
+                    winner
+                }
+            })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum PhysicalSide {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl PhysicalSide {
+    fn opposite(self) -> Self {
+        match self {
+            PhysicalSide::Top => PhysicalSide::Bottom,
+            PhysicalSide::Right => PhysicalSide::Left,
+            PhysicalSide::Bottom => PhysicalSide::Top,
+            PhysicalSide::Left => PhysicalSide::Right,
+        }
+    }
+}
+
+impl Table {
+    /// In the collapsing border model, adjacent cells share the border between them,
+    /// resolved from the borders of the cells, rows, and row groups on either side.
+    /// Each cell is given half of the resolved internal borders, and the whole of
+    /// the borders at the edges of the table, unless the table has a border there.
+    /// https://drafts.csswg.org/css2/tables.html#collapsing-borders
+    // FIXME: borders of columns and column groups
+    fn collapse_borders(&mut self) {
+        let row_count = self.rows.len();
+        let column_count = self.columns.len();
+        // The cell occupying each slot of the grid, as (row, index in row).
+        let mut slots = vec![vec![None; column_count]; row_count];
+        for (row_index, row) in self.rows.iter().enumerate() {
+            for (index, cell) in row.cells.iter().enumerate() {
+                for slot_row in &mut slots[row_index..row_index + cell.rowspan] {
+                    for slot in &mut slot_row[cell.column..cell.column + cell.colspan] {
+                        slot.get_or_insert((row_index, index));
+                    }
+                }
+            }
+        }
+        let group_of_row = |row_index: usize| {
+            self.row_groups
+                .iter()
+                .find(|group| group.rows.contains(&row_index))
+                .expect("every row is in a row group")
+        };
+
+        let mut resolved = Vec::with_capacity(self.rows.len());
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let mut resolved_row = Vec::with_capacity(row.cells.len());
+            for cell in &row.cells {
+                let rows = row_index..row_index + cell.rowspan;
+                let columns = cell.column..cell.column + cell.colspan;
+                let last_row = rows.end - 1;
+                let resolve_side = |side: PhysicalSide| {
+                    let own = CollapsedBorder::from_style(&cell.style, side);
+                    // The cells on the other side of this border, if any.
+                    let neighbors: Option<Vec<(usize, usize)>> = match side {
+                        PhysicalSide::Top if rows.start > 0 => Some(
+                            columns.clone().filter_map(|c| slots[rows.start - 1][c]).collect(),
+                        ),
+                        PhysicalSide::Bottom if rows.end < row_count => Some(
+                            columns.clone().filter_map(|c| slots[rows.end][c]).collect(),
+                        ),
+                        PhysicalSide::Left if columns.start > 0 => Some(
+                            rows.clone().filter_map(|r| slots[r][columns.start - 1]).collect(),
+                        ),
+                        PhysicalSide::Right if columns.end < column_count => Some(
+                            rows.clone().filter_map(|r| slots[r][columns.end]).collect(),
+                        ),
+                        _ => None,
+                    };
+                    let neighbors = match neighbors {
+                        Some(neighbors) => neighbors,
+                        None => {
+                            // At the edge of the table
+                            if CollapsedBorder::from_style(&self.style, side).width > Length::zero()
+                            {
+                                return (CollapsedBorder::NONE, false);
+                            }
+                            let mut candidates = vec![own];
+                            match side {
+                                PhysicalSide::Top => {
+                                    candidates.push(CollapsedBorder::from_style(&row.style, side));
+                                    candidates.push(CollapsedBorder::from_style(
+                                        &group_of_row(row_index).style,
+                                        side,
+                                    ));
+                                }
+                                PhysicalSide::Bottom => {
+                                    candidates.push(CollapsedBorder::from_style(
+                                        &self.rows[last_row].style,
+                                        side,
+                                    ));
+                                    candidates.push(CollapsedBorder::from_style(
+                                        &group_of_row(last_row).style,
+                                        side,
+                                    ));
+                                }
+                                PhysicalSide::Left | PhysicalSide::Right => {
+                                    for r in rows.clone() {
+                                        candidates.push(CollapsedBorder::from_style(
+                                            &self.rows[r].style,
+                                            side,
+                                        ));
+                                    }
+                                    for r in rows.clone() {
+                                        candidates.push(CollapsedBorder::from_style(
+                                            &group_of_row(r).style,
+                                            side,
+                                        ));
+                                    }
+                                }
+                            }
+                            return (CollapsedBorder::resolve(candidates), false);
+                        }
+                    };
+                    let neighbor_borders = neighbors.iter().map(|&(r, i)| {
+                        CollapsedBorder::from_style(&self.rows[r].cells[i].style, side.opposite())
+                    });
+                    // On a tie, the border of the box further to the top or the left wins.
+                    let mut candidates: Vec<CollapsedBorder> = match side {
+                        PhysicalSide::Top | PhysicalSide::Left => {
+                            neighbor_borders.chain(std::iter::once(own)).collect()
+                        }
+                        PhysicalSide::Bottom | PhysicalSide::Right => {
+                            std::iter::once(own).chain(neighbor_borders).collect()
+                        }
+                    };
+                    // Rows and row groups on either side of a horizontal border.
+                    let rows_around = match side {
+                        PhysicalSide::Top => Some((rows.start - 1, rows.start)),
+                        PhysicalSide::Bottom => Some((last_row, rows.end)),
+                        PhysicalSide::Left | PhysicalSide::Right => None,
+                    };
+                    if let Some((above, below)) = rows_around {
+                        candidates.push(CollapsedBorder::from_style(
+                            &self.rows[above].style,
+                            PhysicalSide::Bottom,
+                        ));
+                        candidates.push(CollapsedBorder::from_style(
+                            &self.rows[below].style,
+                            PhysicalSide::Top,
+                        ));
+                        let (group_above, group_below) = (group_of_row(above), group_of_row(below));
+                        if !std::ptr::eq(group_above, group_below) {
+                            candidates.push(CollapsedBorder::from_style(
+                                &group_above.style,
+                                PhysicalSide::Bottom,
+                            ));
+                            candidates.push(CollapsedBorder::from_style(
+                                &group_below.style,
+                                PhysicalSide::Top,
+                            ));
+                        }
+                    }
+                    (CollapsedBorder::resolve(candidates), true)
+                };
+                resolved_row.push([
+                    resolve_side(PhysicalSide::Top),
+                    resolve_side(PhysicalSide::Right),
+                    resolve_side(PhysicalSide::Bottom),
+                    resolve_side(PhysicalSide::Left),
+                ]);
+            }
+            resolved.push(resolved_row);
+        }
+
+        for (row, resolved_row) in self.rows.iter_mut().zip(resolved) {
+            for (cell, sides) in row.cells.iter_mut().zip(resolved_row) {
+                let mut style = (*cell.style).clone();
+                let border = Arc::make_mut(&mut style.border);
+                let [top, right, bottom, left] = sides.map(|(border, shared)| {
+                    let width = if shared { border.width / 2. } else { border.width };
+                    (LineWidth(LengthOrPercentage::Length(width)), border.style, border.color)
+                });
+                (border.border_top_width, border.border_top_style, border.border_top_color) = top;
+                (border.border_right_width, border.border_right_style, border.border_right_color) =
+                    right;
+                (
+                    border.border_bottom_width,
+                    border.border_bottom_style,
+                    border.border_bottom_color,
+                ) = bottom;
+                (border.border_left_width, border.border_left_style, border.border_left_color) =
+                    left;
+                cell.style = Arc::new(style);
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use super::{content_sizes, layout_boxes};
+
+const BOXES: &str = r#"
+    <span style="display: inline-block; width: 30px; height: 10px"></span>
+    <span style="display: inline-block; width: 50px; height: 10px"></span>
+    <span style="display: inline-block; width: 20px; height: 10px"></span>"#;
+
+#[test]
+fn lines_wrap_only_below_the_max_content_size() {
+    assert_eq!(content_sizes(BOXES), [50., 100.]);
+
+    let boxes = layout_boxes(&format!(r#"<div style="width: 100px">{}</div>"#, BOXES));
+    assert_eq!(boxes[0], [0., 0., 100., 10.]);
+    assert_eq!(boxes[3], [80., 0., 20., 10.]);
+
+    let boxes = layout_boxes(&format!(r#"<div style="width: 99px">{}</div>"#, BOXES));
+    assert_eq!(boxes[0], [0., 0., 99., 20.]);
+    assert_eq!(boxes[3], [0., 10., 20., 10.]);
+
+    // at the min-content size, no box overflows
+    let boxes = layout_boxes(&format!(r#"<div style="width: 50px">{}</div>"#, BOXES));
+    assert_eq!(boxes[0], [0., 0., 50., 30.]);
+    assert_eq!(boxes[1], [0., 0., 30., 10.]);
+    assert_eq!(boxes[2], [0., 10., 50., 10.]);
+    assert_eq!(boxes[3], [0., 20., 20., 10.]);
+}
+
+#[test]
+fn content_sizes_include_margins_borders_and_padding() {
+    let html = r#"
+        <div style="padding: 0 5px; border: 2px solid; margin-left: 3px">
+            <span style="display: inline-block; width: 30px; height: 10px"></span>
+            <span style="display: inline-block; width: 50px; height: 10px"></span>
+        </div>
+        <div style="width: 60px"></div>"#;
+    assert_eq!(content_sizes(html), [67., 97.]);
+
+    let boxes = layout_boxes(&format!(r#"<div style="width: 97px">{}</div>"#, html));
+    assert_eq!(boxes[1], [3., 0., 94., 14.]);
+    assert_eq!(boxes[3], [40., 2., 50., 10.]);
+}
+
+#[test]
+fn content_sizes_of_flex_containers_and_tables() {
+    let flex = r#"
+        <div style="display: flex; gap: 10px">
+            <div style="width: 30px"></div><div style="width: 40px"></div>
+        </div>"#;
+    assert_eq!(content_sizes(flex), [80., 80.]);
+
+    // cells have 1px of padding
+    let table = r#"
+        <table style="border-spacing: 0"><tr>
+            <td><span style="display: inline-block; width: 30px; height: 10px"></span>
+                <span style="display: inline-block; width: 50px; height: 10px"></span></td>
+            <td></td>
+        </tr></table>"#;
+    assert_eq!(content_sizes(table), [54., 84.]);
+}
@@ -0,0 +1,50 @@
+use super::text_runs;
+
+fn texts(html: &str) -> Vec<String> {
+    text_runs(html).into_iter().map(|(text, ..)| text).collect()
+}
+
+#[test]
+fn nested_counters() {
+    let texts = texts(
+        r#"<style>
+            div { counter-reset: item }
+            p { counter-increment: item }
+            p::before { content: counters(item, ".") " " }
+        </style>
+        <div><p>a</p><p>b</p><div><p>c</p><div><p>d</p><p>e</p><p>f</p></div></div></div>"#,
+    );
+    assert_eq!(texts, vec!["1 ", "a", "2 ", "b", "2.1 ", "c", "2.1.1 ", "d", "2.1.2 ", "e", "2.1.3 ", "f"]);
+}
+
+#[test]
+fn sibling_reset_replaces_counter() {
+    let texts = texts(
+        r#"<style>
+            p { counter-increment: item }
+            p::before { content: counters(item, ".") }
+            .reset { counter-reset: item 5 }
+        </style>
+        <div><p></p><p class="reset"></p><p></p></div>"#,
+    );
+    assert_eq!(texts, vec!["1", "6", "7"]);
+}
+
+#[test]
+fn display_none_does_not_count() {
+    let texts = texts(
+        r#"<style>
+            p { counter-increment: item }
+            p::before { content: counter(item) }
+            .hidden { display: none }
+        </style>
+        <div><p></p><p class="hidden"></p><p></p></div>"#,
+    );
+    assert_eq!(texts, vec!["1", "2"]);
+}
+
+#[test]
+fn list_item_markers() {
+    let texts = texts(r#"<ol start="3"><li>a</li><li value="7">b</li><li>c</li></ol>"#);
+    assert_eq!(texts, vec!["3. ", "a", "7. ", "b", "8. ", "c"]);
+}
@@ -0,0 +1,59 @@
+use super::text_runs;
+use cssparser::RGBA;
+
+const BLACK: RGBA = RGBA { red: 0, green: 0, blue: 0, alpha: 255 };
+const RED: RGBA = RGBA { red: 255, green: 0, blue: 0, alpha: 255 };
+const BLUE: RGBA = RGBA { red: 0, green: 0, blue: 255, alpha: 255 };
+
+fn text(text: &str, color: RGBA, first_line_color: RGBA) -> (String, RGBA, RGBA) {
+    (text.into(), color, first_line_color)
+}
+
+#[test]
+fn inline_boxes_inherit_from_first_line() {
+    let runs = text_runs(
+        r#"<style>p::first-line { color: red }</style>
+        <p>a<span>b</span><em style="color: blue">c</em></p>"#,
+    );
+    assert_eq!(
+        runs,
+        vec![text("a", BLACK, RED), text("b", BLACK, RED), text("c", BLUE, BLUE)]
+    );
+}
+
+#[test]
+fn first_line_of_first_block_child() {
+    let runs = text_runs(
+        r#"<style>div::first-line { color: red }</style>
+        <div><p>a</p><p>b</p></div><div>c<p>d</p></div>"#,
+    );
+    assert_eq!(
+        runs,
+        vec![
+            text("a", BLACK, RED),
+            text("b", BLACK, BLACK),
+            text("c", BLACK, RED),
+            text("d", BLACK, BLACK),
+        ]
+    );
+}
+
+#[test]
+fn first_letter_with_punctuation() {
+    let runs = text_runs(
+        r#"<style>p::first-letter { color: red }</style><p>  ("A") b</p>"#,
+    );
+    assert_eq!(runs, vec![text(r#"("A")"#, RED, RED), text(" b", BLACK, BLACK)]);
+}
+
+#[test]
+fn first_letter_of_first_block_child() {
+    let runs = text_runs(
+        r#"<style>
+            div::first-line { color: blue }
+            div::first-letter { color: red }
+        </style>
+        <div><p>ab</p></div>"#,
+    );
+    assert_eq!(runs, vec![text("a", RED, RED), text("b", BLACK, BLUE)]);
+}
@@ -0,0 +1,72 @@
+use super::layout_boxes;
+
+#[test]
+fn flex_grow_distributes_free_space() {
+    let boxes = layout_boxes(
+        r#"<div style="display: flex; width: 300px">
+            <div style="flex: 1 1 0; height: 10px"></div>
+            <div style="flex: 2 1 0; height: 20px"></div>
+            <div style="width: 60px; height: 5px"></div>
+        </div>"#,
+    );
+    assert_eq!(boxes[0], [0., 0., 300., 20.]);
+    assert_eq!(boxes[1], [0., 0., 80., 10.]);
+    assert_eq!(boxes[2], [80., 0., 160., 20.]);
+    assert_eq!(boxes[3], [240., 0., 60., 5.]);
+}
+
+#[test]
+fn flex_shrink_is_scaled_by_the_flex_basis() {
+    let boxes = layout_boxes(
+        r#"<div style="display: flex; width: 100px">
+            <div style="flex-basis: 80px; flex-shrink: 1; height: 10px"></div>
+            <div style="flex-basis: 40px; flex-shrink: 3; height: 10px"></div>
+        </div>"#,
+    );
+    assert_eq!(boxes[1], [0., 0., 72., 10.]);
+    assert_eq!(boxes[2], [72., 0., 28., 10.]);
+}
+
+#[test]
+fn justify_content_align_items_and_gap() {
+    let boxes = layout_boxes(
+        r#"<div style="display: flex; width: 200px; justify-content: space-between;
+                       align-items: center; gap: 5px">
+            <div style="width: 40px; height: 10px"></div>
+            <div style="width: 40px; height: 30px"></div>
+            <div style="width: 40px; height: 20px"></div>
+        </div>"#,
+    );
+    assert_eq!(boxes[0], [0., 0., 200., 30.]);
+    assert_eq!(boxes[1], [0., 10., 40., 10.]);
+    assert_eq!(boxes[2], [80., 0., 40., 30.]);
+    assert_eq!(boxes[3], [160., 5., 40., 20.]);
+}
+
+#[test]
+fn flex_wrap_with_row_and_column_gaps() {
+    let boxes = layout_boxes(
+        r#"<div style="display: flex; flex-wrap: wrap; width: 100px; gap: 4px 10px">
+            <div style="width: 40px; height: 10px"></div>
+            <div style="width: 40px; height: 20px"></div>
+            <div style="width: 40px; height: 10px"></div>
+        </div>"#,
+    );
+    assert_eq!(boxes[0], [0., 0., 100., 34.]);
+    assert_eq!(boxes[1], [0., 0., 40., 10.]);
+    assert_eq!(boxes[2], [50., 0., 40., 20.]);
+    assert_eq!(boxes[3], [0., 24., 40., 10.]);
+}
+
+#[test]
+fn column_direction_grows_and_stretches() {
+    let boxes = layout_boxes(
+        r#"<div style="display: flex; flex-direction: column; height: 100px">
+            <div style="flex-grow: 1; width: 10px"></div>
+            <div style="height: 20px"></div>
+        </div>"#,
+    );
+    assert_eq!(boxes[1], [0., 0., 10., 80.]);
+    // items without a width stretch to the cross size of the line
+    assert_eq!(boxes[2], [0., 80., 400., 20.]);
+}
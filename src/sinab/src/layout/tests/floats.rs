@@ -0,0 +1,62 @@
+use super::{layout_boxes, png_file};
+
+#[test]
+fn floats_stack_and_clear() {
+    let boxes = layout_boxes(
+        r#"<div style="width: 200px">
+            <div style="float: left; width: 50px; height: 20px"></div>
+            <div style="float: left; width: 60px; height: 30px"></div>
+            <div style="float: right; width: 40px; height: 10px"></div>
+            <div style="float: left; width: 100px; height: 10px"></div>
+            <div style="clear: left; height: 10px"></div>
+        </div>"#,
+    );
+    assert_eq!(boxes[1], [0., 0., 50., 20.]);
+    // left floats stack to the right of each other
+    assert_eq!(boxes[2], [50., 0., 60., 30.]);
+    assert_eq!(boxes[3], [160., 0., 40., 10.]);
+    // a float that does not fit beside the others moves below them
+    assert_eq!(boxes[4], [0., 30., 100., 10.]);
+    // clearance moves the block below all left floats
+    assert_eq!(boxes[5], [0., 40., 200., 10.]);
+    assert_eq!(boxes[0], [0., 0., 200., 50.]);
+}
+
+#[test]
+fn line_boxes_are_shortened_around_floats() {
+    let boxes = layout_boxes(
+        r#"<div style="width: 100px">
+            <div style="float: left; width: 30px; height: 20px"></div>
+            <div style="float: right; width: 20px; height: 20px"></div>
+            <span style="display: inline-block; width: 40px; height: 10px"></span>
+            <span style="display: inline-block; width: 40px; height: 10px"></span>
+        </div>"#,
+    );
+    assert_eq!(boxes[1], [0., 0., 30., 20.]);
+    assert_eq!(boxes[2], [80., 0., 20., 20.]);
+    // the line boxes are in an anonymous block box
+    assert_eq!(boxes[3], [0., 0., 100., 20.]);
+    // only one inline-block fits between the floats
+    assert_eq!(boxes[4], [30., 0., 40., 10.]);
+    assert_eq!(boxes[5], [30., 10., 40., 10.]);
+}
+
+#[test]
+fn block_level_images_avoid_floats() {
+    let boxes = layout_boxes(&format!(
+        r#"<div style="width: 200px">
+            <div style="float: left; width: 50px; height: 30px"></div>
+            <img style="display: block" src="{0}" width="40">
+            <img style="display: block; margin: 0 auto" src="{0}" width="40">
+            <img style="display: block; clear: left" src="{0}" width="40">
+        </div>"#,
+        png_file(2, 1),
+    ));
+    assert_eq!(boxes[1], [0., 0., 50., 30.]);
+    // the border box starts after the float
+    assert_eq!(boxes[2], [50., 0., 40., 20.]);
+    // auto margins center it in the space left by the float
+    assert_eq!(boxes[3], [105., 20., 40., 20.]);
+    // unless it clears the float
+    assert_eq!(boxes[4], [0., 40., 40., 20.]);
+}
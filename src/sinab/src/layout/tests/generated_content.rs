@@ -0,0 +1,30 @@
+use super::text_runs;
+
+fn texts(html: &str) -> Vec<String> {
+    text_runs(html).into_iter().map(|(text, ..)| text).collect()
+}
+
+#[test]
+fn before_and_after() {
+    let texts = texts(
+        r#"<style>
+            p::before { content: "(" attr(title) ") " }
+            p::after { content: open-quote "b" open-quote "c" close-quote close-quote }
+            em::before { content: none }
+        </style>
+        <p title="a">x<em>y</em></p>"#,
+    );
+    assert_eq!(texts, vec!["(a) ", "x", "y", "“b‘c’”"]);
+}
+
+#[test]
+fn display_none_and_contents() {
+    let texts = texts(
+        r#"<style>
+            p::before { content: "a"; display: none }
+            p::after { content: "b"; display: contents }
+        </style>
+        <p>x</p>"#,
+    );
+    assert_eq!(texts, vec!["xb"]);
+}
@@ -0,0 +1,37 @@
+use super::layout_boxes;
+
+#[test]
+fn grid_placement() {
+    let boxes = layout_boxes(
+        r#"<div style="display: grid; grid-template-columns: 100px 100px 100px; grid-auto-rows: 10px">
+            <div style="grid-column: auto / span 2"></div>
+            <div style="grid-column: span 2 / auto"></div>
+            <div style="grid-column: 3; grid-row: 1"></div>
+            <div style="grid-column: -2 / -4; grid-row: 3"></div>
+        </div>"#,
+    );
+    assert_eq!(boxes[0], [0., 0., 400., 30.]);
+    // auto-placed items span two tracks, whichever line is the span
+    assert_eq!(boxes[1], [0., 0., 200., 10.]);
+    assert_eq!(boxes[2], [0., 10., 200., 10.]);
+    // explicitly placed items are placed first
+    assert_eq!(boxes[3], [200., 0., 100., 10.]);
+    // negative lines count from the end, and are swapped when reversed
+    assert_eq!(boxes[4], [0., 20., 200., 10.]);
+}
+
+#[test]
+fn flexible_tracks_and_gaps() {
+    let boxes = layout_boxes(
+        r#"<div style="display: grid; width: 320px; grid-template-columns: 50px 1fr 2fr;
+                       gap: 10px 15px">
+            <div style="height: 10px"></div><div></div><div></div>
+            <div style="grid-row: span 2; height: 30px"></div>
+        </div>"#,
+    );
+    assert_eq!(boxes[0], [0., 0., 320., 50.]);
+    assert_eq!(boxes[1], [0., 0., 50., 10.]);
+    assert_eq!(boxes[2], [65., 0., 80., 10.]);
+    assert_eq!(boxes[3], [160., 0., 160., 10.]);
+    assert_eq!(boxes[4], [0., 20., 50., 30.]);
+}
@@ -0,0 +1,59 @@
+use super::layout_boxes;
+
+#[test]
+fn inline_blocks_share_a_baseline() {
+    let boxes = layout_boxes(
+        r#"<div>
+            <span style="display: inline-block; width: 10px; height: 20px"></span>
+            <span style="display: inline-block; width: 10px; height: 50px"></span>
+            <span style="display: inline-block; width: 10px; height: 30px; margin-bottom: 5px"></span>
+            <span style="display: inline-block; padding: 2px; border: 1px solid">
+                <span style="display: inline-block; width: 10px; height: 8px"></span>
+            </span>
+        </div>"#,
+    );
+    // without line boxes, the baseline is the bottom margin edge
+    assert_eq!(boxes[1], [0., 30., 10., 20.]);
+    assert_eq!(boxes[2], [10., 0., 10., 50.]);
+    assert_eq!(boxes[3], [20., 15., 10., 30.]);
+    // otherwise it is the baseline of the last line box
+    assert_eq!(boxes[4], [30., 39., 16., 14.]);
+    assert_eq!(boxes[5], [33., 42., 10., 8.]);
+    assert_eq!(boxes[0], [0., 0., 400., 53.]);
+}
+
+#[test]
+fn inline_blocks_with_overflow_sit_on_their_bottom_margin_edge() {
+    let boxes = layout_boxes(
+        r#"<div>
+            <span style="display: inline-block; width: 10px; height: 30px"></span>
+            <span style="display: inline-block; padding: 2px; margin-bottom: 3px; overflow: hidden">
+                <span style="display: inline-block; width: 10px; height: 8px"></span>
+            </span>
+            <span style="display: inline-block; padding: 2px; overflow: clip">
+                <span style="display: inline-block; width: 10px; height: 8px"></span>
+            </span>
+        </div>"#,
+    );
+    assert_eq!(boxes[1], [0., 0., 10., 30.]);
+    assert_eq!(boxes[2], [10., 15., 14., 12.]);
+    assert_eq!(boxes[3], [12., 17., 10., 8.]);
+    assert_eq!(boxes[4], [24., 18., 14., 12.]);
+    assert_eq!(boxes[5], [26., 20., 10., 8.]);
+    assert_eq!(boxes[0], [0., 0., 400., 30.]);
+}
+
+#[test]
+fn inline_blocks_break_between_lines() {
+    let boxes = layout_boxes(
+        r#"<div style="width: 50px">
+            <span style="display: inline-block; width: 20px; height: 10px"></span>
+            <span style="display: inline-block; width: 20px; height: 10px"></span>
+            <span style="display: inline-block; width: 20px; height: 10px"></span>
+        </div>"#,
+    );
+    assert_eq!(boxes[1], [0., 0., 20., 10.]);
+    assert_eq!(boxes[2], [20., 0., 20., 10.]);
+    assert_eq!(boxes[3], [0., 10., 20., 10.]);
+    assert_eq!(boxes[0], [0., 0., 50., 20.]);
+}
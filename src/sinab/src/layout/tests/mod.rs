@@ -0,0 +1,187 @@
+// Tests of the geometry of laid out documents
+
+#[cfg(test)]
+mod content_sizes;
+
+#[cfg(test)]
+mod flex;
+
+#[cfg(test)]
+mod counters;
+
+#[cfg(test)]
+mod first_line;
+
+#[cfg(test)]
+mod floats;
+
+#[cfg(test)]
+mod generated_content;
+
+#[cfg(test)]
+mod grid;
+
+#[cfg(test)]
+mod inline_block;
+
+#[cfg(test)]
+mod multicol;
+
+#[cfg(test)]
+mod positioned;
+
+#[cfg(test)]
+mod replaced;
+
+#[cfg(test)]
+mod sizing;
+
+#[cfg(test)]
+mod table;
+
+#[cfg(test)]
+use super::*;
+
+#[cfg(test)]
+use cssparser::RGBA;
+
+#[cfg(test)]
+const USER_CSS: &str = "body { margin: 0; font-size: 0 }";
+
+/// The min-content and max-content inline sizes of `html` in CSS pixels,
+/// with the same user stylesheet as `layout_boxes`.
+#[cfg(test)]
+fn content_sizes(html: &str) -> [f32; 2] {
+    let document = dom::Document::parse_html(html.as_bytes());
+    let sizes = document.inline_content_sizes(Some(USER_CSS));
+    [sizes.min_content.px, sizes.max_content.px]
+}
+
+/// Lays out `html` in a 400px wide viewport and returns the border boxes of
+/// the box fragments inside the body, including those of anonymous block boxes,
+/// in tree order, as `[x, y, width, height]` in CSS pixels. The body has no margin and a zero font size, so that text
+/// and whitespace take no room.
+#[cfg(test)]
+fn layout_boxes(html: &str) -> Vec<[f32; 4]> {
+    let document = dom::Document::parse_html(html.as_bytes());
+    let viewport = crate::primitives::Size::new(400., 400.);
+    let fragments = document.layout(viewport, Some(USER_CSS));
+    let mut boxes = Vec::new();
+    collect_boxes(&fragments, (0., 0.), &mut boxes);
+    // The root and body elements come first.
+    boxes.split_off(2)
+}
+
+/// Writes a PNG file of the given size to the temporary directory, and
+/// returns its path. Only its header is written, which is all layout reads.
+#[cfg(test)]
+fn png_file(width: u32, height: u32) -> String {
+    let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    bytes.extend_from_slice(&width.to_be_bytes());
+    bytes.extend_from_slice(&height.to_be_bytes());
+    bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+    // one file per call, as tests run in parallel
+    static FILES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    let file = FILES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let name = format!("sinab-{}-{}.png", std::process::id(), file);
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, bytes).unwrap();
+    path.to_str().unwrap().to_owned()
+}
+
+#[cfg(test)]
+fn collect_boxes(fragments: &[Fragment], origin: (f32, f32), boxes: &mut Vec<[f32; 4]>) {
+    for fragment in fragments {
+        match fragment {
+            Fragment::Box(b) => {
+                let rect = b.border_rect();
+                boxes.push([
+                    origin.0 + rect.start_corner.inline.px,
+                    origin.1 + rect.start_corner.block.px,
+                    rect.size.inline.px,
+                    rect.size.block.px,
+                ]);
+                let content = &b.content_rect.start_corner;
+                let origin = (origin.0 + content.inline.px, origin.1 + content.block.px);
+                collect_boxes(&b.children, origin, boxes)
+            }
+            Fragment::Anonymous(a) => {
+                let start = &a.rect.start_corner;
+                let origin = (origin.0 + start.inline.px, origin.1 + start.block.px);
+                collect_boxes(&a.children, origin, boxes)
+            }
+            Fragment::Text(_) | Fragment::Image(_) => {}
+        }
+    }
+}
+
+/// The text runs in the box tree of `html`, in tree order, including those of
+/// `::marker` boxes, as their text, the color of their text, and the color of
+/// the part of their text on the first formatted line.
+#[cfg(test)]
+fn text_runs(html: &str) -> Vec<(String, RGBA, RGBA)> {
+    let document = dom::Document::parse_html(html.as_bytes());
+    let mut text_runs = Vec::new();
+    collect_block_container(&document.box_tree(Some(USER_CSS)).contents, &mut text_runs);
+    text_runs
+}
+
+#[cfg(test)]
+fn collect_block_container(container: &BlockContainer, text_runs: &mut Vec<(String, RGBA, RGBA)>) {
+    match container {
+        BlockContainer::BlockLevelBoxes(boxes) => {
+            for box_ in boxes {
+                match &**box_ {
+                    BlockLevelBox::SameFormattingContextBlock { contents, .. }
+                    | BlockLevelBox::OutsideMarker { contents } => {
+                        collect_block_container(contents, text_runs)
+                    }
+                    BlockLevelBox::Independent { contents, .. } => {
+                        collect_independent(contents, text_runs)
+                    }
+                    BlockLevelBox::OutOfFlowAbsolutelyPositionedBox(b) => {
+                        collect_independent(&b.contents, text_runs)
+                    }
+                    BlockLevelBox::OutOfFlowFloatBox(b) => {
+                        collect_independent(&b.contents, text_runs)
+                    }
+                }
+            }
+        }
+        BlockContainer::InlineFormattingContext(ifc) => {
+            collect_inline_level_boxes(&ifc.inline_level_boxes, text_runs)
+        }
+    }
+}
+
+#[cfg(test)]
+fn collect_inline_level_boxes(
+    boxes: &[Arc<InlineLevelBox>],
+    text_runs: &mut Vec<(String, RGBA, RGBA)>,
+) {
+    for box_ in boxes {
+        match &**box_ {
+            InlineLevelBox::InlineBox(b) => collect_inline_level_boxes(&b.children, text_runs),
+            InlineLevelBox::TextRun(r) => {
+                let color = r.parent_style.color.color;
+                let first_line_color = r.first_line.as_ref().map_or(color, |(s, _)| s.color.color);
+                text_runs.push((r.text.clone(), color, first_line_color))
+            }
+            InlineLevelBox::Atomic { contents, .. } => collect_independent(contents, text_runs),
+            InlineLevelBox::OutOfFlowAbsolutelyPositionedBox(b) => {
+                collect_independent(&b.contents, text_runs)
+            }
+            InlineLevelBox::OutOfFlowFloatBox(b) => collect_independent(&b.contents, text_runs),
+        }
+    }
+}
+
+#[cfg(test)]
+fn collect_independent(
+    contents: &IndependentFormattingContext,
+    text_runs: &mut Vec<(String, RGBA, RGBA)>,
+) {
+    if let IndependentFormattingContext::Flow(bfc) = contents {
+        collect_block_container(&bfc.contents, text_runs)
+    }
+}
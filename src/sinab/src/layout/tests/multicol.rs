@@ -0,0 +1,53 @@
+use super::layout_boxes;
+
+#[test]
+fn column_count_balances_the_contents() {
+    let boxes = layout_boxes(
+        r#"<div style="column-count: 2; column-gap: 20px; width: 220px">
+            <div style="height: 10px"></div><div style="height: 20px"></div>
+            <div style="height: 10px"></div><div style="height: 20px"></div>
+        </div>"#,
+    );
+    assert_eq!(boxes[0], [0., 0., 220., 30.]);
+    assert_eq!(boxes[1], [0., 0., 100., 10.]);
+    assert_eq!(boxes[2], [0., 10., 100., 20.]);
+    assert_eq!(boxes[3], [120., 0., 100., 10.]);
+    assert_eq!(boxes[4], [120., 10., 100., 20.]);
+}
+
+#[test]
+fn column_width_determines_the_column_count() {
+    let boxes = layout_boxes(
+        r#"<div style="column-width: 100px; column-gap: 10px; width: 350px">
+            <div style="height: 30px"></div>
+            <div style="height: 30px"></div>
+            <div style="height: 30px"></div>
+        </div>"#,
+    );
+    // three columns fit, and share the remaining space
+    assert_eq!(boxes[0], [0., 0., 350., 30.]);
+    assert_eq!(boxes[1], [0., 0., 110., 30.]);
+    assert_eq!(boxes[2], [120., 0., 110., 30.]);
+    assert_eq!(boxes[3], [240., 0., 110., 30.]);
+}
+
+#[test]
+fn boxes_are_sliced_between_columns() {
+    let boxes = layout_boxes(
+        r#"<div style="column-count: 2; column-gap: 0; width: 200px">
+            <div style="padding: 5px">
+                <div style="height: 10px"></div><div style="height: 10px"></div>
+                <div style="height: 10px"></div><div style="height: 10px"></div>
+            </div>
+        </div>"#,
+    );
+    assert_eq!(boxes[0], [0., 0., 200., 25.]);
+    // the block-start padding is only on the first slice,
+    // and the block-end padding only on the last one
+    assert_eq!(boxes[1], [0., 0., 100., 25.]);
+    assert_eq!(boxes[2], [5., 5., 90., 10.]);
+    assert_eq!(boxes[3], [5., 15., 90., 10.]);
+    assert_eq!(boxes[4], [100., 0., 100., 25.]);
+    assert_eq!(boxes[5], [105., 0., 90., 10.]);
+    assert_eq!(boxes[6], [105., 10., 90., 10.]);
+}
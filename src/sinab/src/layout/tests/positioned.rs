@@ -0,0 +1,30 @@
+use super::layout_boxes;
+
+#[test]
+fn absolutely_positioned_boxes_shrink_to_fit() {
+    let boxes = layout_boxes(
+        r#"<div style="position: relative; width: 300px; height: 100px">
+            <div style="position: absolute; top: 10px; left: 20px; padding: 2px">
+                <span style="display: inline-block; width: 30px; height: 10px"></span>
+                <span style="display: inline-block; width: 40px; height: 10px"></span>
+            </div>
+            <div style="position: absolute; bottom: 0; right: 10px">
+                <span style="display: inline-block; width: 25px; height: 12px"></span>
+            </div>
+            <div style="position: absolute; left: 260px; top: 0">
+                <span style="display: inline-block; width: 30px; height: 10px"></span>
+                <span style="display: inline-block; width: 30px; height: 10px"></span>
+            </div>
+        </div>"#,
+    );
+    // the max-content width, when it fits
+    assert_eq!(boxes[1], [20., 10., 74., 14.]);
+    assert_eq!(boxes[2], [22., 12., 30., 10.]);
+    assert_eq!(boxes[3], [52., 12., 40., 10.]);
+    // which also places boxes from the end
+    assert_eq!(boxes[4], [265., 88., 25., 12.]);
+    // otherwise the available width, breaking lines
+    assert_eq!(boxes[6], [260., 0., 40., 20.]);
+    assert_eq!(boxes[7], [260., 0., 30., 10.]);
+    assert_eq!(boxes[8], [260., 10., 30., 10.]);
+}
@@ -0,0 +1,21 @@
+use super::{layout_boxes, png_file};
+
+#[test]
+fn image_with_one_size_attribute() {
+    let boxes = layout_boxes(&format!(
+        r#"<img style="display: block" src="{0}" width="50">
+        <img style="display: block" src="{0}" height="50">
+        <img style="display: block; height: 10px" src="{0}" width="50">"#,
+        png_file(200, 100),
+    ));
+    assert_eq!(
+        boxes,
+        [
+            // the other dimension follows the intrinsic ratio
+            [0., 0., 50., 25.],
+            [0., 25., 100., 50.],
+            // CSS sizes win over the attributes
+            [0., 75., 50., 10.],
+        ]
+    );
+}
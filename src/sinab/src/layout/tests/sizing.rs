@@ -0,0 +1,80 @@
+use super::layout_boxes;
+
+#[test]
+fn max_width_clamps_auto_width() {
+    let boxes = layout_boxes(
+        r#"<div style="max-width: 100px; height: 10px"></div>
+        <div style="max-width: 50%; margin-left: auto; height: 10px"></div>"#,
+    );
+    assert_eq!(boxes, [[0., 0., 100., 10.], [200., 10., 200., 10.]]);
+}
+
+#[test]
+fn min_height_overrides_content_height() {
+    let boxes = layout_boxes(
+        r#"<div style="min-height: 30px"><div style="height: 10px"></div></div>
+        <div style="max-height: 5px"><div style="height: 10px"></div></div>"#,
+    );
+    assert_eq!(
+        boxes,
+        [
+            [0., 0., 400., 30.],
+            [0., 0., 400., 10.],
+            // content overflows a max-height
+            [0., 30., 400., 5.],
+            [0., 30., 400., 10.],
+        ]
+    );
+}
+
+#[test]
+fn min_width_beats_max_width() {
+    let boxes = layout_boxes(
+        r#"<div style="width: 50px; min-width: 120px; max-width: 80px; height: 5px"></div>
+        <div style="height: 100px; min-height: 20px; max-height: 10px"></div>"#,
+    );
+    assert_eq!(boxes, [[0., 0., 120., 5.], [0., 5., 400., 20.]]);
+}
+
+#[test]
+fn border_box_sizing() {
+    let boxes = layout_boxes(
+        r#"<div style="box-sizing: border-box; width: 100px; height: 50px;
+                       padding: 10px; border: 5px solid">
+            <div style="height: 10px"></div>
+        </div>
+        <div style="box-sizing: border-box; max-width: 60px; padding: 0 10px; height: 5px"></div>
+        <div style="box-sizing: border-box; width: 10px; padding: 0 10px; height: 5px"></div>"#,
+    );
+    assert_eq!(
+        boxes,
+        [
+            [0., 0., 100., 50.],
+            [15., 15., 70., 10.],
+            // min and max sizes include padding and borders too
+            [0., 50., 60., 5.],
+            // the content box is never negative
+            [0., 55., 20., 5.],
+        ]
+    );
+}
+
+#[test]
+fn absolutely_positioned_min_and_max_sizes() {
+    let boxes = layout_boxes(
+        r#"<div style="position: relative; width: 300px; height: 100px">
+            <div style="position: absolute; left: 0; right: 0; max-width: 100px;
+                        height: 10px; min-height: 20px"></div>
+            <div style="position: absolute; top: 50px; right: 0; box-sizing: border-box;
+                        width: 50px; height: 30px; padding: 5px; max-height: 20px"></div>
+        </div>"#,
+    );
+    assert_eq!(
+        boxes,
+        [
+            [0., 0., 300., 100.],
+            [0., 0., 100., 20.],
+            [250., 50., 50., 20.],
+        ]
+    );
+}
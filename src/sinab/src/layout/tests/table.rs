@@ -0,0 +1,37 @@
+use super::layout_boxes;
+
+#[test]
+fn auto_layout_columns_fit_their_widest_cells() {
+    let boxes = layout_boxes(
+        r#"<table style="border-spacing: 0">
+            <tr><td><span style="display: inline-block; width: 30px; height: 10px"></span></td>
+                <td><span style="display: inline-block; width: 50px; height: 20px"></span></td></tr>
+            <tr><td><span style="display: inline-block; width: 60px; height: 10px"></span></td>
+                <td></td></tr>
+        </table>"#,
+    );
+    // table, row group and rows
+    assert_eq!(boxes[0], [0., 0., 114., 34.]);
+    assert_eq!(boxes[2], [0., 0., 114., 22.]);
+    assert_eq!(boxes[3], [0., 22., 114., 12.]);
+    // cells have 1px of padding and are vertically centered
+    assert_eq!(boxes[4], [0., 0., 62., 22.]);
+    assert_eq!(boxes[5], [1., 6., 30., 10.]);
+    assert_eq!(boxes[6], [62., 0., 52., 22.]);
+    assert_eq!(boxes[8], [0., 22., 62., 12.]);
+    assert_eq!(boxes[10], [62., 22., 52., 12.]);
+}
+
+#[test]
+fn fixed_layout_distributes_the_table_width() {
+    let boxes = layout_boxes(
+        r#"<table style="table-layout: fixed; width: 300px; border-spacing: 10px 5px">
+            <tr><td style="width: 100px"></td><td></td><td></td></tr>
+        </table>"#,
+    );
+    assert_eq!(boxes[0], [0., 0., 300., 12.]);
+    // the columns share what is left after the spacing and the first column
+    assert_eq!(boxes[3], [10., 5., 102., 2.]);
+    assert_eq!(boxes[4], [122., 5., 79., 2.]);
+    assert_eq!(boxes[5], [211., 5., 79., 2.]);
+}
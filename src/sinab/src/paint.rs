@@ -1,11 +1,21 @@
 use crate::dom::*;
 use crate::geom::physical::*;
 use crate::geom::Length;
-use crate::layout::{BoxFragment, Fragment};
-use crate::primitives::{CssPx, Size, Point, RGBA};
-use crate::graphics_engine::renderer::RenderDevice;
+use crate::layout::{BoxFragment, Fragment, Image, IntrinsicSizes};
+use crate::primitives::{CssPx, Size, Transform2D, RGBA};
+use crate::graphics_engine::renderer::{color_at, Gradient, RenderDevice};
 use crate::style::style_for_element;
-use crate::style::values::LineStyle;
+use crate::style::ComputedValues;
+use crate::style::values::{
+    BackgroundBox, BackgroundRepeat, BackgroundSize, BorderCornerRadius, ColorStop, Display,
+    DisplayGeneratingBox, DisplayInside, DisplayOutside, Float, GradientDirection, ImageValue,
+    LengthOrAuto, LengthOrPercentage, LineStyle, LinearGradient, Overflow, Position, RadialExtent,
+    RadialGradient, RadialShape, RepeatStyle, TransformFunction, Visibility,
+};
+use std::rc::Rc;
+
+#[cfg(test)]
+mod tests; // painting order
 
 impl crate::dom::Document {
     pub fn paint_onto(&self, rdev: &mut RenderDevice, user_css: Option<&str>, page_size: Size<CssPx>) {
@@ -25,16 +35,405 @@ impl crate::dom::Document {
             },
         };
 
+        let context = StackingContext::root(&fragments, &containing_block);
+        let mut painter = Painter {
+            rdev,
+            clips: vec![],
+        };
+        context.paint(&mut painter);
+        painter.pop_clips(0);
+    }
+}
+
+/// The clip rectangle of a box whose `overflow` is not `visible`, identified
+/// by the address of that box's fragment. Rounded corners of the box clip
+/// along `path`, which is empty otherwise.
+#[derive(Clone)]
+struct Clip {
+    id: usize,
+    rect: Rect<Length>,
+    path: Vec<Vec2<Length>>,
+}
+
+/// A fragment painted on its own: the background and borders of a box
+/// (but not its children), a text run, or an image.
+struct Item<'a> {
+    fragment: &'a Fragment,
+    /// The absolute position of the containing block of the fragment.
+    containing_block: Rect<Length>,
+    /// The clip rectangles of the ancestors of the fragment, outermost first.
+    clips: Rc<Vec<Clip>>,
+}
+
+impl Item<'_> {
+    /// The transform of the box of the item, if any, around its transform
+    /// origin. Percentages refer to the size of its border box.
+    /// https://drafts.csswg.org/css-transforms/#transform-rendering
+    fn transform(&self) -> Option<Transform2D<CssPx, CssPx>> {
+        let b = match self.fragment {
+            Fragment::Box(b) if !b.style.box_.transform.0.is_empty() => b,
+            _ => return None,
+        };
+        let border_rect = b
+            .border_rect()
+            .to_physical(b.style.writing_mode(), &self.containing_block)
+            .translate(&self.containing_block.top_left);
+        let size = &border_rect.size;
+        let origin = &b.style.box_.transform_origin;
+        let x = (border_rect.top_left.x + origin.x.resolve(size.x)).px;
+        let y = (border_rect.top_left.y + origin.y.resolve(size.y)).px;
+
+        // the functions apply from right to left
+        let mut transform: Transform2D<CssPx, CssPx> = Transform2D::identity();
+        for function in &b.style.box_.transform.0 {
+            let matrix = match *function {
+                TransformFunction::Matrix([a, b, c, d, e, f]) => Transform2D::row_major(a, b, c, d, e, f),
+                TransformFunction::Translate(tx, ty) => Transform2D::create_translation(
+                    tx.percentage_relative_to(size.x).px,
+                    ty.percentage_relative_to(size.y).px,
+                ),
+                TransformFunction::Scale(sx, sy) => Transform2D::create_scale(sx, sy),
+                TransformFunction::Rotate(angle) => {
+                    let (sin, cos) = angle.to_radians().sin_cos();
+                    Transform2D::row_major(cos, sin, -sin, cos, 0., 0.)
+                }
+                TransformFunction::Skew(ax, ay) => Transform2D::row_major(
+                    1.,
+                    ay.to_radians().tan(),
+                    ax.to_radians().tan(),
+                    1.,
+                    0.,
+                    0.,
+                ),
+            };
+            transform = matrix.post_transform(&transform);
+        }
+        Some(
+            Transform2D::create_translation(-x, -y)
+                .post_transform(&transform)
+                .post_translate(euclid::vec2(x, y)),
+        )
+    }
+}
+
+enum Paintable<'a> {
+    Item(Item<'a>),
+    Context(StackingContext<'a>),
+}
+
+/// The in-flow, non-positioned contents of a stacking context, in tree order.
+#[derive(Default)]
+struct Contents<'a> {
+    blocks: Vec<Item<'a>>,
+    floats: Vec<StackingContext<'a>>,
+    inlines: Vec<Paintable<'a>>,
+}
+
+/// The positioned descendants of a stacking context, in tree order.
+/// Those with `z-index: auto` are painted as if they established stacking
+/// contexts with a z-index of zero.
+#[derive(Default)]
+struct Stacked<'a> {
+    negative: Vec<StackingContext<'a>>,
+    zero: Vec<StackingContext<'a>>,
+    positive: Vec<StackingContext<'a>>,
+}
+
+/// A stacking context, or a box painted atomically as if it established one.
+/// The positioned descendants of the latter belong to the enclosing stacking
+/// context instead, so `stacked` is empty for them.
+/// https://drafts.csswg.org/css2/zindex.html
+struct StackingContext<'a> {
+    z_index: i32,
+    root: Option<Item<'a>>,
+    contents: Contents<'a>,
+    stacked: Stacked<'a>,
+}
+
+impl<'a> StackingContext<'a> {
+    fn new(root: Option<Item<'a>>, z_index: i32) -> Self {
+        StackingContext {
+            z_index,
+            root,
+            contents: Contents::default(),
+            stacked: Stacked::default(),
+        }
+    }
+
+    /// The root stacking context, of the fragments of a document.
+    fn root(fragments: &'a [Fragment], containing_block: &Rect<Length>) -> Self {
+        let mut context = StackingContext::new(None, 0);
+        let clips = Rc::new(vec![]);
         for fragment in fragments {
-            fragment.paint_onto(rdev, &containing_block)
+            fragment.collect(
+                containing_block,
+                &clips,
+                &mut context.contents,
+                &mut context.stacked,
+            )
+        }
+        context
+    }
+
+    /// https://drafts.csswg.org/css2/zindex.html#painting-order
+    fn paint(&self, painter: &mut Painter) {
+        let opacity = match &self.root {
+            Some(Item {
+                fragment: Fragment::Box(b),
+                ..
+            }) => b.style.box_.opacity.0,
+            _ => 1.,
+        };
+        if opacity < 1. {
+            painter.rdev.push_opacity(opacity);
+        }
+        // The clip rectangles of ancestors are not transformed, and those
+        // within the transformed box are popped with the transform.
+        let transformed = self.root.as_ref().and_then(|root| Some((root, root.transform()?)));
+        if let Some((root, transform)) = &transformed {
+            painter.push_clips(&root.clips);
+            painter.rdev.push_transform(transform);
+        }
+        self.paint_layers(painter);
+        if let Some((root, _)) = &transformed {
+            painter.pop_clips(root.clips.len());
+            painter.rdev.pop_transform();
+        }
+        if opacity < 1. {
+            painter.rdev.pop_opacity();
+        }
+    }
+
+    fn paint_layers(&self, painter: &mut Painter) {
+        self.for_each_layer(&mut |layer| match layer {
+            Layer::Item(item) => painter.paint(item),
+            Layer::Context(context) => context.paint(painter),
+            Layer::Outline(item) => painter.paint_outline(item),
+        })
+    }
+
+    /// Visits the root of the stacking context and its layers in painting order,
+    /// then the outlines of the boxes painted as items.
+    fn for_each_layer<'s>(&'s self, f: &mut dyn FnMut(Layer<'s, 'a>)) {
+        if let Some(root) = &self.root {
+            f(Layer::Item(root));
+        }
+        fn by_z_index<'c, 'a>(contexts: &'c [StackingContext<'a>]) -> Vec<&'c StackingContext<'a>> {
+            let mut contexts: Vec<_> = contexts.iter().collect();
+            // stable, so that tree order is preserved for equal z-indices
+            contexts.sort_by_key(|context| context.z_index);
+            contexts
+        }
+
+        for context in by_z_index(&self.stacked.negative) {
+            f(Layer::Context(context));
+        }
+        for item in &self.contents.blocks {
+            f(Layer::Item(item));
+        }
+        for context in &self.contents.floats {
+            f(Layer::Context(context));
+        }
+        for paintable in &self.contents.inlines {
+            match paintable {
+                Paintable::Item(item) => f(Layer::Item(item)),
+                Paintable::Context(context) => f(Layer::Context(context)),
+            }
+        }
+        for context in &self.stacked.zero {
+            f(Layer::Context(context));
+        }
+        for context in by_z_index(&self.stacked.positive) {
+            f(Layer::Context(context));
+        }
+
+        // Outlines go on top of everything else in the stacking context.
+        let inlines = self.contents.inlines.iter().filter_map(|paintable| match paintable {
+            Paintable::Item(item) => Some(item),
+            Paintable::Context(_) => None,
+        });
+        for item in self.root.iter().chain(&self.contents.blocks).chain(inlines) {
+            if let Fragment::Box(_) = item.fragment {
+                f(Layer::Outline(item));
+            }
+        }
+    }
+}
+
+/// An item, a nested stacking context, or the outline of an item,
+/// painted in turn by a stacking context.
+enum Layer<'s, 'a> {
+    Item(&'s Item<'a>),
+    Context(&'s StackingContext<'a>),
+    Outline(&'s Item<'a>),
+}
+
+impl<'a> Stacked<'a> {
+    fn push(&mut self, context: StackingContext<'a>) {
+        match context.z_index {
+            z if z < 0 => self.negative.push(context),
+            0 => self.zero.push(context),
+            _ => self.positive.push(context),
+        }
+    }
+}
+
+/// Paints items onto the device, pushing and popping their clip rectangles.
+struct Painter<'r> {
+    rdev: &'r mut RenderDevice,
+    /// The ids of the clip rectangles currently pushed onto the device.
+    clips: Vec<usize>,
+}
+
+impl Painter<'_> {
+    fn paint(&mut self, item: &Item) {
+        self.push_clips(&item.clips);
+        item.fragment.paint_onto(self.rdev, &item.containing_block)
+    }
+
+    fn paint_outline(&mut self, item: &Item) {
+        if let Fragment::Box(b) = item.fragment {
+            self.push_clips(&item.clips);
+            b.paint_outline(self.rdev, &item.containing_block)
+        }
+    }
+
+    /// Pushes the clip rectangles of an item, keeping those shared with
+    /// the previous item pushed.
+    fn push_clips(&mut self, clips: &[Clip]) {
+        let shared = self
+            .clips
+            .iter()
+            .zip(clips.iter())
+            .take_while(|(&id, clip)| id == clip.id)
+            .count();
+        self.pop_clips(shared);
+        for clip in &clips[shared..] {
+            self.rdev.push_clip(&clip.rect, &clip.path);
+            self.clips.push(clip.id);
+        }
+    }
+
+    fn pop_clips(&mut self, depth: usize) {
+        while self.clips.len() > depth {
+            self.rdev.pop_clip();
+            self.clips.pop();
         }
     }
 }
 
 impl Fragment {
-    fn paint_onto(&self, rdev: &mut RenderDevice, containing_block: &Rect<Length>) {
-        //println!("{:?}", self);
+    /// Sorts the fragment and its descendants into the layers
+    /// of the enclosing stacking context.
+    fn collect<'a>(
+        &'a self,
+        containing_block: &Rect<Length>,
+        clips: &Rc<Vec<Clip>>,
+        contents: &mut Contents<'a>,
+        stacked: &mut Stacked<'a>,
+    ) {
+        let item = Item {
+            fragment: self,
+            containing_block: containing_block.clone(),
+            clips: clips.clone(),
+        };
+        let b = match self {
+            Fragment::Box(b) => b,
+            Fragment::Anonymous(a) => {
+                let rect = a
+                    .rect
+                    .to_physical(a.mode, containing_block)
+                    .translate(&containing_block.top_left);
+                contents.blocks.push(item);
+                for child in &a.children {
+                    child.collect(&rect, clips, contents, stacked)
+                }
+                return;
+            }
+            Fragment::Text(_) | Fragment::Image(_) => {
+                contents.inlines.push(Paintable::Item(item));
+                return;
+            }
+        };
+
+        let style = &b.style;
+        let content_rect = b
+            .content_rect
+            .to_physical(style.writing_mode(), containing_block)
+            .translate(&containing_block.top_left);
+        // children are clipped to the padding box unless overflow is visible
+        // https://drafts.csswg.org/css-overflow-3/#overflow-control
+        let child_clips = if style.box_.overflow != Overflow::Visible {
+            let padding_rect = b
+                .padding_rect()
+                .to_physical(style.writing_mode(), containing_block)
+                .translate(&containing_block.top_left);
+            // the padding box follows the inner edge of rounded borders
+            let border_rect = b
+                .border_rect()
+                .to_physical(style.writing_mode(), containing_block)
+                .translate(&containing_block.top_left);
+            let radii = corner_radii(style, &border_rect);
+            let path = if radii.iter().any(|radius| radius.x > Length::zero()) {
+                let widths = border_widths(&border_rect, &padding_rect);
+                rounded_rect(&padding_rect, &inner_radii(&radii, &widths))
+            } else {
+                vec![]
+            };
+            let mut child_clips = (**clips).clone();
+            child_clips.push(Clip {
+                id: b as *const BoxFragment as usize,
+                rect: padding_rect,
+                path,
+            });
+            Rc::new(child_clips)
+        } else {
+            clips.clone()
+        };
+        let collect_children = |contents: &mut Contents<'a>, stacked: &mut Stacked<'a>| {
+            for child in &b.children {
+                child.collect(&content_rect, &child_clips, contents, stacked)
+            }
+        };
+
+        let (outside, inside) = match style.box_.display {
+            Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                outside, inside, ..
+            }) => (Some(outside), Some(inside)),
+            _ => (None, None),
+        };
+        if let Some(z_index) = style.stacking_context_z_index() {
+            let mut context = StackingContext::new(Some(item), z_index);
+            collect_children(&mut context.contents, &mut context.stacked);
+            stacked.push(context);
+        } else if style.box_.position != Position::Static {
+            // The box comes before its positioned descendants in tree order.
+            let index = stacked.zero.len();
+            stacked.zero.push(StackingContext::new(Some(item), 0));
+            let mut children = Contents::default();
+            collect_children(&mut children, stacked);
+            stacked.zero[index].contents = children;
+        } else if style.box_.float != Float::None {
+            let mut context = StackingContext::new(Some(item), 0);
+            collect_children(&mut context.contents, stacked);
+            contents.floats.push(context);
+        } else if outside == Some(DisplayOutside::Inline) && inside != Some(DisplayInside::Flow) {
+            // atomic inline-level boxes
+            let mut context = StackingContext::new(Some(item), 0);
+            collect_children(&mut context.contents, stacked);
+            contents.inlines.push(Paintable::Context(context));
+        } else if outside == Some(DisplayOutside::Inline) {
+            contents.inlines.push(Paintable::Item(item));
+            collect_children(contents, stacked);
+        } else {
+            contents.blocks.push(item);
+            collect_children(contents, stacked);
+        }
+    }
 
+    /// Paints the fragment itself. The children of boxes and anonymous
+    /// fragments are painted separately, in the order of their layers.
+    fn paint_onto(&self, rdev: &mut RenderDevice, containing_block: &Rect<Length>) {
         match self {
             Fragment::Box(b) => b.paint_onto(rdev, containing_block),
             Fragment::Anonymous(a) => {
@@ -44,10 +443,6 @@ impl Fragment {
                     .translate(&containing_block.top_left);
                 // record bounding box
                 rdev.record_bbox(&rect);
-                // draw children
-                for child in &a.children {
-                    child.paint_onto(rdev, &rect)
-                }
             }
             Fragment::Text(t) => {
                 let rect = t
@@ -58,11 +453,31 @@ impl Fragment {
 
                 // record bounding box
                 rdev.record_bbox(&rect);
+                if t.parent_style.inherited_box.visibility != Visibility::Visible {
+                    return;
+                }
 
                 // Distance from top edge to baseline
                 let ascender: Length = t.text.font.get_ascent().into();
                 origin.y += ascender;
 
+                // shadows, the first one on top
+                // https://drafts.csswg.org/css-text-decor/#text-shadow-property
+                for shadow in t.parent_style.text_inherited.text_shadow.0.iter().rev() {
+                    let color = t.parent_style.to_rgba(shadow.color);
+                    if color.alpha == 0 {
+                        continue;
+                    }
+                    rdev.draw_text_shadow(
+                        &t.text.glyphs,
+                        origin.x + shadow.offset_x,
+                        origin.y + shadow.offset_y,
+                        &t.text.font,
+                        color.into(),
+                        shadow.blur,
+                    );
+                }
+
                 rdev.draw_text(
                     &t.text.glyphs,
                     origin.x.into(),
@@ -71,6 +486,22 @@ impl Fragment {
                     t.parent_style.color.color.into()
                 );
             }
+            Fragment::Image(i) => {
+                let mode = i.style.writing_mode();
+                let rect = i
+                    .rect
+                    .to_physical(mode, containing_block)
+                    .translate(&containing_block.top_left);
+                let clip_rect = i
+                    .clip_rect
+                    .to_physical(mode, containing_block)
+                    .translate(&containing_block.top_left);
+                rdev.record_bbox(&clip_rect);
+                if i.style.inherited_box.visibility != Visibility::Visible {
+                    return;
+                }
+                rdev.draw_image(&i.image.path, i.image.format, &rect, &clip_rect);
+            }
         }
     }
 }
@@ -104,97 +535,929 @@ impl BoxFragment {
         // bounding box
         rdev.record_bbox(&margin_rect);
 
-        // background
-        let background_color = self.style.to_rgba(self.style.background.background_color);
-        if background_color.alpha > 0 {
-            rdev.draw_rect(
-                border_rect.top_left.x.into(),
-                border_rect.top_left.y.into(),
-                border_rect.size.x.into(),
-                border_rect.size.y.into(),
-                background_color.into(),
-            );
+        // the children of invisible boxes may still be visible
+        // https://drafts.csswg.org/css2/visufx.html#visibility
+        if self.style.inherited_box.visibility != Visibility::Visible {
+            return;
         }
 
+        let radii = corner_radii(&self.style, &border_rect);
+        let widths = border_widths(&border_rect, &padding_rect);
+
+        // outer shadows, below the background
+        self.paint_box_shadows(rdev, &border_rect, &widths, &radii, false);
+
+        // background
+        self.paint_background(rdev, &border_rect, &padding_rect, &content_rect, &radii);
+
+        // inset shadows, between the background and the borders
+        self.paint_box_shadows(rdev, &border_rect, &widths, &radii, true);
+
         // borders
-        // calculate widths by subtracting border rect from padding rect
-        // (Note: y goes down, so y values need to be subtracted the other way)
-        let border_top_width = padding_rect.top_left.y - border_rect.top_left.y;
-        let border_right_width = border_rect.top_left.x + border_rect.size.x
-            - padding_rect.top_left.x - padding_rect.size.x;
-        let border_bottom_width = border_rect.top_left.y + border_rect.size.y
-            - padding_rect.top_left.y - padding_rect.size.y;
-        let border_left_width = padding_rect.top_left.x - border_rect.top_left.x;
-
-        if border_top_width > Length::zero() &&
-            self.style.border.border_top_style != LineStyle::None {
-            let mut v: Vec<Vec2<Length>> = Vec::with_capacity(2);
-            let mut p1 = border_rect.top_left.clone();
-            // adjust midpoint by half linewidth
-            p1.y += border_top_width / 2.0;
-            let mut p2 = p1.clone();
-            p2.x += border_rect.size.x;
-            v.push(p1);
-            v.push(p2);
-            let border_color = self.style.to_rgba(self.style.border.border_top_color);
-            rdev.draw_line(
-                &v, border_color.into(), border_top_width, self.style.border.border_top_style
-            );
+        self.paint_borders(rdev, &border_rect, &widths, &radii);
+
+        // column rules, midway between adjacent columns
+        // https://drafts.csswg.org/css-multicol/#column-gaps-and-rules
+        let multicol = &self.style.multicol;
+        let rule_width = multicol.column_rule_width.0.percentage_relative_to(Length::zero());
+        if self.style.is_multicol_container()
+            && rule_width > Length::zero()
+            && multicol.column_rule_style != LineStyle::None
+        {
+            let rule_color = self.style.to_rgba(multicol.column_rule_color);
+            let columns: Vec<_> = self
+                .children
+                .iter()
+                .filter_map(|child| match child {
+                    Fragment::Anonymous(column) => Some(
+                        column
+                            .rect
+                            .to_physical(column.mode, &content_rect)
+                            .translate(&content_rect.top_left),
+                    ),
+                    _ => None,
+                })
+                .collect();
+            for pair in columns.windows(2) {
+                let mut p1 = content_rect.top_left.clone();
+                p1.x = (pair[0].top_left.x + pair[0].size.x + pair[1].top_left.x) / 2.0;
+                let mut p2 = p1.clone();
+                p2.y += content_rect.size.y;
+                rdev.draw_line(
+                    &vec![p1, p2], rule_color.into(), rule_width, multicol.column_rule_style
+                );
+            }
         }
+    }
 
-        if border_right_width > Length::zero() &&
-            self.style.border.border_right_style != LineStyle::None {
-            let mut v: Vec<Vec2<Length>> = Vec::with_capacity(2);
-            let mut p1 = border_rect.top_left.clone();
-            // adjust midpoint by half linewidth
-            p1.x += border_rect.size.x - border_right_width / 2.0;
-            let mut p2 = p1.clone();
-            p2.y += border_rect.size.y;
-            v.push(p1);
-            v.push(p2);
-            let border_color = self.style.to_rgba(self.style.border.border_right_color);
-            rdev.draw_line(
-                &v, border_color.into(), border_right_width, self.style.border.border_right_style
-            );
+    /// Paints the background color of a box within the painting area of the
+    /// bottom layer, then the background images from the bottom layer up.
+    /// https://drafts.csswg.org/css-backgrounds/#backgrounds
+    fn paint_background(
+        &self,
+        rdev: &mut RenderDevice,
+        border_rect: &Rect<Length>,
+        padding_rect: &Rect<Length>,
+        content_rect: &Rect<Length>,
+        radii: &[Vec2<Length>; 4],
+    ) {
+        let background = &self.style.background;
+        // a background box and the radii of its corners
+        let background_box = |which: BackgroundBox| match which {
+            BackgroundBox::BorderBox => (border_rect.clone(), radii.clone()),
+            BackgroundBox::PaddingBox => (
+                padding_rect.clone(),
+                inner_radii(radii, &border_widths(border_rect, padding_rect)),
+            ),
+            BackgroundBox::ContentBox => (
+                content_rect.clone(),
+                inner_radii(radii, &border_widths(border_rect, content_rect)),
+            ),
+        };
+        let layers = background.background_image.0.len();
+
+        let color = self.style.to_rgba(background.background_color);
+        if color.alpha > 0 {
+            let (rect, radii) = background_box(*background.background_clip.layer(layers - 1));
+            if radii.iter().any(|radius| radius.x > Length::zero()) {
+                rdev.draw_polygon(&rounded_rect(&rect, &radii), color.into());
+            } else {
+                rdev.draw_rect(
+                    rect.top_left.x,
+                    rect.top_left.y,
+                    rect.size.x,
+                    rect.size.y,
+                    color.into(),
+                );
+            }
         }
 
-        if border_bottom_width > Length::zero() &&
-            self.style.border.border_bottom_style != LineStyle::None {
-            let mut v: Vec<Vec2<Length>> = Vec::with_capacity(2);
-            let mut p1 = border_rect.top_left.clone();
-            // adjust midpoint by half linewidth
-            p1.y += border_rect.size.y - border_bottom_width / 2.0;
-            let mut p2 = p1.clone();
-            p2.x += border_rect.size.x;
-            v.push(p2);
-            v.push(p1);
-            let border_color = self.style.to_rgba(self.style.border.border_bottom_color);
-            rdev.draw_line(
-                &v, border_color.into(), border_bottom_width, self.style.border.border_bottom_style
+        for layer in (0..layers).rev() {
+            let image = &background.background_image.0[layer];
+            let (intrinsic, url_image) = match image {
+                ImageValue::None => continue,
+                ImageValue::Url(url) => match Image::load(url) {
+                    Some((image, intrinsic)) => (intrinsic, Some(image)),
+                    None => continue,
+                },
+                ImageValue::LinearGradient(_) | ImageValue::RadialGradient(_) => {
+                    let none = IntrinsicSizes { width: None, height: None, ratio: None };
+                    (none, None)
+                }
+            };
+            let (area, _) = background_box(*background.background_origin.layer(layer));
+            let (clip, clip_radii) = background_box(*background.background_clip.layer(layer));
+            let repeat = background.background_repeat.layer(layer);
+            let size = background_tile_size(
+                *background.background_size.layer(layer),
+                &intrinsic,
+                &area.size,
+                repeat,
+            );
+            if size.x <= Length::zero() || size.y <= Length::zero() {
+                continue;
+            }
+            let position = background.background_position.layer(layer);
+            let position = Vec2 {
+                x: area.top_left.x + position.x.resolve(area.size.x - size.x),
+                y: area.top_left.y + position.y.resolve(area.size.y - size.y),
+            };
+            let xs = background_tile_positions(
+                repeat.x, position.x, size.x, area.top_left.x, area.size.x, clip.top_left.x, clip.size.x,
+            );
+            let ys = background_tile_positions(
+                repeat.y, position.y, size.y, area.top_left.y, area.size.y, clip.top_left.y, clip.size.y,
             );
+
+            let rounded = clip_radii.iter().any(|radius| radius.x > Length::zero());
+            let path = if rounded { rounded_rect(&clip, &clip_radii) } else { vec![] };
+            rdev.push_clip(&clip, &path);
+            for &y in &ys {
+                for &x in &xs {
+                    let tile = Rect {
+                        top_left: Vec2 { x, y },
+                        size: size.clone(),
+                    };
+                    match (image, &url_image) {
+                        (_, Some(url_image)) => {
+                            rdev.draw_image(&url_image.path, url_image.format, &tile, &clip)
+                        }
+                        (ImageValue::LinearGradient(gradient), _) => {
+                            let (gradient, stops) = linear_gradient(&self.style, gradient, &tile);
+                            rdev.draw_gradient(&tile, &gradient, &stops);
+                        }
+                        (ImageValue::RadialGradient(gradient), _) => {
+                            let (gradient, stops) = radial_gradient(&self.style, gradient, &tile);
+                            rdev.draw_gradient(&tile, &gradient, &stops);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            rdev.pop_clip();
         }
+    }
 
-        if border_left_width > Length::zero() &&
-            self.style.border.border_left_style != LineStyle::None {
-            let mut v: Vec<Vec2<Length>> = Vec::with_capacity(2);
-            let mut p1 = border_rect.top_left.clone();
-            // adjust midpoint by half linewidth
-            p1.x += border_left_width / 2.0;
-            let mut p2 = p1.clone();
-            p2.y += border_rect.size.y;
-            v.push(p2);
-            v.push(p1);
-            let border_color = self.style.to_rgba(self.style.border.border_left_color);
-            rdev.draw_line(
-                &v, border_color.into(), border_left_width, self.style.border.border_left_style
-            );
+    /// Paints either the outer or the inset shadows of a box, the first
+    /// shadow on top. Outer shadows are the border box grown by the spread
+    /// distance, inset shadows fill the padding box outside of it shrunk by
+    /// the spread distance. Outer shadows are not clipped out of the border
+    /// box, which only shows with a translucent background.
+    /// https://drafts.csswg.org/css-backgrounds/#box-shadow
+    fn paint_box_shadows(
+        &self,
+        rdev: &mut RenderDevice,
+        border_rect: &Rect<Length>,
+        widths: &Sides<Length>,
+        radii: &[Vec2<Length>; 4],
+        inset: bool,
+    ) {
+        let shadows = &self.style.background.box_shadow.0;
+        if !shadows.iter().any(|shadow| shadow.inset == inset) {
+            return;
+        }
+        let (rect, radii) = if inset {
+            let padding_rect = Rect {
+                top_left: Vec2 {
+                    x: border_rect.top_left.x + widths.left,
+                    y: border_rect.top_left.y + widths.top,
+                },
+                size: Vec2 {
+                    x: border_rect.size.x - widths.left - widths.right,
+                    y: border_rect.size.y - widths.top - widths.bottom,
+                },
+            };
+            (padding_rect, inner_radii(radii, widths))
+        } else {
+            (border_rect.clone(), radii.clone())
+        };
+        if inset {
+            let rounded = radii.iter().any(|radius| radius.x > Length::zero());
+            let path = if rounded { rounded_rect(&rect, &radii) } else { vec![] };
+            rdev.push_clip(&rect, &path);
+        }
+
+        for shadow in shadows.iter().rev().filter(|shadow| shadow.inset == inset) {
+            let color = self.style.to_rgba(shadow.color);
+            if color.alpha == 0 {
+                continue;
+            }
+            // inset shadows spread inwards
+            let spread = if inset { -shadow.spread } else { shadow.spread };
+            let offset = Vec2 { x: shadow.offset_x, y: shadow.offset_y };
+            let shadow_rect = grow_rect(&rect, spread).translate(&offset);
+            // square corners stay square when spread
+            let shadow_radii = radii.clone().map(|radius| {
+                if radius.x > Length::zero() {
+                    grow_radius(&radius, spread)
+                } else {
+                    radius
+                }
+            });
+            let outline = |distance: Length| {
+                let grown = grow_rect(&shadow_rect, distance);
+                let grown_radii = shadow_radii.clone().map(|radius| {
+                    let radius = grow_radius(&radius, distance);
+                    Vec2 {
+                        x: radius.x.min(grown.size.x / 2.),
+                        y: radius.y.min(grown.size.y / 2.),
+                    }
+                });
+                rounded_rect(&grown, &grown_radii)
+            };
+            if inset {
+                // anything covering the padding box and the shadow's outline
+                let margin = Length {
+                    px: shadow.offset_x.px.abs() + shadow.offset_y.px.abs() + 1.,
+                };
+                let outer = grow_rect(&rect, margin + shadow.blur);
+                rdev.draw_shadow(outline, Some(&outer), color.into(), shadow.blur);
+            } else {
+                rdev.record_bbox(&grow_rect(&shadow_rect, shadow.blur));
+                rdev.draw_shadow(outline, None, color.into(), shadow.blur);
+            }
         }
 
-        // content
-        for child in &self.children {
-            child.paint_onto(rdev, &content_rect)
+        if inset {
+            rdev.pop_clip();
         }
     }
+
+    /// Paints the borders of a box. Each side is filled as a polygon between
+    /// the outer and inner edges of the border, with mitered joins at square
+    /// corners and ending midway around rounded ones. Dashed and dotted sides
+    /// are stroked along the middle of the border instead; with rounded
+    /// corners, this requires all sides to have the same width, style and
+    /// color, otherwise they are painted as solid.
+    /// https://drafts.csswg.org/css-backgrounds/#border-style
+    fn paint_borders(
+        &self,
+        rdev: &mut RenderDevice,
+        border_rect: &Rect<Length>,
+        widths: &Sides<Length>,
+        radii: &[Vec2<Length>; 4],
+    ) {
+        let border = &self.style.border;
+        // clockwise from the top, like the corners
+        let sides = [
+            (widths.top, border.border_top_style, self.style.to_rgba(border.border_top_color)),
+            (widths.right, border.border_right_style, self.style.to_rgba(border.border_right_color)),
+            (widths.bottom, border.border_bottom_style, self.style.to_rgba(border.border_bottom_color)),
+            (widths.left, border.border_left_style, self.style.to_rgba(border.border_left_color)),
+        ];
+        draw_border(rdev, border_rect, widths, radii, &sides);
+    }
+
+    /// Paints the outline of a box around its border box, as a border
+    /// of the same width on all sides, outset by `outline-offset`.
+    /// Outlines do not take up space, and follow rounded corners. They are
+    /// painted after everything else in the stacking context of the box.
+    /// https://drafts.csswg.org/css-ui/#outline-props
+    /// https://drafts.csswg.org/css2/zindex.html#painting-order
+    fn paint_outline(&self, rdev: &mut RenderDevice, containing_block: &Rect<Length>) {
+        let outline = &self.style.outline;
+        let width = outline.outline_width.0.percentage_relative_to(Length::zero());
+        let color = self.style.to_rgba(outline.outline_color);
+        if width <= Length::zero()
+            || color.alpha == 0
+            || self.style.inherited_box.visibility != Visibility::Visible
+        {
+            return;
+        }
+        let border_rect = &self
+            .border_rect()
+            .to_physical(self.style.writing_mode(), containing_block)
+            .translate(&containing_block.top_left);
+        let radii = &corner_radii(&self.style, border_rect);
+        let distance = outline.outline_offset + width;
+        let outline_rect = grow_rect(border_rect, distance);
+        let outline_radii = radii.clone().map(|radius| {
+            if radius.x > Length::zero() {
+                grow_radius(&radius, distance)
+            } else {
+                radius
+            }
+        });
+        let widths = Sides {
+            top: width,
+            left: width,
+            bottom: width,
+            right: width,
+        };
+        let side = (width, outline.outline_style, color);
+        draw_border(rdev, &outline_rect, &widths, &outline_radii, &[side; 4]);
+    }
+}
+
+/// Draws a border inside `border_rect`, with the given width, style and color
+/// of each side, clockwise from the top.
+fn draw_border(
+    rdev: &mut RenderDevice,
+    border_rect: &Rect<Length>,
+    widths: &Sides<Length>,
+    radii: &[Vec2<Length>; 4],
+    sides: &[(Length, LineStyle, cssparser::RGBA); 4],
+) {
+    let rounded = radii.iter().any(|radius| radius.x > Length::zero());
+
+    let (width, style, color) = sides[0];
+    if rounded
+        && matches!(style, LineStyle::Dashed | LineStyle::Dotted)
+        && width > Length::zero()
+        && sides.iter().all(|side| *side == (width, style, color))
+    {
+        let half = width / 2.;
+        let middle = Rect {
+            top_left: Vec2 {
+                x: border_rect.top_left.x + half,
+                y: border_rect.top_left.y + half,
+            },
+            size: Vec2 {
+                x: border_rect.size.x - width,
+                y: border_rect.size.y - width,
+            },
+        };
+        let middle_radii = scale_overlapping_radii(radii, &border_rect.size).map(|radius| Vec2 {
+            x: (radius.x - half).max(Length::zero()),
+            y: (radius.y - half).max(Length::zero()),
+        });
+        // Radii clamped at zero can leave the others too large for the middle line.
+        let middle_radii = scale_overlapping_radii(&middle_radii, &middle.size);
+        let mut points = rounded_rect(&middle, &middle_radii);
+        points.push(points[0].clone());
+        rdev.draw_line(&points, color.into(), width, style);
+        return;
+    }
+
+    for (side, &(width, style, color)) in sides.iter().enumerate() {
+        if width <= Length::zero() || color.alpha == 0 {
+            continue;
+        }
+        // The 3D styles are lit from the top left.
+        let lit = side == 0 || side == 3;
+        let (dark, light) = shades(color);
+        // bands of the border, as fractions of its width from the outer edge
+        let bands = match style {
+            LineStyle::None | LineStyle::Hidden => vec![],
+            LineStyle::Dashed | LineStyle::Dotted if !rounded => {
+                let points = border_middle_line(border_rect, widths, side);
+                rdev.draw_line(&points, color.into(), width, style);
+                vec![]
+            }
+            // double borders need at least 1px per line and for the gap
+            LineStyle::Double if width.px >= 3. => {
+                vec![(0., 1. / 3., color), (2. / 3., 1., color)]
+            }
+            LineStyle::Groove | LineStyle::Ridge => {
+                if (style == LineStyle::Groove) == lit {
+                    vec![(0., 0.5, dark), (0.5, 1., light)]
+                } else {
+                    vec![(0., 0.5, light), (0.5, 1., dark)]
+                }
+            }
+            LineStyle::Inset | LineStyle::Outset => {
+                if (style == LineStyle::Inset) == lit {
+                    vec![(0., 1., dark)]
+                } else {
+                    vec![(0., 1., light)]
+                }
+            }
+            _ => vec![(0., 1., color)],
+        };
+        for (from, to, color) in bands {
+            let points = border_band(border_rect, widths, radii, side, from, to);
+            rdev.draw_polygon(&points, color.into());
+        }
+    }
+}
+
+/// The darker and lighter shades of a border color, for the 3D border styles.
+fn shades(color: cssparser::RGBA) -> (cssparser::RGBA, cssparser::RGBA) {
+    let dark = |channel: u8| (channel as f32 * 2. / 3.) as u8;
+    let light = |channel: u8| channel + (255 - channel) / 3;
+    (
+        cssparser::RGBA::new(dark(color.red), dark(color.green), dark(color.blue), color.alpha),
+        cssparser::RGBA::new(light(color.red), light(color.green), light(color.blue), color.alpha),
+    )
+}
+
+/// The line along the middle of one side of a border, clockwise from the top
+/// side, spanning the whole border box.
+fn border_middle_line(border_rect: &Rect<Length>, widths: &Sides<Length>, side: usize) -> Vec<Vec2<Length>> {
+    let left = border_rect.top_left.x;
+    let top = border_rect.top_left.y;
+    let right = left + border_rect.size.x;
+    let bottom = top + border_rect.size.y;
+    let point = |x, y| Vec2 { x, y };
+    match side {
+        0 => vec![point(left, top + widths.top / 2.), point(right, top + widths.top / 2.)],
+        1 => vec![point(right - widths.right / 2., top), point(right - widths.right / 2., bottom)],
+        2 => vec![point(right, bottom - widths.bottom / 2.), point(left, bottom - widths.bottom / 2.)],
+        _ => vec![point(left + widths.left / 2., bottom), point(left + widths.left / 2., top)],
+    }
+}
+
+/// The polygon covering one side of a border, clockwise from the top side,
+/// between the fractions `from` and `to` of the border widths from its outer
+/// edge. At rounded corners, the side ends midway around the corner.
+fn border_band(
+    border_rect: &Rect<Length>,
+    widths: &Sides<Length>,
+    radii: &[Vec2<Length>; 4],
+    side: usize,
+    from: f32,
+    to: f32,
+) -> Vec<Vec2<Length>> {
+    // the corners of the edge at the given fraction of the border widths
+    let edge = |fraction: f32| {
+        let widths = Sides {
+            top: widths.top * fraction,
+            left: widths.left * fraction,
+            bottom: widths.bottom * fraction,
+            right: widths.right * fraction,
+        };
+        let rect = Rect {
+            top_left: Vec2 {
+                x: border_rect.top_left.x + widths.left,
+                y: border_rect.top_left.y + widths.top,
+            },
+            size: Vec2 {
+                x: border_rect.size.x - widths.left - widths.right,
+                y: border_rect.size.y - widths.top - widths.bottom,
+            },
+        };
+        corner_arcs(&rect, &inner_radii(radii, &widths))
+    };
+    let (outer, inner) = (edge(from), edge(to));
+    let middle = ARC_SEGMENTS / 2;
+    let (start, end) = (side, (side + 1) % 4);
+    let mut points: Vec<Vec2<Length>> = vec![];
+    points.extend_from_slice(&outer[start][middle..]);
+    points.extend_from_slice(&outer[end][..=middle]);
+    points.extend(inner[end][..=middle].iter().rev().cloned());
+    points.extend(inner[start][middle..].iter().rev().cloned());
+    // square corners repeat the same point
+    points.dedup_by(|a, b| a.x == b.x && a.y == b.y);
+    points
+}
+
+/// The border widths of a box, computed by subtracting its padding rect
+/// from its border rect.
+fn border_widths(border_rect: &Rect<Length>, padding_rect: &Rect<Length>) -> Sides<Length> {
+    // (Note: y goes down, so y values need to be subtracted the other way)
+    Sides {
+        top: padding_rect.top_left.y - border_rect.top_left.y,
+        right: border_rect.top_left.x + border_rect.size.x
+            - padding_rect.top_left.x - padding_rect.size.x,
+        bottom: border_rect.top_left.y + border_rect.size.y
+            - padding_rect.top_left.y - padding_rect.size.y,
+        left: padding_rect.top_left.x - border_rect.top_left.x,
+    }
+}
+
+/// The size of the tiles of a background image, from its `background-size`
+/// and intrinsic dimensions, in a background positioning area of size
+/// `area`. Images without intrinsic dimensions, such as gradients, fill the
+/// area by default.
+/// https://drafts.csswg.org/css-backgrounds/#background-size
+fn background_tile_size(
+    size: BackgroundSize,
+    intrinsic: &IntrinsicSizes,
+    area: &Vec2<Length>,
+    repeat: &BackgroundRepeat,
+) -> Vec2<Length> {
+    let ratio = intrinsic.ratio.filter(|ratio| *ratio > 0.);
+    let contain = |cover: bool| match ratio {
+        Some(ratio) => {
+            let width = if cover {
+                area.x.max(area.y * ratio)
+            } else {
+                area.x.min(area.y * ratio)
+            };
+            Vec2 { x: width, y: width / ratio }
+        }
+        None => area.clone(),
+    };
+    let (width, height) = match size {
+        BackgroundSize::Cover => return contain(true),
+        BackgroundSize::Contain => return contain(false),
+        BackgroundSize::Explicit(width, height) => (
+            width.percentage_relative_to(area.x),
+            height.percentage_relative_to(area.y),
+        ),
+    };
+    let mut tile = match (width, height) {
+        (LengthOrAuto::Length(x), LengthOrAuto::Length(y)) => Vec2 { x, y },
+        (LengthOrAuto::Length(x), LengthOrAuto::Auto) => Vec2 {
+            x,
+            y: match (ratio, intrinsic.height) {
+                (Some(ratio), _) => x / ratio,
+                (None, Some(height)) => height,
+                (None, None) => area.y,
+            },
+        },
+        (LengthOrAuto::Auto, LengthOrAuto::Length(y)) => Vec2 {
+            x: match (ratio, intrinsic.width) {
+                (Some(ratio), _) => y * ratio,
+                (None, Some(width)) => width,
+                (None, None) => area.x,
+            },
+            y,
+        },
+        (LengthOrAuto::Auto, LengthOrAuto::Auto) => match (intrinsic.width, intrinsic.height, ratio) {
+            (Some(x), Some(y), _) => Vec2 { x, y },
+            (Some(x), None, Some(ratio)) => Vec2 { x, y: x / ratio },
+            (None, Some(y), Some(ratio)) => Vec2 { x: y * ratio, y },
+            (None, None, Some(_)) => contain(false),
+            (x, y, _) => Vec2 {
+                x: x.unwrap_or(area.x),
+                y: y.unwrap_or(area.y),
+            },
+        },
+    };
+
+    // `round` scales the tiles to fit a whole number of times, and restores
+    // the aspect ratio if the other dimension is `auto`.
+    let round = |tile: Length, area: Length| {
+        let count = (area.px / tile.px).round().max(1.);
+        area / count
+    };
+    let auto_width = matches!(width, LengthOrAuto::Auto);
+    let auto_height = matches!(height, LengthOrAuto::Auto);
+    if repeat.x == RepeatStyle::Round && tile.x > Length::zero() {
+        let x = round(tile.x, area.x);
+        if repeat.y != RepeatStyle::Round && auto_height {
+            tile.y = tile.y * (x.px / tile.x.px);
+        }
+        tile.x = x;
+    }
+    if repeat.y == RepeatStyle::Round && tile.y > Length::zero() {
+        let y = round(tile.y, area.y);
+        if repeat.x != RepeatStyle::Round && auto_width {
+            tile.x = tile.x * (y.px / tile.y.px);
+        }
+        tile.y = y;
+    }
+    tile
+}
+
+/// The positions of the tiles of a background image along one axis that
+/// intersect the clip area, given the position of one tile, the size of
+/// tiles, and the extent of the positioning and clip areas.
+/// https://drafts.csswg.org/css-backgrounds/#background-repeat
+fn background_tile_positions(
+    repeat: RepeatStyle,
+    position: Length,
+    size: Length,
+    area_start: Length,
+    area_size: Length,
+    clip_start: Length,
+    clip_size: Length,
+) -> Vec<Length> {
+    // `space` spreads the tiles that fit over the positioning area,
+    // the first and last touching its edges.
+    let spaced = (area_size.px / size.px).floor();
+    let (first, step) = match repeat {
+        RepeatStyle::NoRepeat => return vec![position],
+        RepeatStyle::Space if spaced <= 1. => return vec![position],
+        RepeatStyle::Space => (
+            area_start,
+            size + (area_size - size * spaced) / (spaced - 1.),
+        ),
+        RepeatStyle::Repeat | RepeatStyle::Round => (position, size),
+    };
+    // back up from the first tile to the start of the clip area
+    let before = ((first - clip_start).px / step.px).ceil().max(0.);
+    let mut tile = first - step * before;
+    let clip_end = clip_start + clip_size;
+    let mut positions = vec![];
+    while tile < clip_end && positions.len() < MAX_TILES {
+        positions.push(tile);
+        tile += step;
+    }
+    positions
+}
+
+/// The maximum number of tiles of a background image along one axis.
+const MAX_TILES: usize = 1000;
+
+/// The geometry and color stops of a linear gradient filling `rect`.
+/// https://drafts.csswg.org/css-images/#linear-gradients
+fn linear_gradient(
+    style: &ComputedValues,
+    gradient: &LinearGradient,
+    rect: &Rect<Length>,
+) -> (Gradient, Vec<(f32, RGBA)>) {
+    let size = &rect.size;
+    // the unit vector of the gradient line, with y pointing down
+    let (x, y) = match gradient.direction {
+        GradientDirection::Angle(degrees) => {
+            let angle = degrees.to_radians();
+            (angle.sin(), -angle.cos())
+        }
+        // perpendicular to the diagonal between the two other corners
+        GradientDirection::Corner { right, bottom } => {
+            let x = if right { size.y.px } else { -size.y.px };
+            let y = if bottom { size.x.px } else { -size.x.px };
+            let length = x.hypot(y);
+            if length > 0. {
+                (x / length, y / length)
+            } else {
+                (0., 1.)
+            }
+        }
+    };
+    let length = size.x * x.abs() + size.y * y.abs();
+    let center = Vec2 {
+        x: rect.top_left.x + size.x / 2.,
+        y: rect.top_left.y + size.y / 2.,
+    };
+    let half = Vec2 { x: length * (x / 2.), y: length * (y / 2.) };
+    let start = Vec2 { x: center.x - half.x, y: center.y - half.y };
+    let end = Vec2 { x: center.x + half.x, y: center.y + half.y };
+
+    // move the ends of the line to the first and last stops
+    let stops = color_stops(style, &gradient.stops, length);
+    let (first, last) = (stops[0].0, stops[stops.len() - 1].0);
+    if last - first <= f32::EPSILON {
+        return solid_gradient(start, end, &stops);
+    }
+    let along = |offset: f32| Vec2 {
+        x: start.x + (end.x - start.x) * offset,
+        y: start.y + (end.y - start.y) * offset,
+    };
+    let gradient = Gradient::Linear {
+        start: along(first),
+        end: along(last),
+    };
+    let stops = stops
+        .iter()
+        .map(|&(offset, color)| ((offset - first) / (last - first), color))
+        .collect();
+    (gradient, stops)
+}
+
+/// The geometry and color stops of a radial gradient filling `rect`.
+/// https://drafts.csswg.org/css-images/#radial-gradients
+fn radial_gradient(
+    style: &ComputedValues,
+    gradient: &RadialGradient,
+    rect: &Rect<Length>,
+) -> (Gradient, Vec<(f32, RGBA)>) {
+    let size = &rect.size;
+    let center = Vec2 {
+        x: gradient.center.x.resolve(size.x),
+        y: gradient.center.y.resolve(size.y),
+    };
+    // distances from the center to the closest and farthest sides
+    let closest = Vec2 {
+        x: center.x.min(size.x - center.x).max(Length::zero()),
+        y: center.y.min(size.y - center.y).max(Length::zero()),
+    };
+    let farthest = Vec2 {
+        x: center.x.max(size.x - center.x),
+        y: center.y.max(size.y - center.y),
+    };
+    let circle = gradient.shape == RadialShape::Circle;
+    let radii = match gradient.extent {
+        RadialExtent::Radii(x, y) => Vec2 {
+            x: x.percentage_relative_to(size.x),
+            y: y.percentage_relative_to(size.y),
+        },
+        RadialExtent::ClosestSide if circle => {
+            let radius = closest.x.min(closest.y);
+            Vec2 { x: radius, y: radius }
+        }
+        RadialExtent::FarthestSide if circle => {
+            let radius = farthest.x.max(farthest.y);
+            Vec2 { x: radius, y: radius }
+        }
+        RadialExtent::ClosestSide => closest.clone(),
+        RadialExtent::FarthestSide => farthest.clone(),
+        RadialExtent::ClosestCorner | RadialExtent::FarthestCorner => {
+            let (side, corner) = if let RadialExtent::ClosestCorner = gradient.extent {
+                (&closest, &closest)
+            } else {
+                (&farthest, &farthest)
+            };
+            if circle {
+                let radius = Length { px: corner.x.px.hypot(corner.y.px) };
+                Vec2 { x: radius, y: radius }
+            } else if side.x > Length::zero() && side.y > Length::zero() {
+                // the ellipse through the corner with the aspect ratio
+                // of the one touching the sides
+                let ratio = side.y.px / side.x.px;
+                let x = Length { px: corner.x.px.hypot(corner.y.px / ratio) };
+                Vec2 { x, y: x * ratio }
+            } else {
+                Vec2 { x: Length::zero(), y: Length::zero() }
+            }
+        }
+    };
+    let center = Vec2 {
+        x: rect.top_left.x + center.x,
+        y: rect.top_left.y + center.y,
+    };
+
+    // the device gradient starts at the center and ends at the last stop
+    let mut stops = color_stops(style, &gradient.stops, radii.x);
+    if stops[0].0 < 0. {
+        let color = color_at(&stops, 0.);
+        stops.retain(|&(offset, _)| offset > 0.);
+        stops.insert(0, (0., color));
+    }
+    let last = stops[stops.len() - 1].0;
+    if last <= f32::EPSILON || radii.x <= Length::zero() || radii.y <= Length::zero() {
+        return solid_gradient(center.clone(), center, &stops);
+    }
+    let gradient = Gradient::Radial {
+        center,
+        radii: Vec2 { x: radii.x * last, y: radii.y * last },
+    };
+    let stops = stops.iter().map(|&(offset, color)| (offset / last, color)).collect();
+    (gradient, stops)
+}
+
+/// A degenerate gradient, filled with the color of its last stop.
+fn solid_gradient(
+    start: Vec2<Length>,
+    end: Vec2<Length>,
+    stops: &[(f32, RGBA)],
+) -> (Gradient, Vec<(f32, RGBA)>) {
+    let color = stops[stops.len() - 1].1;
+    (Gradient::Linear { start, end }, vec![(0., color), (1., color)])
+}
+
+/// The offsets of color stops along a gradient of the given length, as
+/// fractions of it, and their colors. The first and last stops default to
+/// the ends of the gradient, stops are not before earlier ones, and those
+/// without a position are spaced evenly between their neighbors.
+/// https://drafts.csswg.org/css-images/#color-stop-fixup
+fn color_stops(style: &ComputedValues, stops: &[ColorStop], length: Length) -> Vec<(f32, RGBA)> {
+    let mut offsets: Vec<Option<f32>> = stops
+        .iter()
+        .map(|stop| {
+            stop.position.map(|position| match position {
+                LengthOrPercentage::Percentage(percentage) => percentage.unit_value,
+                LengthOrPercentage::Length(_) if length.px <= 0. => 0.,
+                LengthOrPercentage::Length(position) => position.px / length.px,
+            })
+        })
+        .collect();
+    let last = offsets.len() - 1;
+    offsets[0].get_or_insert(0.);
+    offsets[last].get_or_insert(1.);
+    let mut max = f32::NEG_INFINITY;
+    for offset in offsets.iter_mut().flatten() {
+        max = max.max(*offset);
+        *offset = max;
+    }
+    let mut resolved = Vec::with_capacity(offsets.len());
+    let mut previous = 0;
+    for (i, offset) in offsets.iter().enumerate() {
+        if let Some(offset) = *offset {
+            // spread the stops since the previous positioned one
+            let start: f32 = resolved.get(previous).copied().unwrap_or(offset);
+            let count = (i - previous) as f32;
+            for j in resolved.len()..i {
+                resolved.push(start + (offset - start) * (j - previous) as f32 / count);
+            }
+            resolved.push(offset);
+            previous = i;
+        }
+    }
+    resolved
+        .into_iter()
+        .zip(stops)
+        .map(|(offset, stop)| (offset, style.to_rgba(stop.color).into()))
+        .collect()
+}
+
+/// A rectangle grown by `distance` on all sides, or shrunk if it is
+/// negative, to at most an empty rectangle at its center.
+fn grow_rect(rect: &Rect<Length>, distance: Length) -> Rect<Length> {
+    let distance = distance.max(-rect.size.x.min(rect.size.y) / 2.);
+    Rect {
+        top_left: Vec2 {
+            x: rect.top_left.x - distance,
+            y: rect.top_left.y - distance,
+        },
+        size: Vec2 {
+            x: rect.size.x + distance * 2.,
+            y: rect.size.y + distance * 2.,
+        },
+    }
+}
+
+/// The radius of a corner of a rectangle grown by `distance`.
+fn grow_radius(radius: &Vec2<Length>, distance: Length) -> Vec2<Length> {
+    Vec2 {
+        x: (radius.x + distance).max(Length::zero()),
+        y: (radius.y + distance).max(Length::zero()),
+    }
+}
+
+/// The number of line segments approximating the arc of a rounded corner.
+const ARC_SEGMENTS: usize = 8;
+
+/// The used radii of the corners of a box, clockwise from the top left corner.
+/// Corners with a zero radius are square; radii are scaled down so that the
+/// curves of adjacent corners do not overlap.
+/// https://drafts.csswg.org/css-backgrounds/#corner-overlap
+fn corner_radii(style: &ComputedValues, border_rect: &Rect<Length>) -> [Vec2<Length>; 4] {
+    let border = &style.border;
+    let size = &border_rect.size;
+    let resolve = |radius: BorderCornerRadius| {
+        let x = radius.horizontal.percentage_relative_to(size.x);
+        let y = radius.vertical.percentage_relative_to(size.y);
+        if x > Length::zero() && y > Length::zero() {
+            Vec2 { x, y }
+        } else {
+            Vec2 { x: Length::zero(), y: Length::zero() }
+        }
+    };
+    let radii = [
+        resolve(border.border_top_left_radius),
+        resolve(border.border_top_right_radius),
+        resolve(border.border_bottom_right_radius),
+        resolve(border.border_bottom_left_radius),
+    ];
+    scale_overlapping_radii(&radii, size)
+}
+
+/// Scales radii down by the same factor so that the curves of adjacent
+/// corners of a rectangle of the given size do not overlap.
+/// https://drafts.csswg.org/css-backgrounds/#corner-overlap
+fn scale_overlapping_radii(radii: &[Vec2<Length>; 4], size: &Vec2<Length>) -> [Vec2<Length>; 4] {
+    let ratio = |available: Length, a: Length, b: Length| {
+        if a + b > available {
+            available.px / (a + b).px
+        } else {
+            1.
+        }
+    };
+    let factor = [
+        ratio(size.x, radii[0].x, radii[1].x),
+        ratio(size.x, radii[3].x, radii[2].x),
+        ratio(size.y, radii[0].y, radii[3].y),
+        ratio(size.y, radii[1].y, radii[2].y),
+    ]
+    .iter()
+    .fold(1., |factor: f32, &ratio| factor.min(ratio));
+    radii.clone().map(|radius| Vec2 {
+        x: radius.x * factor,
+        y: radius.y * factor,
+    })
+}
+
+/// The corner radii of the inner edge of a border.
+fn inner_radii(radii: &[Vec2<Length>; 4], widths: &Sides<Length>) -> [Vec2<Length>; 4] {
+    let inner = |radius: &Vec2<Length>, horizontal: Length, vertical: Length| Vec2 {
+        x: (radius.x - horizontal).max(Length::zero()),
+        y: (radius.y - vertical).max(Length::zero()),
+    };
+    [
+        inner(&radii[0], widths.left, widths.top),
+        inner(&radii[1], widths.right, widths.top),
+        inner(&radii[2], widths.right, widths.bottom),
+        inner(&radii[3], widths.left, widths.bottom),
+    ]
+}
+
+/// Points along the arcs of the four corners of a rectangle, each clockwise
+/// and with `ARC_SEGMENTS + 1` points. Square corners repeat the same point.
+fn corner_arcs(rect: &Rect<Length>, radii: &[Vec2<Length>; 4]) -> [Vec<Vec2<Length>>; 4] {
+    let left = rect.top_left.x;
+    let top = rect.top_left.y;
+    let right = left + rect.size.x;
+    let bottom = top + rect.size.y;
+    // the center of each corner's ellipse, and the angle at which its arc
+    // starts, with y pointing down
+    let centers = [
+        (left + radii[0].x, top + radii[0].y, 180.),
+        (right - radii[1].x, top + radii[1].y, 270.),
+        (right - radii[2].x, bottom - radii[2].y, 0.),
+        (left + radii[3].x, bottom - radii[3].y, 90.),
+    ];
+    let mut arcs: [Vec<Vec2<Length>>; 4] = Default::default();
+    for (corner, &(x, y, start)) in centers.iter().enumerate() {
+        arcs[corner] = (0..=ARC_SEGMENTS)
+            .map(|i| {
+                let angle = (start + 90. * i as f32 / ARC_SEGMENTS as f32).to_radians();
+                Vec2 {
+                    x: x + radii[corner].x * angle.cos(),
+                    y: y + radii[corner].y * angle.sin(),
+                }
+            })
+            .collect();
+    }
+    arcs
+}
+
+/// The outline of a rectangle with rounded corners, clockwise from the top
+/// left corner.
+fn rounded_rect(rect: &Rect<Length>, radii: &[Vec2<Length>; 4]) -> Vec<Vec2<Length>> {
+    corner_arcs(rect, radii).concat()
 }
 
 
@@ -207,6 +1470,13 @@ pub fn render_html(text_input: &str, css_input: &str, mut rdev: RenderDevice, pa
     document.paint_onto(&mut rdev, Some(css_input), page_size);
 }
 
+/// The min-content and max-content widths of the rendered html, in px.
+pub fn html_content_sizes(text_input: &str, css_input: &str) -> (f32, f32) {
+    let document = Document::parse_html(text_input.as_bytes());
+    let sizes = document.inline_content_sizes(Some(css_input));
+    (sizes.min_content.px, sizes.max_content.px)
+}
+
 
 // debug code for testing
 use crate::dom::*;
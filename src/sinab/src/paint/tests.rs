@@ -0,0 +1,109 @@
+use super::*;
+
+/// Lays out `html` in a 400px wide viewport and returns the border boxes of
+/// the box fragments, including those of the root and body elements, in
+/// painting order, as `[x, y, width, height]` in CSS pixels. Boxes with an
+/// outline come once more when it is painted.
+fn paint_order(html: &str) -> Vec<[f32; 4]> {
+    let document = Document::parse_html(html.as_bytes());
+    let page_size = Size::new(400., 400.);
+    let fragments = document.layout(page_size, Some("body { margin: 0; font-size: 0 }"));
+    let containing_block = Rect {
+        top_left: Vec2 {
+            x: Length::zero(),
+            y: Length::zero(),
+        },
+        size: Vec2 {
+            x: Length { px: 400. },
+            y: Length { px: 400. },
+        },
+    };
+    let mut boxes = vec![];
+    collect_boxes(&StackingContext::root(&fragments, &containing_block), &mut boxes);
+    boxes
+}
+
+fn collect_boxes(context: &StackingContext, boxes: &mut Vec<[f32; 4]>) {
+    context.for_each_layer(&mut |layer| {
+        let item = match layer {
+            Layer::Item(item) => item,
+            Layer::Outline(item) => match item.fragment {
+                Fragment::Box(b) if b.style.outline.outline_style != LineStyle::None => item,
+                _ => return,
+            },
+            Layer::Context(context) => return collect_boxes(context, boxes),
+        };
+        if let Fragment::Box(b) = item.fragment {
+            let containing_block = &item.containing_block;
+            let rect = b
+                .border_rect()
+                .to_physical(b.style.writing_mode(), containing_block)
+                .translate(&containing_block.top_left);
+            boxes.push([
+                rect.top_left.x.px,
+                rect.top_left.y.px,
+                rect.size.x.px,
+                rect.size.y.px,
+            ])
+        }
+    })
+}
+
+#[test]
+fn positioned_boxes_paint_in_tree_order() {
+    let boxes = paint_order(
+        r#"<div style="position: relative; padding: 5px">
+            <div style="position: relative; height: 10px">
+                <div style="position: relative; height: 4px"></div>
+            </div>
+        </div>
+        <div style="height: 10px"></div>
+        <div style="position: relative; z-index: -1; height: 3px"></div>
+        <div style="float: left; width: 5px; height: 5px"></div>"#,
+    );
+    assert_eq!(
+        boxes,
+        [
+            // negative z-indices first
+            [0., 30., 400., 3.],
+            // then in-flow blocks, including the root and body elements, and floats
+            [0., 0., 400., 38.],
+            [0., 0., 400., 33.],
+            [0., 20., 400., 10.],
+            [0., 33., 5., 5.],
+            // positioned boxes come after, ancestors before their descendants
+            [0., 0., 400., 20.],
+            [5., 5., 390., 10.],
+            [5., 5., 390., 4.],
+        ]
+    );
+}
+
+#[test]
+fn outlines_paint_last_in_their_stacking_context() {
+    let boxes = paint_order(
+        r#"<div style="outline: 2px solid; height: 10px"></div>
+        <div style="margin-top: -5px; height: 10px"></div>
+        <div style="position: relative; z-index: 1; outline: 1px solid; height: 5px">
+            <div style="outline: 1px solid; height: 3px"></div>
+            <div style="position: relative; height: 2px"></div>
+        </div>"#,
+    );
+    assert_eq!(
+        boxes,
+        [
+            [0., 0., 400., 20.],
+            [0., 0., 400., 20.],
+            [0., 0., 400., 10.],
+            [0., 5., 400., 10.],
+            // the stacking context paints its positioned descendant before its outlines
+            [0., 15., 400., 5.],
+            [0., 15., 400., 3.],
+            [0., 18., 400., 2.],
+            [0., 15., 400., 5.],
+            [0., 15., 400., 3.],
+            // the outline of the first box is painted over everything else
+            [0., 0., 400., 10.],
+        ]
+    );
+}
@@ -10,6 +10,7 @@ pub type Size<U> = euclid::Size2D<f32, U>;
 pub type Rect<U> = euclid::Rect<f32, U>;
 pub type SideOffsets<U> = euclid::SideOffsets2D<f32, U>;
 pub type Scale<Src, Dest> = euclid::Scale<f32, Src, Dest>;
+pub type Transform2D<Src, Dest> = euclid::Transform2D<f32, Src, Dest>;
 
 #[derive(Copy, Clone, PartialEq)]
 pub struct RGBA(pub u8, pub u8, pub u8, pub u8);
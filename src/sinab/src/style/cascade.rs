@@ -2,7 +2,7 @@ use crate::dom;
 use crate::style::declaration_block::DeclarationBlock;
 use crate::style::properties::{ComputedValues, Phase};
 use crate::style::rules::{CssRule, RulesParser};
-use crate::style::selectors::{self, Selector};
+use crate::style::selectors::{self, PseudoElement, Selector};
 use cssparser::{Parser, ParserInput, RuleListParser};
 use smallvec::SmallVec;
 use std::sync::Arc;
@@ -59,10 +59,11 @@ impl StyleSet {
         &'a self,
         document: &dom::Document,
         node: dom::NodeId,
+        pseudo_element: Option<PseudoElement>,
         into: &mut SmallVec<impl smallvec::Array<Item = &'a DeclarationBlock>>,
     ) {
         for &(ref selector, ref block) in &self.rules {
-            if selectors::matches(selector, document, node) {
+            if selectors::matches(selector, document, node, pseudo_element) {
                 into.push(block)
             }
         }
@@ -100,8 +101,8 @@ pub(crate) fn style_for_element(
         ua: SmallVec::new(),
         author: SmallVec::new(),
     };
-    USER_AGENT_STYLESHEET.push_matching(document, node, &mut matching.ua);
-    author.push_matching(document, node, &mut matching.author);
+    USER_AGENT_STYLESHEET.push_matching(document, node, None, &mut matching.ua);
+    author.push_matching(document, node, None, &mut matching.author);
     if let ns!(html) | ns!(svg) | ns!(mathml) = element.name.ns {
         if let Some(style_attr) = element.get_attr(&local_name!("style")) {
             let mut input = ParserInput::new(style_attr);
@@ -112,3 +113,38 @@ pub(crate) fn style_for_element(
     }
     ComputedValues::new(parent_style, Some(&matching))
 }
+
+/// The style of a pseudo-element of `node`, such as `::marker`.
+/// Pseudo-elements inherit from their originating element.
+pub(crate) fn style_for_pseudo_element(
+    author: &StyleSet,
+    document: &dom::Document,
+    node: dom::NodeId,
+    pseudo_element: PseudoElement,
+    element_style: &ComputedValues,
+) -> Arc<ComputedValues> {
+    let mut matching = MatchingDeclarations {
+        ua: SmallVec::new(),
+        author: SmallVec::new(),
+    };
+    USER_AGENT_STYLESHEET.push_matching(document, node, Some(pseudo_element), &mut matching.ua);
+    author.push_matching(document, node, Some(pseudo_element), &mut matching.author);
+    ComputedValues::new(Some(element_style), Some(&matching))
+}
+
+/// Whether any rule applies to the given pseudo-element of `node`.
+/// `::first-line` and `::first-letter` are only generated if one does.
+pub(crate) fn pseudo_element_has_rules(
+    author: &StyleSet,
+    document: &dom::Document,
+    node: dom::NodeId,
+    pseudo_element: PseudoElement,
+) -> bool {
+    let matches = |style_set: &StyleSet| {
+        style_set
+            .rules
+            .iter()
+            .any(|(selector, _)| selectors::matches(selector, document, node, Some(pseudo_element)))
+    };
+    matches(&USER_AGENT_STYLESHEET) || matches(author)
+}
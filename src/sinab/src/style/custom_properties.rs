@@ -0,0 +1,274 @@
+//! Custom properties and `var()` substitution.
+//! https://drafts.csswg.org/css-variables/
+
+use crate::style::properties::{property_data_by_name, LonghandDeclaration, LonghandId};
+use crate::style::values::CssWideKeyword;
+use cssparser::{ParseError, Parser, ParserInput, Token};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// The computed custom properties of an element, by name including the
+/// leading `--`. Values have their `var()` references substituted.
+/// Custom properties are always inherited.
+pub(crate) type CustomProperties = HashMap<String, String>;
+
+/// A `--*` declaration.
+#[derive(Debug)]
+pub(super) struct CustomDeclaration {
+    pub name: String,
+    pub value: CustomDeclarationValue,
+}
+
+#[derive(Debug)]
+pub(super) enum CustomDeclarationValue {
+    Css { css: String, references_variables: bool },
+    CssWide(CssWideKeyword),
+}
+
+/// The value of a declaration that references custom properties, parsed for
+/// the declared property once they are substituted at computed-value time.
+#[derive(Debug)]
+pub(super) struct UnparsedValue {
+    /// The declared longhand or shorthand property.
+    pub property: String,
+    pub css: String,
+}
+
+/// Consumes a declaration value up to an optional `!important`, returning its
+/// source text and whether it contains any `var()` function.
+pub(super) fn parse_unparsed_value(parser: &mut Parser) -> (String, bool) {
+    let start = parser.position();
+    let mut end = start;
+    let mut references_variables = false;
+    loop {
+        let state = parser.state();
+        let at_important = parser
+            .try_parse(|parser| {
+                cssparser::parse_important(parser)?;
+                parser.expect_exhausted()
+            })
+            .is_ok();
+        if at_important {
+            parser.reset(&state);
+            break;
+        }
+        let (is_var, is_block) = match parser.next_including_whitespace_and_comments() {
+            Ok(token) => (is_var_function(token), is_block(token)),
+            Err(_) => break,
+        };
+        references_variables |= is_var;
+        if is_block {
+            references_variables |= parser
+                .parse_nested_block(|parser| Ok::<_, ParseError<()>>(consume_block(parser)))
+                .unwrap_or(false);
+        }
+        end = parser.position();
+    }
+    (parser.slice(start..end).trim().to_owned(), references_variables)
+}
+
+/// Consumes the rest of a block, returning whether it contains any `var()` function.
+fn consume_block(parser: &mut Parser) -> bool {
+    let mut references_variables = false;
+    while let Ok(token) = parser.next_including_whitespace_and_comments() {
+        let (is_var, is_block) = (is_var_function(token), is_block(token));
+        references_variables |= is_var;
+        if is_block {
+            references_variables |= parser
+                .parse_nested_block(|parser| Ok::<_, ParseError<()>>(consume_block(parser)))
+                .unwrap_or(false);
+        }
+    }
+    references_variables
+}
+
+fn is_var_function(token: &Token) -> bool {
+    matches!(token, Token::Function(name) if name.eq_ignore_ascii_case("var"))
+}
+
+fn is_block(token: &Token) -> bool {
+    closing_token(token).is_some()
+}
+
+fn closing_token(token: &Token) -> Option<&'static str> {
+    match token {
+        Token::Function(_) | Token::ParenthesisBlock => Some(")"),
+        Token::SquareBracketBlock => Some("]"),
+        Token::CurlyBracketBlock => Some("}"),
+        _ => None,
+    }
+}
+
+impl UnparsedValue {
+    /// The declaration of `longhand` obtained by substituting custom
+    /// properties, or `None` if the value is invalid at computed-value time.
+    /// https://drafts.csswg.org/css-variables/#invalid-at-computed-value-time
+    pub fn substitute(
+        &self,
+        longhand: LonghandId,
+        custom_properties: &CustomProperties,
+    ) -> Option<LonghandDeclaration> {
+        let css = substitute(&self.css, &mut |name| custom_properties.get(name).cloned())?;
+        let data = property_data_by_name(&self.property)?;
+        let mut input = ParserInput::new(&css);
+        let mut parser = Parser::new(&mut input);
+        let mut declarations = Vec::new();
+        parser.parse_entirely(|parser| (data.parse)(parser, &mut declarations)).ok()?;
+        // A shorthand pushes the declarations of its longhands in order.
+        let index = data.longhands.iter().position(|&id| id as usize == longhand as usize)?;
+        declarations.into_iter().nth(index)
+    }
+}
+
+/// Replaces the `var()` functions in `css` with the values given by `lookup`
+/// or their fallback, or returns `None` if one refers to a custom property
+/// that has no value and there is no fallback.
+fn substitute(css: &str, lookup: &mut dyn FnMut(&str) -> Option<String>) -> Option<String> {
+    let mut input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut input);
+    let mut substituted = String::new();
+    substitute_in_block(&mut parser, lookup, &mut substituted).ok()?;
+    Some(substituted.trim().to_owned())
+}
+
+fn substitute_in_block<'i>(
+    parser: &mut Parser<'i, '_>,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+    substituted: &mut String,
+) -> Result<(), ParseError<'i, ()>> {
+    let mut start = parser.position();
+    loop {
+        let before = parser.position();
+        let (is_var, closing) = match parser.next_including_whitespace_and_comments() {
+            Ok(token) => (is_var_function(token), closing_token(token)),
+            Err(_) => break,
+        };
+        let closing = match closing {
+            Some(closing) => closing,
+            None => continue,
+        };
+        if is_var {
+            substituted.push_str(parser.slice(start..before));
+            parser.parse_nested_block(|parser| substitute_var(parser, lookup, substituted))?;
+        } else {
+            substituted.push_str(parser.slice_from(start));
+            parser.parse_nested_block(|parser| substitute_in_block(parser, lookup, substituted))?;
+            substituted.push_str(closing);
+        }
+        start = parser.position();
+    }
+    substituted.push_str(parser.slice_from(start));
+    Ok(())
+}
+
+/// The arguments of `var()`: a custom property name and an optional fallback.
+fn substitute_var<'i>(
+    parser: &mut Parser<'i, '_>,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+    substituted: &mut String,
+) -> Result<(), ParseError<'i, ()>> {
+    let name = parser.expect_ident_cloned()?;
+    if !name.starts_with("--") {
+        return Err(parser.new_custom_error(()));
+    }
+    let value = lookup(&name);
+    let mut fallback = None;
+    if parser.try_parse(|parser| parser.expect_comma()).is_ok() {
+        let mut css = String::new();
+        if substitute_in_block(parser, lookup, &mut css).is_ok() {
+            fallback = Some(css)
+        }
+        while parser.next_including_whitespace_and_comments().is_ok() {}
+    }
+    match value.or(fallback) {
+        Some(value) => substituted.push_str(value.trim()),
+        None => return Err(parser.new_custom_error(())),
+    }
+    Ok(())
+}
+
+/// Computes the custom properties of an element from the ones it inherits
+/// and its `--*` declarations, applied in cascade order.
+pub(super) struct CustomPropertiesCascade<'a> {
+    inherited: &'a Arc<CustomProperties>,
+    /// `None` until a declaration applies, in which case the element
+    /// shares the custom properties of its parent.
+    properties: Option<CustomProperties>,
+    /// Declared values that still contain `var()` functions.
+    unresolved: HashSet<String>,
+}
+
+impl<'a> CustomPropertiesCascade<'a> {
+    pub fn new(inherited: &'a Arc<CustomProperties>) -> Self {
+        Self {
+            inherited,
+            properties: None,
+            unresolved: HashSet::new(),
+        }
+    }
+
+    pub fn apply(&mut self, declaration: &CustomDeclaration) {
+        let inherited = self.inherited;
+        let properties = self.properties.get_or_insert_with(|| (**inherited).clone());
+        let name = &declaration.name;
+        self.unresolved.remove(name);
+        match &declaration.value {
+            CustomDeclarationValue::Css { css, references_variables } => {
+                if *references_variables {
+                    self.unresolved.insert(name.clone());
+                }
+                properties.insert(name.clone(), css.clone());
+            }
+            // The initial value is the guaranteed-invalid value.
+            CustomDeclarationValue::CssWide(CssWideKeyword::Initial) => {
+                properties.remove(name);
+            }
+            CustomDeclarationValue::CssWide(CssWideKeyword::Inherit)
+            | CustomDeclarationValue::CssWide(CssWideKeyword::Unset) => {
+                match inherited.get(name) {
+                    Some(value) => properties.insert(name.clone(), value.clone()),
+                    None => properties.remove(name),
+                };
+            }
+        }
+    }
+
+    /// Substitutes references between custom properties. The ones that are
+    /// part of a reference cycle become invalid.
+    /// https://drafts.csswg.org/css-variables/#cycles
+    pub fn finish(mut self) -> Arc<CustomProperties> {
+        let mut properties = match self.properties {
+            Some(properties) => properties,
+            None => return self.inherited.clone(),
+        };
+        let names: Vec<String> = self.unresolved.iter().cloned().collect();
+        for name in names {
+            resolve(&name, &mut properties, &mut self.unresolved, &mut Vec::new());
+        }
+        Arc::new(properties)
+    }
+}
+
+fn resolve(
+    name: &str,
+    properties: &mut CustomProperties,
+    unresolved: &mut HashSet<String>,
+    stack: &mut Vec<String>,
+) -> Option<String> {
+    if !unresolved.contains(name) {
+        return properties.get(name).cloned();
+    }
+    if stack.iter().any(|n| n == name) {
+        return None;
+    }
+    let css = properties.get(name)?.clone();
+    stack.push(name.to_owned());
+    let value = substitute(&css, &mut |name| resolve(name, properties, unresolved, stack));
+    stack.pop();
+    unresolved.remove(name);
+    match &value {
+        Some(value) => properties.insert(name.to_owned(), value.clone()),
+        None => properties.remove(name),
+    };
+    value
+}
@@ -1,9 +1,12 @@
+use crate::style::custom_properties::{parse_unparsed_value, UnparsedValue};
+use crate::style::custom_properties::{CustomDeclaration, CustomDeclarationValue};
 use crate::style::errors::PropertyParseErrorKind;
 use crate::style::properties::{property_data_by_name, LonghandDeclaration, PerPhase, Phase};
 use crate::style::values::{CssWideKeyword, Parse};
 use cssparser::{AtRuleParser, ParseError, Parser};
 use cssparser::{CowRcStr, DeclarationListParser, DeclarationParser};
 use std::iter::repeat;
+use std::sync::Arc;
 
 #[derive(Debug, Default)]
 pub(super) struct DeclarationBlock {
@@ -37,11 +40,15 @@ impl DeclarationBlock {
             );
         }
         debug_assert_eq!(
-            iter.parser.block.any_normal.early || iter.parser.block.any_normal.late,
+            iter.parser.block.any_normal.custom
+                || iter.parser.block.any_normal.early
+                || iter.parser.block.any_normal.late,
             !iter.parser.block.important.all_true()
         );
         debug_assert_eq!(
-            iter.parser.block.any_important.early || iter.parser.block.any_important.late,
+            iter.parser.block.any_important.custom
+                || iter.parser.block.any_important.early
+                || iter.parser.block.any_important.late,
             !iter.parser.block.important.all_false()
         );
         iter.parser.block
@@ -86,11 +93,22 @@ impl<'i> DeclarationParser<'i> for LonghandDeclarationParser {
         name: CowRcStr<'i>,
         parser: &mut Parser<'i, 't>,
     ) -> Result<Self::Declaration, ParseError<'i, Self::Error>> {
-        if let Some(data) = property_data_by_name(&name) {
-            let previous_len = self.block.declarations.len();
-            let mut parsed;
+        let previous_len = self.block.declarations.len();
+        let mut parsed = PerPhase::default();
+        if name.starts_with("--") {
+            let value = match parser.r#try(CssWideKeyword::parse) {
+                Ok(keyword) => CustomDeclarationValue::CssWide(keyword),
+                Err(_) => {
+                    let (css, references_variables) = parse_unparsed_value(parser);
+                    CustomDeclarationValue::Css { css, references_variables }
+                }
+            };
+            let name = name.to_string();
+            let declaration = Box::new(CustomDeclaration { name, value });
+            self.block.declarations.push(LonghandDeclaration::Custom(declaration));
+            parsed.custom = true;
+        } else if let Some(data) = property_data_by_name(&name) {
             if let Ok(keyword) = parser.r#try(CssWideKeyword::parse) {
-                parsed = PerPhase::default();
                 for &longhand in data.longhands {
                     self.block
                         .declarations
@@ -101,23 +119,49 @@ impl<'i> DeclarationParser<'i> for LonghandDeclarationParser {
                         parsed.late = true
                     }
                 }
+            } else if let Some(value) = parse_value_with_variables(&name, parser) {
+                // Each longhand is parsed from the substituted value when it is computed.
+                for &longhand in data.longhands {
+                    self.block
+                        .declarations
+                        .push(LonghandDeclaration::WithVariables(longhand, value.clone()));
+                    if longhand.is_early() {
+                        parsed.early = true
+                    } else {
+                        parsed.late = true
+                    }
+                }
             } else {
                 parsed = (data.parse)(parser, &mut self.block.declarations)?
             }
-            let important = parser.r#try(cssparser::parse_important).is_ok();
-            let count = self.block.declarations.len() - previous_len;
-            assert!(count > 0);
-            self.block.important.extend(repeat(important).take(count));
-            let any = if important {
-                &mut self.block.any_important
-            } else {
-                &mut self.block.any_normal
-            };
-            any.early |= parsed.early;
-            any.late |= parsed.late;
-            Ok(())
         } else {
-            Err(parser.new_custom_error(PropertyParseErrorKind::UnknownProperty(name)))
+            return Err(parser.new_custom_error(PropertyParseErrorKind::UnknownProperty(name)));
+        }
+        let important = parser.r#try(cssparser::parse_important).is_ok();
+        let count = self.block.declarations.len() - previous_len;
+        assert!(count > 0);
+        self.block.important.extend(repeat(important).take(count));
+        let any = if important {
+            &mut self.block.any_important
+        } else {
+            &mut self.block.any_normal
+        };
+        any.custom |= parsed.custom;
+        any.early |= parsed.early;
+        any.late |= parsed.late;
+        Ok(())
+    }
+}
+
+/// A value containing `var()` functions, which can only be parsed once
+/// custom properties are substituted. Other values are left to be parsed.
+fn parse_value_with_variables(name: &str, parser: &mut Parser) -> Option<Arc<UnparsedValue>> {
+    let state = parser.state();
+    match parse_unparsed_value(parser) {
+        (css, true) => Some(Arc::new(UnparsedValue { property: name.to_owned(), css })),
+        (_, false) => {
+            parser.reset(&state);
+            None
         }
     }
 }
@@ -1,4 +1,5 @@
 mod cascade;
+mod custom_properties;
 mod declaration_block;
 mod errors;
 mod properties;
@@ -7,5 +8,9 @@ mod selectors;
 mod tests; // unit tests for declarations etc.
 pub(crate) mod values;
 
-pub(crate) use self::cascade::{style_for_element, StyleSet, StyleSetBuilder};
+pub(crate) use self::cascade::{
+    pseudo_element_has_rules, style_for_element, style_for_pseudo_element, StyleSet,
+    StyleSetBuilder,
+};
 pub(crate) use self::properties::ComputedValues;
+pub(crate) use self::selectors::PseudoElement;
@@ -19,6 +19,7 @@ properties! {
 
     reset struct line_reset {
         vertical_align { "vertical-align", VerticalAlign, initial = VerticalAlign::Baseline }
+        initial_letter { "initial-letter", InitialLetter, initial = InitialLetter::Normal }
     }
 
     inherited struct color {
@@ -29,11 +30,94 @@ properties! {
     inherited struct text_inherited {
         text_align { "text-align", TextAlign, initial = TextAlign::Left }
         white_space { "white-space", WhiteSpace, initial = WhiteSpace::Normal }
+        text_shadow { "text-shadow", TextShadow, initial = TextShadow::NONE }
+    }
+
+    inherited struct inherited_box {
+        visibility { "visibility", Visibility, initial = Visibility::Visible }
+    }
+
+    inherited struct list {
+        list_style_type { "list-style-type", ListStyleType, initial = ListStyleType::Disc }
+        list_style_position {
+            "list-style-position", ListStylePosition, initial = ListStylePosition::Outside
+        }
+        list_style_image { "list-style-image", ListStyleImage, initial = ListStyleImage::None }
+    }
+
+    inherited struct content_inherited {
+        quotes { "quotes", Quotes, initial = Quotes::Auto }
+    }
+
+    reset struct content_reset {
+        content { "content", Content, initial = Content::Normal }
+        counter_reset { "counter-reset", CounterReset, initial = CounterReset(Vec::new()) }
+        counter_increment {
+            "counter-increment", CounterIncrement, initial = CounterIncrement(Vec::new())
+        }
+        counter_set { "counter-set", CounterSet, initial = CounterSet(Vec::new()) }
+    }
+
+    inherited struct table_inherited {
+        border_collapse {
+            "border-collapse", BorderCollapse, initial = BorderCollapse::Separate
+        }
+        border_spacing { "border-spacing", BorderSpacing, initial = BorderSpacing::ZERO }
+        caption_side { "caption-side", CaptionSide, initial = CaptionSide::Top }
+    }
+
+    reset struct table_reset {
+        table_layout { "table-layout", TableLayout, initial = TableLayout::Auto }
+    }
+
+    reset struct flex {
+        flex_direction { "flex-direction", FlexDirection, initial = FlexDirection::Row }
+        flex_wrap { "flex-wrap", FlexWrap, initial = FlexWrap::Nowrap }
+        justify_content { "justify-content", JustifyContent, initial = JustifyContent::FlexStart }
+        align_items { "align-items", AlignItems, initial = AlignItems::Stretch }
+        align_self { "align-self", AlignSelf, initial = AlignSelf::Auto }
+        justify_items { "justify-items", AlignItems, initial = AlignItems::Stretch }
+        justify_self { "justify-self", AlignSelf, initial = AlignSelf::Auto }
+        flex_grow { "flex-grow", Number, initial = Number { value: 0. } }
+        flex_shrink { "flex-shrink", Number, initial = Number { value: 1. } }
+        flex_basis {
+            "flex-basis", LengthOrPercentageOrAuto, initial = LengthOrPercentageOrAuto::Auto
+        }
+        row_gap { "row-gap", LengthOrPercentageOrNormal, initial = LengthOrPercentageOrNormal::Normal }
+        column_gap {
+            "column-gap", LengthOrPercentageOrNormal, initial = LengthOrPercentageOrNormal::Normal
+        }
+    }
+
+    reset struct grid {
+        grid_template_columns {
+            "grid-template-columns", TrackList, initial = TrackList::NONE
+        }
+        grid_template_rows { "grid-template-rows", TrackList, initial = TrackList::NONE }
+        grid_auto_columns { "grid-auto-columns", TrackSize, initial = TrackSize::AUTO }
+        grid_auto_rows { "grid-auto-rows", TrackSize, initial = TrackSize::AUTO }
+        grid_auto_flow { "grid-auto-flow", GridAutoFlow, initial = GridAutoFlow::Row }
+        grid_row_start { "grid-row-start", GridLine, initial = GridLine::Auto }
+        grid_row_end { "grid-row-end", GridLine, initial = GridLine::Auto }
+        grid_column_start { "grid-column-start", GridLine, initial = GridLine::Auto }
+        grid_column_end { "grid-column-end", GridLine, initial = GridLine::Auto }
+    }
+
+    reset struct multicol {
+        column_count { "column-count", ColumnCount, initial = ColumnCount::Auto }
+        column_width {
+            "column-width", LengthOrPercentageOrAuto, initial = LengthOrPercentageOrAuto::Auto
+        }
+        column_rule_color { "column-rule-color", Color, initial = Color::CurrentColor }
+        column_rule_style { "column-rule-style", LineStyle, initial = LineStyle::None }
+        column_rule_width { "column-rule-width", LineWidth, initial = LineWidth::MEDIUM }
     }
 
     reset struct box_ {
         position { "position", Position, initial = Position::Static }
         float { "float", Float, initial = Float::None }
+        clear { "clear", Clear, initial = Clear::None }
+        z_index { "z-index", ZIndex, initial = ZIndex::Auto }
         display { "display", Display, initial = Display::INITIAL }
         top { "top", LengthOrPercentageOrAuto, initial = LengthOrPercentageOrAuto::Auto }
         left { "left", LengthOrPercentageOrAuto, initial = LengthOrPercentageOrAuto::Auto }
@@ -41,6 +125,16 @@ properties! {
         right { "right", LengthOrPercentageOrAuto, initial = LengthOrPercentageOrAuto::Auto }
         width { "width", LengthOrPercentageOrAuto, initial = LengthOrPercentageOrAuto::Auto }
         height { "height", LengthOrPercentageOrAuto, initial = LengthOrPercentageOrAuto::Auto }
+        min_width { "min-width", LengthOrPercentageOrAuto, initial = LengthOrPercentageOrAuto::Auto }
+        min_height { "min-height", LengthOrPercentageOrAuto, initial = LengthOrPercentageOrAuto::Auto }
+        max_width { "max-width", LengthOrPercentageOrNone, initial = LengthOrPercentageOrNone::None }
+        max_height { "max-height", LengthOrPercentageOrNone, initial = LengthOrPercentageOrNone::None }
+        box_sizing { "box-sizing", BoxSizing, initial = BoxSizing::ContentBox }
+        overflow { "overflow", Overflow, initial = Overflow::Visible }
+        object_fit { "object-fit", ObjectFit, initial = ObjectFit::Fill }
+        opacity { "opacity", Opacity, initial = Opacity(1.) }
+        transform { "transform", Transform, initial = Transform::NONE }
+        transform_origin { "transform-origin", PositionValue, initial = PositionValue::CENTER }
     }
 
     reset struct margin {
@@ -72,10 +166,43 @@ properties! {
         border_right_width { "border-right-width", LineWidth, initial = LineWidth::MEDIUM }
         border_bottom_width { "border-bottom-width", LineWidth, initial = LineWidth::MEDIUM }
         border_left_width { "border-left-width", LineWidth, initial = LineWidth::MEDIUM }
+
+        border_top_left_radius {
+            "border-top-left-radius", BorderCornerRadius, initial = BorderCornerRadius::ZERO
+        }
+        border_top_right_radius {
+            "border-top-right-radius", BorderCornerRadius, initial = BorderCornerRadius::ZERO
+        }
+        border_bottom_right_radius {
+            "border-bottom-right-radius", BorderCornerRadius, initial = BorderCornerRadius::ZERO
+        }
+        border_bottom_left_radius {
+            "border-bottom-left-radius", BorderCornerRadius, initial = BorderCornerRadius::ZERO
+        }
+    }
+
+    reset struct outline {
+        outline_color { "outline-color", Color, initial = Color::CurrentColor }
+        outline_style { "outline-style", LineStyle, initial = LineStyle::None }
+        outline_width { "outline-width", LineWidth, initial = LineWidth::MEDIUM }
+        outline_offset { "outline-offset", Length, initial = Length::zero() }
     }
 
     reset struct background {
         background_color { "background-color", Color, initial = Color::RGBA(RGBA::transparent()) }
+        background_image { "background-image", Layers<ImageValue>, initial = ImageValue::None }
+        background_position {
+            "background-position", Layers<PositionValue>, initial = PositionValue::TOP_LEFT
+        }
+        background_size { "background-size", Layers<BackgroundSize>, initial = BackgroundSize::AUTO }
+        background_repeat {
+            "background-repeat", Layers<BackgroundRepeat>, initial = BackgroundRepeat::REPEAT
+        }
+        background_origin {
+            "background-origin", Layers<BackgroundBox>, initial = BackgroundBox::PaddingBox
+        }
+        background_clip { "background-clip", Layers<BackgroundBox>, initial = BackgroundBox::BorderBox }
+        box_shadow { "box-shadow", BoxShadow, initial = BoxShadow::NONE }
     }
 
     @shorthands {
@@ -143,8 +270,66 @@ properties! {
             width_bottom: border_bottom_width,
             width_left: border_left_width,
         }
+        "outline" => BorderSide {
+            style: outline_style,
+            color: outline_color,
+            width: outline_width,
+        }
+        "border-radius" => BorderRadius {
+            top_left: border_top_left_radius,
+            top_right: border_top_right_radius,
+            bottom_right: border_bottom_right_radius,
+            bottom_left: border_bottom_left_radius,
+        }
+        "list-style" => ListStyle {
+            type_: list_style_type,
+            position: list_style_position,
+            image: list_style_image,
+        }
         "background" => Background {
             color: background_color,
+            image: background_image,
+            position: background_position,
+            size: background_size,
+            repeat: background_repeat,
+            origin: background_origin,
+            clip: background_clip,
+        }
+        "flex-flow" => FlexFlow {
+            direction: flex_direction,
+            wrap: flex_wrap,
+        }
+        "flex" => Flex {
+            grow: flex_grow,
+            shrink: flex_shrink,
+            basis: flex_basis,
+        }
+        "gap" => Gap {
+            row: row_gap,
+            column: column_gap,
+        }
+        "columns" => Columns {
+            width: column_width,
+            count: column_count,
+        }
+        "column-rule" => BorderSide {
+            style: column_rule_style,
+            color: column_rule_color,
+            width: column_rule_width,
+        }
+        "grid-row" => GridLineRange {
+            start: grid_row_start,
+            end: grid_row_end,
+        }
+        "grid-column" => GridLineRange {
+            start: grid_column_start,
+            end: grid_column_end,
+        }
+        "grid-area" => GridArea {
+            row_start: grid_row_start,
+            column_start: grid_column_start,
+            row_end: grid_row_end,
+            column_end: grid_column_end,
         }
     }
 }
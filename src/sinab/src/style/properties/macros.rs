@@ -98,7 +98,9 @@ macro_rules! properties {
                 $($(
                     $ident(<$ValueType as crate::style::values::SpecifiedValue>::SpecifiedValue),
                 )+)+
-                CssWide(LonghandId, crate::style::values::CssWideKeyword)
+                CssWide(LonghandId, crate::style::values::CssWideKeyword),
+                Custom(Box<crate::style::custom_properties::CustomDeclaration>),
+                WithVariables(LonghandId, Arc<crate::style::custom_properties::UnparsedValue>)
             }
 
             pub(in crate::style) fn if_early_cascade_into(
@@ -128,6 +130,20 @@ macro_rules! properties {
                             )
                         }
                     }
+                    LonghandDeclaration::Custom(ref _declaration) => {}
+                    LonghandDeclaration::WithVariables(ref longhand, ref value) => {
+                        if longhand.is_early() {
+                            let custom_properties = &context.this.0.custom_properties;
+                            match value.substitute(*longhand, custom_properties) {
+                                Some(declaration) => declaration.if_early_cascade_into(context),
+                                None => longhand.cascade_css_wide_keyword_into(
+                                    crate::style::values::CssWideKeyword::Unset,
+                                    context.this.0,
+                                    context.inherited,
+                                ),
+                            }
+                        }
+                    }
                 }
             }
 
@@ -158,6 +174,20 @@ macro_rules! properties {
                             )
                         }
                     }
+                    LonghandDeclaration::Custom(ref _declaration) => {}
+                    LonghandDeclaration::WithVariables(ref longhand, ref value) => {
+                        if !longhand.is_early() {
+                            let custom_properties = &context.this.0.custom_properties;
+                            match value.substitute(*longhand, custom_properties) {
+                                Some(declaration) => declaration.if_late_cascade_into(context),
+                                None => longhand.cascade_css_wide_keyword_into(
+                                    crate::style::values::CssWideKeyword::Unset,
+                                    context.this.0,
+                                    context.inherited,
+                                ),
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -168,6 +198,7 @@ macro_rules! properties {
                 pub(crate) $struct_name: Arc<style_structs::$struct_name>,
             )+
             pub(crate) specified_display: Display,
+            pub(crate) custom_properties: Arc<crate::style::custom_properties::CustomProperties>,
         }
 
         impl std::fmt::Debug for ComputedValues {
@@ -206,6 +237,7 @@ macro_rules! properties {
                             ),
                         )+
                         specified_display: Display::INITIAL,
+                        custom_properties: Default::default(),
                     };
                 }
                 let inherited = inherited.unwrap_or(&*INITIAL_VALUES);
@@ -218,8 +250,15 @@ macro_rules! properties {
                         $struct_name: Arc::clone(&select!($inherited).$struct_name),
                     )+
                     specified_display: Display::INITIAL,
+                    custom_properties: inherited.custom_properties.clone(),
                 };
                 if let Some(matching) = matching {
+                    let mut custom_properties =
+                        crate::style::custom_properties::CustomPropertiesCascade::new(
+                            &inherited.custom_properties,
+                        );
+                    matching.cascade(&mut custom_properties);
+                    computed.custom_properties = custom_properties.finish();
                     matching.cascade(&mut crate::style::values::EarlyCascadeContext {
                         inherited,
                         this: ComputedValuesForEarlyCascade(&mut computed)
@@ -262,6 +301,7 @@ macro_rules! properties {
                             let v = crate::style::values::Parse::parse(parser)?;
                             declarations.push(LonghandDeclaration::$ident(v));
                             Ok(crate::style::properties::PerPhase {
+                                custom: false,
                                 early: is_early!($($early)?),
                                 late: !is_early!($($early)?),
                             })
@@ -291,6 +331,7 @@ macro_rules! properties {
                                 );
                             )+
                             Ok(crate::style::properties::PerPhase {
+                                custom: false,
                                 early: $( LonghandId::$longhand_ident.is_early() )||+,
                                 late: $( !LonghandId::$longhand_ident.is_early() )||+,
                             })
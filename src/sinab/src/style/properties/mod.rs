@@ -1,10 +1,11 @@
 pub(crate) use self::definitions::ComputedValues;
-use self::definitions::LonghandId;
+pub(super) use self::definitions::LonghandId;
 pub(super) use self::definitions::{property_data_by_name, LonghandDeclaration};
 pub(super) use self::definitions::{ComputedValuesForEarlyCascade, ComputedValuesForLateCascade};
 use crate::geom::{flow_relative, physical};
+use crate::style::custom_properties::CustomPropertiesCascade;
 use crate::style::errors::PropertyParseError;
-use crate::style::values::{self, CssWideKeyword, Direction, Display, WritingMode};
+use crate::style::values::{self, BorderCollapse, CssWideKeyword, Direction, Display, WritingMode};
 use crate::style::values::{CascadeContext, EarlyCascadeContext};
 use cssparser::{Color, RGBA};
 use std::sync::Arc;
@@ -25,8 +26,79 @@ impl ComputedValues {
         b.border_left_width.fixup(b.border_left_style);
         b.border_bottom_width.fixup(b.border_bottom_style);
         b.border_right_width.fixup(b.border_right_style);
+        let m = Arc::make_mut(&mut self.multicol);
+        m.column_rule_width.fixup(m.column_rule_style);
+        let o = Arc::make_mut(&mut self.outline);
+        o.outline_width.fixup(o.outline_style);
 
         Display::fixup(self);
+
+        // In the collapsing border model, tables have no padding.
+        // https://drafts.csswg.org/css2/tables.html#collapsing-borders
+        if self.box_.display.is_table()
+            && self.table_inherited.border_collapse == BorderCollapse::Collapse
+        {
+            let p = Arc::make_mut(&mut self.padding);
+            p.padding_top = values::LengthOrPercentage::Length(values::Length::zero());
+            p.padding_left = values::LengthOrPercentage::Length(values::Length::zero());
+            p.padding_bottom = values::LengthOrPercentage::Length(values::Length::zero());
+            p.padding_right = values::LengthOrPercentage::Length(values::Length::zero());
+        }
+    }
+
+    /// https://drafts.csswg.org/css-multicol/#multi-column-container
+    pub(crate) fn is_multicol_container(&self) -> bool {
+        self.multicol.column_count != values::ColumnCount::Auto
+            || !matches!(self.multicol.column_width, values::LengthOrPercentageOrAuto::Auto)
+    }
+
+    /// Boxes with `overflow: hidden` are scroll containers, and establish
+    /// independent formatting contexts. `overflow: clip` does neither.
+    /// https://drafts.csswg.org/css-overflow-3/#scroll-container
+    pub(crate) fn is_scroll_container(&self) -> bool {
+        self.box_.overflow == values::Overflow::Hidden
+    }
+
+    /// The z-index of the stacking context established by the box, if any.
+    /// Positioned boxes with an integer z-index establish stacking contexts,
+    /// as do boxes with an opacity below 1 or a transform, at z-index zero
+    /// otherwise.
+    /// https://drafts.csswg.org/css2/visuren.html#z-index
+    /// https://drafts.csswg.org/css-color/#transparency
+    /// https://drafts.csswg.org/css-transforms/#transform-rendering
+    pub(crate) fn stacking_context_z_index(&self) -> Option<i32> {
+        match self.box_.z_index {
+            values::ZIndex::Integer(z_index) if self.box_.position != values::Position::Static => {
+                Some(z_index)
+            }
+            _ if self.box_.opacity.0 < 1. || !self.box_.transform.0.is_empty() => Some(0),
+            _ => None,
+        }
+    }
+
+    /// The style of a `::first-letter` with `initial-letter`, floated to the
+    /// inline start with a font size and line height of `size` lines of the
+    /// paragraph, raised above the first line unless it sinks as many lines.
+    /// The em box of the letter, rather than its cap height, spans the lines.
+    /// https://drafts.csswg.org/css-inline/#initial-letter-styling
+    pub(crate) fn with_initial_letter(self: &Arc<Self>, paragraph: &ComputedValues) -> Arc<Self> {
+        let (size, sink) = match self.line_reset.initial_letter {
+            values::InitialLetter::Normal => return self.clone(),
+            values::InitialLetter::Lines { size, sink } => (size, sink),
+        };
+        let line_height = paragraph
+            .line_inherited
+            .line_height
+            .percentage_or_number_relative_to(paragraph.font.font_size.0);
+        let mut style = (**self).clone();
+        Arc::make_mut(&mut style.font).font_size = values::FontSize(line_height * size);
+        Arc::make_mut(&mut style.line_inherited).line_height =
+            values::LengthOrPercentageOrNumber::Length(line_height * size);
+        Arc::make_mut(&mut style.box_).float = values::Float::Left;
+        let raise = (size - sink as f32).max(0.);
+        Arc::make_mut(&mut style.margin).margin_top =
+            values::LengthOrPercentageOrAuto::Length(-(line_height * raise));
+        Arc::new(style)
     }
 
     pub(crate) fn writing_mode(&self) -> (WritingMode, Direction) {
@@ -52,6 +124,23 @@ impl ComputedValues {
         .size_to_flow_relative(self.writing_mode())
     }
 
+    /// An `auto` minimum size behaves as zero.
+    pub(crate) fn min_box_size(&self) -> flow_relative::Vec2<values::LengthOrPercentageOrAuto> {
+        physical::Vec2 {
+            x: self.box_.min_width,
+            y: self.box_.min_height,
+        }
+        .size_to_flow_relative(self.writing_mode())
+    }
+
+    pub(crate) fn max_box_size(&self) -> flow_relative::Vec2<values::LengthOrPercentageOrNone> {
+        physical::Vec2 {
+            x: self.box_.max_width,
+            y: self.box_.max_height,
+        }
+        .size_to_flow_relative(self.writing_mode())
+    }
+
     pub(crate) fn padding(&self) -> flow_relative::Sides<values::LengthOrPercentage> {
         physical::Sides {
             top: self.padding.padding_top,
@@ -115,8 +204,23 @@ impl Phase for CascadeContext<'_> {
     }
 }
 
+/// Custom properties are computed before the early phase,
+/// since any property can reference them.
+impl Phase for CustomPropertiesCascade<'_> {
+    fn select(&self, p: PerPhase<bool>) -> bool {
+        p.custom
+    }
+
+    fn cascade(&mut self, declaration: &LonghandDeclaration) {
+        if let LonghandDeclaration::Custom(declaration) = declaration {
+            self.apply(declaration)
+        }
+    }
+}
+
 #[derive(Default, Copy, Clone, Debug)]
 pub(super) struct PerPhase<T> {
+    pub custom: T,
     pub early: T,
     pub late: T,
 }
@@ -1,17 +1,30 @@
 use crate::dom::{Document, Node, NodeId};
 use crate::style::errors::RuleParseErrorKind;
-use cssparser::ToCss;
+use cssparser::{CowRcStr, ParseError, SourceLocation, ToCss};
 use html5ever::{LocalName, Namespace, Prefix};
 use selectors::attr::{AttrSelectorOperation, CaseSensitivity, NamespaceConstraint};
 use selectors::context::{MatchingContext, MatchingMode, QuirksMode};
 use selectors::matching::{matches_selector, ElementSelectorFlags};
-use selectors::parser::{SelectorImpl};
+use selectors::parser::{SelectorImpl, SelectorParseErrorKind};
 use std::fmt;
 
 pub(super) type SelectorList = selectors::SelectorList<Impl>;
 pub(super) type Selector = selectors::parser::Selector<Impl>;
 
-pub(super) fn matches(selector: &Selector, document: &Document, element: NodeId) -> bool {
+/// Whether `selector` matches `element`, or the given pseudo-element of `element`.
+pub(super) fn matches(
+    selector: &Selector,
+    document: &Document,
+    element: NodeId,
+    pseudo_element: Option<PseudoElement>,
+) -> bool {
+    if selector.pseudo_element() != pseudo_element.as_ref() {
+        return false;
+    }
+    let mode = match pseudo_element {
+        Some(_) => MatchingMode::ForStatelessPseudoElement,
+        None => MatchingMode::Normal,
+    };
     matches_selector(
         selector,
         0,
@@ -20,31 +33,51 @@ pub(super) fn matches(selector: &Selector, document: &Document, element: NodeId)
             document,
             node: element,
         },
-        &mut MatchingContext::new(MatchingMode::Normal, None, None, QuirksMode::NoQuirks),
+        &mut MatchingContext::new(mode, None, None, QuirksMode::NoQuirks),
         &mut |_, _| {},
     )
 }
 
 #[derive(Clone, Debug)]
-pub(super) struct Impl;
+pub(crate) struct Impl;
 
 pub(super) struct Parser;
 
-#[derive(Clone, PartialEq, Eq)]
-pub(super) enum PseudoElement {}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PseudoElement {
+    /// https://drafts.csswg.org/css-pseudo-4/#marker-pseudo
+    Marker,
+    /// https://drafts.csswg.org/css-pseudo-4/#generated-content
+    Before,
+    After,
+    /// https://drafts.csswg.org/css-pseudo-4/#first-line-pseudo
+    FirstLine,
+    /// https://drafts.csswg.org/css-pseudo-4/#first-letter-pseudo
+    FirstLetter,
+}
 
+/// Pseudo-classes not handled by the `selectors` crate itself. Structural
+/// pseudo-classes such as `:nth-child()` are parsed and matched there.
 #[derive(Clone, PartialEq, Eq)]
-pub(super) enum PseudoClass {}
+pub(crate) enum PseudoClass {
+    /// https://drafts.csswg.org/selectors-4/#the-lang-pseudo
+    Lang(String),
+}
 
 impl selectors::parser::NonTSPseudoClass for PseudoClass {
     type Impl = Impl;
+
     fn is_active_or_hover(&self) -> bool {
-        match *self {}
+        false
     }
 
-    // todo items
-    fn is_user_action_state(&self) -> bool { todo!() }
-    fn has_zero_specificity(&self) -> bool { todo!() }
+    fn is_user_action_state(&self) -> bool {
+        false
+    }
+
+    fn has_zero_specificity(&self) -> bool {
+        false
+    }
 }
 
 impl selectors::parser::SelectorImpl for Impl {
@@ -65,6 +98,36 @@ impl selectors::parser::SelectorImpl for Impl {
 impl<'i> selectors::parser::Parser<'i> for Parser {
     type Impl = Impl;
     type Error = RuleParseErrorKind<'i>;
+
+    fn parse_pseudo_element(
+        &self,
+        location: SourceLocation,
+        name: CowRcStr<'i>,
+    ) -> Result<PseudoElement, ParseError<'i, Self::Error>> {
+        match_ignore_ascii_case! { &name,
+            "marker" => Ok(PseudoElement::Marker),
+            "before" => Ok(PseudoElement::Before),
+            "after" => Ok(PseudoElement::After),
+            "first-line" => Ok(PseudoElement::FirstLine),
+            "first-letter" => Ok(PseudoElement::FirstLetter),
+            _ => Err(location.new_custom_error(
+                SelectorParseErrorKind::UnsupportedPseudoClassOrElement(name),
+            )),
+        }
+    }
+
+    fn parse_non_ts_functional_pseudo_class<'t>(
+        &self,
+        name: CowRcStr<'i>,
+        arguments: &mut cssparser::Parser<'i, 't>,
+    ) -> Result<PseudoClass, ParseError<'i, Self::Error>> {
+        match_ignore_ascii_case! { &name,
+            "lang" => Ok(PseudoClass::Lang(arguments.expect_ident_or_string()?.to_string())),
+            _ => Err(arguments.new_custom_error(
+                SelectorParseErrorKind::UnsupportedPseudoClassOrElement(name),
+            )),
+        }
+    }
 }
 
 impl selectors::parser::PseudoElement for PseudoElement {
@@ -72,20 +135,32 @@ impl selectors::parser::PseudoElement for PseudoElement {
 }
 
 impl ToCss for PseudoElement {
-    fn to_css<W>(&self, _dest: &mut W) -> fmt::Result
+    fn to_css<W>(&self, dest: &mut W) -> fmt::Result
     where
         W: fmt::Write,
     {
-        match *self {}
+        match *self {
+            PseudoElement::Marker => dest.write_str("::marker"),
+            PseudoElement::Before => dest.write_str("::before"),
+            PseudoElement::After => dest.write_str("::after"),
+            PseudoElement::FirstLine => dest.write_str("::first-line"),
+            PseudoElement::FirstLetter => dest.write_str("::first-letter"),
+        }
     }
 }
 
 impl ToCss for PseudoClass {
-    fn to_css<W>(&self, _dest: &mut W) -> fmt::Result
+    fn to_css<W>(&self, dest: &mut W) -> fmt::Result
     where
         W: fmt::Write,
     {
-        match *self {}
+        match *self {
+            PseudoClass::Lang(ref lang) => {
+                dest.write_str(":lang(")?;
+                cssparser::serialize_string(lang, dest)?;
+                dest.write_str(")")
+            }
+        }
     }
 }
 
@@ -105,6 +180,21 @@ impl<'a> NodeRef<'a> {
     fn node(self) -> &'a Node {
         &self.document[self.node]
     }
+
+    /// The content language of the element, from the `lang` attribute of
+    /// the element or of its nearest ancestor that has one.
+    /// https://html.spec.whatwg.org/multipage/dom.html#language
+    fn lang(self) -> Option<&'a str> {
+        let mut node = self.node;
+        loop {
+            if let Some(element) = self.document[node].as_element() {
+                if let Some(lang) = element.get_attr(&local_name!("lang")) {
+                    return Some(lang);
+                }
+            }
+            node = self.document[node].parent?;
+        }
+    }
 }
 
 fn find_element<'a, F>(
@@ -201,15 +291,25 @@ impl<'a> selectors::Element for NodeRef<'a> {
     where
         F: FnMut(&Self, ElementSelectorFlags),
     {
-        match *pseudo_class {}
+        match *pseudo_class {
+            // A language range matches a language tag equal to it or
+            // beginning with it followed by `-`, ignoring case.
+            PseudoClass::Lang(ref range) => self.lang().is_some_and(|lang| {
+                lang.len() >= range.len()
+                    && lang.as_bytes()[..range.len()].eq_ignore_ascii_case(range.as_bytes())
+                    && matches!(lang.as_bytes().get(range.len()), None | Some(b'-'))
+            }),
+        }
     }
 
     fn match_pseudo_element(
         &self,
-        pseudo_element: &PseudoElement,
+        _pseudo_element: &PseudoElement,
         _context: &mut MatchingContext<Self::Impl>,
     ) -> bool {
-        match *pseudo_element {}
+        // Pseudo-elements are matched in `MatchingMode::ForStatelessPseudoElement`,
+        // a `NodeRef` always refers to an element.
+        false
     }
 
     fn is_link(&self) -> bool {
@@ -266,10 +366,29 @@ impl<'a> selectors::Element for NodeRef<'a> {
         self.parent_element().is_none()
     }
 
-    // todo implementations
-    fn is_pseudo_element(&self) -> bool { todo!() }
-    fn is_same_type(&self, _other: &Self) -> bool { todo!() }
-    fn exported_part(&self, _name: &<Self::Impl as SelectorImpl>::PartName) -> Option<<Self::Impl as SelectorImpl>::PartName> { todo!() }
-    fn imported_part(&self, _name: &<Self::Impl as SelectorImpl>::PartName) -> Option<<Self::Impl as SelectorImpl>::PartName> { todo!() }
-    fn is_part(&self, _name: &<Self::Impl as SelectorImpl>::PartName) -> bool { todo!() }
+    fn is_pseudo_element(&self) -> bool {
+        false
+    }
+
+    fn is_same_type(&self, other: &Self) -> bool {
+        self.node().as_element().unwrap().name == other.node().as_element().unwrap().name
+    }
+
+    fn exported_part(
+        &self,
+        _name: &<Self::Impl as SelectorImpl>::PartName,
+    ) -> Option<<Self::Impl as SelectorImpl>::PartName> {
+        None
+    }
+
+    fn imported_part(
+        &self,
+        _name: &<Self::Impl as SelectorImpl>::PartName,
+    ) -> Option<<Self::Impl as SelectorImpl>::PartName> {
+        None
+    }
+
+    fn is_part(&self, _name: &<Self::Impl as SelectorImpl>::PartName) -> bool {
+        false
+    }
 }
@@ -32,7 +32,7 @@ mod tests {
         let mut input = Parser::new(&mut parser_input);
         let mut declarations = DeclarationBlock::parse(&mut input);
 
-        assert_eq!(declarations.get_declarations().len(), 11);
+        assert_eq!(declarations.get_declarations().len(), 17);
         let mut decl_iter = declarations.get_declarations().into_iter();
 
         validate_next_declaration!(
@@ -47,6 +47,14 @@ mod tests {
             assert_eq!(*rgba, RGBA::new(255, 0, 0, 255))
         );
 
+        // the other background longhands are reset to their initial values
+        validate_next_declaration!(
+            decl_iter,
+            LonghandDeclaration::background_image(Layers(ref images)),
+            assert!(matches!(images[..], [SpecifiedImageValue::None]))
+        );
+        let mut decl_iter = decl_iter.skip(5);
+
         validate_next_declaration!(
             decl_iter,
             LonghandDeclaration::margin_top(
@@ -201,4 +209,43 @@ mod tests {
         }
         assert!(true);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_outline() {
+        let css = "
+            outline: 2px dashed red;
+            outline-offset: -1px;";
+        let mut parser_input = ParserInput::new(css);
+        let mut input = Parser::new(&mut parser_input);
+        let mut declarations = DeclarationBlock::parse(&mut input);
+
+        assert_eq!(declarations.get_declarations().len(), 4);
+        let mut decl_iter = declarations.get_declarations().into_iter();
+
+        validate_next_declaration!(
+            decl_iter,
+            LonghandDeclaration::outline_style(style),
+            assert_eq!(*style, LineStyle::Dashed)
+        );
+
+        validate_next_declaration!(
+            decl_iter,
+            LonghandDeclaration::outline_color(Color::RGBA(ref rgba)),
+            assert_eq!(*rgba, RGBA::new(255, 0, 0, 255))
+        );
+
+        validate_next_declaration!(
+            decl_iter,
+            LonghandDeclaration::outline_width(SpecifiedLineWidth(
+                SpecifiedLengthOrPercentage::Length(SpecifiedLength::Absolute(Length { px: value }))
+            )),
+            assert_eq!(*value, 2.0)
+        );
+
+        validate_next_declaration!(
+            decl_iter,
+            LonghandDeclaration::outline_offset(SpecifiedLength::Absolute(Length { px: value })),
+            assert_eq!(*value, -1.0)
+        );
+    }
+}
@@ -3,10 +3,11 @@
 
 use crate::{
     dom::{Document, NodeId, NodeData},
-    style::{StyleSet, style_for_element}
+    style::{StyleSet, style_for_element, style_for_pseudo_element, pseudo_element_has_rules, PseudoElement},
+    style::values::{Content, ContentItem, FontWeight, InitialLetter, Length, LengthOrPercentageOrAuto},
 };
 
-use cssparser::RGBA;
+use cssparser::{Color, RGBA};
 
 /*
 fn process_node<'dom>(node_id: NodeId, author_styles: &'dom StyleSet, document: &'dom Document,
@@ -142,3 +143,192 @@ r#"<body><p>par 1</p><p class="p2">par 2</p><p id="p3">par 3</p><span id="p3">sp
     validate_text!(child, "em", document);
 
 }
+
+#[test]
+fn marker_pseudo_element() {
+    let text_input = r#"<body><ul><li>item</li></ul></body>"#;
+    let css_input = r#"
+        li         { color: green; }
+        li::marker { color: red; }
+    "#;
+
+    let document = Document::parse_html(text_input.as_bytes());
+    let author_styles = &document.parse_stylesheets(Some(css_input));
+
+    let body = document[document.root_element()].first_child.unwrap();
+    let body = document[body].next_sibling.unwrap();
+    let ul = document[body].first_child.unwrap();
+    let li = document[ul].first_child.unwrap();
+    validate_element_type!(li, "li", document);
+
+    // the `::marker` rule does not apply to the element itself
+    let style = style_for_element(author_styles, &document, li, None);
+    assert_eq!(style.color.color, RGBA::new(0, 128, 0, 255));
+    assert!(style.box_.display.is_list_item());
+
+    let marker = style_for_pseudo_element(author_styles, &document, li, PseudoElement::Marker, &style);
+    assert_eq!(marker.color.color, RGBA::new(255, 0, 0, 255));
+}
+
+#[test]
+fn structural_pseudo_classes() {
+    let text_input = r#"<body lang="en-GB"><table><tr><td>1</td></tr><tr><td>2</td></tr><tr><td>3</td></tr></table><div><h1>h</h1><p>a</p><p>b</p><p></p></div><p lang="fr">c</p></body>"#;
+    let css_input = r#"
+        tr:nth-child(2n)              { color: red; }
+        tr:first-child                { color: blue; }
+        tr:last-child:not(:only-child) { color: yellow; }
+        p:nth-of-type(1)              { color: green; }
+        p:empty                       { color: white; }
+        :root                         { color: gray; }
+        div :lang(en)                 { background-color: red; }
+        p:lang(fr)                    { background-color: blue; }
+    "#;
+
+    let document = Document::parse_html(text_input.as_bytes());
+    let author_styles = &document.parse_stylesheets(Some(css_input));
+
+    let html = document.root_element();
+    validate_color!(html, RGBA::new(128, 128, 128, 255), document, author_styles);
+
+    let body = document[document[html].first_child.unwrap()].next_sibling.unwrap();
+    let table = document[body].first_child.unwrap();
+    // the parser inserts the implied `<tbody>`
+    let tbody = document[table].first_child.unwrap();
+    validate_element_type!(tbody, "tbody", document);
+    let rows: Vec<NodeId> = document
+        .node_and_following_siblings(document[tbody].first_child.unwrap())
+        .collect();
+    validate_color!(rows[0], RGBA::new(0, 0, 255, 255), document, author_styles);
+    validate_color!(rows[1], RGBA::new(255, 0, 0, 255), document, author_styles);
+    validate_color!(rows[2], RGBA::new(255, 255, 0, 255), document, author_styles);
+
+    let div = document[table].next_sibling.unwrap();
+    let children: Vec<NodeId> = document
+        .node_and_following_siblings(document[div].first_child.unwrap())
+        .collect();
+    validate_element_type!(children[1], "p", document);
+    validate_color!(children[0], RGBA::new(0, 0, 0, 255), document, author_styles);
+    validate_color!(children[1], RGBA::new(0, 128, 0, 255), document, author_styles);
+    validate_color!(children[2], RGBA::new(0, 0, 0, 255), document, author_styles);
+    validate_color!(children[3], RGBA::new(255, 255, 255, 255), document, author_styles);
+
+    let style = style_for_element(author_styles, &document, children[0], None);
+    assert_eq!(style.background.background_color, Color::RGBA(RGBA::new(255, 0, 0, 255)));
+    let p = document[div].next_sibling.unwrap();
+    let style = style_for_element(author_styles, &document, p, None);
+    assert_eq!(style.background.background_color, Color::RGBA(RGBA::new(0, 0, 255, 255)));
+}
+
+#[test]
+fn before_and_after_pseudo_elements() {
+    let text_input = r#"<body><p title="a">text</p></body>"#;
+    let css_input = r#"
+        p          { color: green; }
+        p::before  { content: "Note: "; }
+        p:after    { content: attr(title); color: red; }
+    "#;
+
+    let document = Document::parse_html(text_input.as_bytes());
+    let author_styles = &document.parse_stylesheets(Some(css_input));
+
+    let body = document[document.root_element()].first_child.unwrap();
+    let body = document[body].next_sibling.unwrap();
+    let p = document[body].first_child.unwrap();
+    validate_element_type!(p, "p", document);
+
+    let style = style_for_element(author_styles, &document, p, None);
+    assert_eq!(style.content_reset.content, Content::Normal);
+
+    // pseudo-elements inherit from their originating element
+    let before = style_for_pseudo_element(author_styles, &document, p, PseudoElement::Before, &style);
+    assert_eq!(before.color.color, RGBA::new(0, 128, 0, 255));
+    assert_eq!(before.content_reset.content, Content::Items(vec![ContentItem::String("Note: ".into())]));
+
+    // the CSS 2 single-colon syntax is supported as well
+    let after = style_for_pseudo_element(author_styles, &document, p, PseudoElement::After, &style);
+    assert_eq!(after.color.color, RGBA::new(255, 0, 0, 255));
+    assert_eq!(after.content_reset.content, Content::Items(vec![ContentItem::Attr("title".into())]));
+}
+
+#[test]
+fn first_line_and_first_letter_pseudo_elements() {
+    let text_input = r#"<body><p>first</p><div>second</div></body>"#;
+    let css_input = r#"
+        p                { color: green; }
+        p::first-line    { font-weight: bold; }
+        p:first-letter   { color: red; initial-letter: 2; }
+    "#;
+
+    let document = Document::parse_html(text_input.as_bytes());
+    let author_styles = &document.parse_stylesheets(Some(css_input));
+
+    let body = document[document.root_element()].first_child.unwrap();
+    let body = document[body].next_sibling.unwrap();
+    let p = document[body].first_child.unwrap();
+    let div = document[p].next_sibling.unwrap();
+    validate_element_type!(div, "div", document);
+
+    // these pseudo-elements are only generated if some rule applies to them
+    assert!(pseudo_element_has_rules(author_styles, &document, p, PseudoElement::FirstLine));
+    assert!(pseudo_element_has_rules(author_styles, &document, p, PseudoElement::FirstLetter));
+    assert!(!pseudo_element_has_rules(author_styles, &document, div, PseudoElement::FirstLine));
+
+    let style = style_for_element(author_styles, &document, p, None);
+    let first_line = style_for_pseudo_element(author_styles, &document, p, PseudoElement::FirstLine, &style);
+    assert_eq!(first_line.font.font_weight, FontWeight::Bold);
+    assert_eq!(first_line.color.color, RGBA::new(0, 128, 0, 255));
+
+    let first_letter =
+        style_for_pseudo_element(author_styles, &document, p, PseudoElement::FirstLetter, &first_line);
+    assert_eq!(first_letter.font.font_weight, FontWeight::Bold);
+    assert_eq!(first_letter.color.color, RGBA::new(255, 0, 0, 255));
+    assert_eq!(first_letter.line_reset.initial_letter, InitialLetter::Lines { size: 2., sink: 2 });
+}
+
+#[test]
+fn custom_properties() {
+    let text_input = r#"<body><p>a<em>b</em><span>c</span></p><div>d</div></body>"#;
+    let css_input = r#"
+        :root { --accent: rgb(0, 128, 0); --muted: var(--accent); --a: var(--b); --b: var(--a); }
+        p     { color: var(--muted); margin: var(--gap, 2px) 0; }
+        em    { color: var(--missing, rgb(0, 0, 255)) !important; }
+        span  { color: var(--a, red); background-color: var(--missing); }
+        div   { --accent: blue; --size: 20px; color: var(--accent); font-size: var(--size); }
+        div   { border-top: var(--size) solid rgb(255, var(--zero, 0), 0); }
+    "#;
+
+    let document = Document::parse_html(text_input.as_bytes());
+    let author_styles = &document.parse_stylesheets(Some(css_input));
+    let style_of = |node, parent| style_for_element(author_styles, &document, node, parent);
+
+    let html = document.root_element();
+    let html_style = style_of(html, None);
+    let body = document[document[html].first_child.unwrap()].next_sibling.unwrap();
+    let body_style = style_of(body, Some(&html_style));
+    // custom properties are inherited
+    assert_eq!(body_style.custom_properties.get("--muted").unwrap(), "rgb(0, 128, 0)");
+    // properties in a reference cycle are invalid
+    assert!(body_style.custom_properties.get("--a").is_none());
+
+    let p = document[body].first_child.unwrap();
+    validate_element_type!(p, "p", document);
+    let p_style = style_of(p, Some(&body_style));
+    assert_eq!(p_style.color.color, RGBA::new(0, 128, 0, 255));
+    assert!(matches!(p_style.margin.margin_top, LengthOrPercentageOrAuto::Length(Length { px }) if px == 2.));
+
+    let em = document[document[p].first_child.unwrap()].next_sibling.unwrap();
+    let em_style = style_of(em, Some(&p_style));
+    assert_eq!(em_style.color.color, RGBA::new(0, 0, 255, 255));
+
+    // an invalid substitution behaves as `unset`
+    let span = document[em].next_sibling.unwrap();
+    let span_style = style_of(span, Some(&p_style));
+    assert_eq!(span_style.color.color, RGBA::new(255, 0, 0, 255));
+    assert_eq!(span_style.background.background_color, Color::RGBA(RGBA::transparent()));
+
+    let div = document[p].next_sibling.unwrap();
+    let div_style = style_of(div, Some(&body_style));
+    assert_eq!(div_style.color.color, RGBA::new(0, 0, 255, 255));
+    assert_eq!(div_style.font.font_size.0.px, 20.);
+    assert_eq!(div_style.border.border_top_color, Color::RGBA(RGBA::new(255, 0, 0, 255)));
+}
@@ -1,15 +1,249 @@
+use super::image::*;
+use super::length::*;
+use super::CascadeContext;
 use crate::style::errors::PropertyParseError;
 use cssparser::{Color, Parser};
 
+/// The values of a background property for each background layer, the
+/// first on top. Properties with fewer values than `background-image`
+/// repeat their list of values.
+/// https://drafts.csswg.org/css-backgrounds/#layering
+#[derive(Clone, Debug)]
+pub(crate) struct Layers<T>(pub Vec<T>);
+
+impl<T> Layers<T> {
+    pub(crate) fn layer(&self, index: usize) -> &T {
+        &self.0[index % self.0.len()]
+    }
+}
+
+impl<T> From<T> for Layers<T> {
+    fn from(value: T) -> Self {
+        Layers(vec![value])
+    }
+}
+
+impl<T: super::Parse> super::Parse for Layers<T> {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        Ok(Layers(parser.parse_comma_separated(|parser| T::parse(parser))?))
+    }
+}
+
+impl<T: super::SpecifiedValue> super::SpecifiedValue for Layers<T> {
+    type SpecifiedValue = Layers<T::SpecifiedValue>;
+}
+
+impl<T: super::FromSpecified> super::FromSpecified for Layers<T> {
+    fn from_specified(specified: &Layers<T::SpecifiedValue>, context: &CascadeContext) -> Self {
+        Layers(
+            specified
+                .0
+                .iter()
+                .map(|value| T::from_specified(value, context))
+                .collect(),
+        )
+    }
+}
+
+/// https://drafts.csswg.org/css-backgrounds/#typedef-repeat-style
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum RepeatStyle {
+    Repeat,
+    Space,
+    Round,
+    NoRepeat,
+}
+
+/// https://drafts.csswg.org/css-backgrounds/#background-repeat
+#[derive(Copy, Clone, Debug, Eq, PartialEq, SpecifiedAsComputed)]
+pub(crate) struct BackgroundRepeat {
+    pub x: RepeatStyle,
+    pub y: RepeatStyle,
+}
+
+impl BackgroundRepeat {
+    pub(in crate::style) const REPEAT: Self = BackgroundRepeat {
+        x: RepeatStyle::Repeat,
+        y: RepeatStyle::Repeat,
+    };
+}
+
+impl super::Parse for BackgroundRepeat {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("repeat-x")).is_ok() {
+            return Ok(BackgroundRepeat {
+                x: RepeatStyle::Repeat,
+                y: RepeatStyle::NoRepeat,
+            });
+        }
+        if parser.try_parse(|parser| parser.expect_ident_matching("repeat-y")).is_ok() {
+            return Ok(BackgroundRepeat {
+                x: RepeatStyle::NoRepeat,
+                y: RepeatStyle::Repeat,
+            });
+        }
+        let x = <RepeatStyle as super::Parse>::parse(parser)?;
+        let y = parser.try_parse(<RepeatStyle as super::Parse>::parse).unwrap_or(x);
+        Ok(BackgroundRepeat { x, y })
+    }
+}
+
+/// https://drafts.csswg.org/css-backgrounds/#background-size
+#[derive(Copy, Clone, Debug)]
+pub(in crate::style) enum SpecifiedBackgroundSize {
+    Cover,
+    Contain,
+    Explicit(SpecifiedLengthOrPercentageOrAuto, SpecifiedLengthOrPercentageOrAuto),
+}
+
+#[derive(Copy, Clone, Debug, FromSpecified)]
+pub(crate) enum BackgroundSize {
+    Cover,
+    Contain,
+    Explicit(LengthOrPercentageOrAuto, LengthOrPercentageOrAuto),
+}
+
+impl BackgroundSize {
+    pub(in crate::style) const AUTO: Self =
+        BackgroundSize::Explicit(LengthOrPercentageOrAuto::Auto, LengthOrPercentageOrAuto::Auto);
+}
+
+impl super::Parse for SpecifiedBackgroundSize {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("cover")).is_ok() {
+            return Ok(SpecifiedBackgroundSize::Cover);
+        }
+        if parser.try_parse(|parser| parser.expect_ident_matching("contain")).is_ok() {
+            return Ok(SpecifiedBackgroundSize::Contain);
+        }
+        let width = <SpecifiedLengthOrPercentageOrAuto as super::Parse>::parse(parser)?;
+        let height = parser
+            .try_parse(<SpecifiedLengthOrPercentageOrAuto as super::Parse>::parse)
+            .unwrap_or(SpecifiedLengthOrPercentageOrAuto::Auto);
+        Ok(SpecifiedBackgroundSize::Explicit(width, height))
+    }
+}
+
+/// The box a background is positioned in (`background-origin`)
+/// or painted within (`background-clip`).
+/// https://drafts.csswg.org/css-backgrounds/#typedef-box
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+#[allow(clippy::enum_variant_names)]
+pub(crate) enum BackgroundBox {
+    BorderBox,
+    PaddingBox,
+    ContentBox,
+}
+
+/// The `background` shorthand. Only the last layer may have a color.
+/// A single box sets both `background-origin` and `background-clip`.
+/// https://drafts.csswg.org/css-backgrounds/#background
 #[derive(Debug)]
 pub(in crate::style) struct Background {
     pub color: Option<Color>,
+    pub image: Option<Layers<SpecifiedImageValue>>,
+    pub position: Option<Layers<SpecifiedPositionValue>>,
+    pub size: Option<Layers<SpecifiedBackgroundSize>>,
+    pub repeat: Option<Layers<BackgroundRepeat>>,
+    pub origin: Option<Layers<BackgroundBox>>,
+    pub clip: Option<Layers<BackgroundBox>>,
+}
+
+/// One layer of the `background` shorthand, with omitted values at their
+/// initial values.
+struct BackgroundLayer {
+    color: Option<Color>,
+    image: SpecifiedImageValue,
+    position: SpecifiedPositionValue,
+    size: SpecifiedBackgroundSize,
+    repeat: BackgroundRepeat,
+    origin: BackgroundBox,
+    clip: BackgroundBox,
 }
 
 impl super::Parse for Background {
     fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        let layers = parser.parse_comma_separated(parse_background_layer)?;
+        let (last, others) = layers.split_last().unwrap();
+        if others.iter().any(|layer| layer.color.is_some()) {
+            return Err(parser.new_error_for_next_token());
+        }
         Ok(Background {
-            color: Some(Color::parse(parser)?),
+            color: last.color,
+            image: Some(Layers(layers.iter().map(|layer| layer.image.clone()).collect())),
+            position: Some(Layers(layers.iter().map(|layer| layer.position).collect())),
+            size: Some(Layers(layers.iter().map(|layer| layer.size).collect())),
+            repeat: Some(Layers(layers.iter().map(|layer| layer.repeat).collect())),
+            origin: Some(Layers(layers.iter().map(|layer| layer.origin).collect())),
+            clip: Some(Layers(layers.iter().map(|layer| layer.clip).collect())),
         })
     }
 }
+
+fn parse_background_layer<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+) -> Result<BackgroundLayer, PropertyParseError<'i>> {
+    let mut color = None;
+    let mut image = None;
+    let mut position = None;
+    let mut size = None;
+    let mut repeat = None;
+    let mut boxes = vec![];
+    loop {
+        if color.is_none() {
+            if let Ok(value) = parser.try_parse(Color::parse) {
+                color = Some(value);
+                continue;
+            }
+        }
+        if image.is_none() {
+            if let Ok(value) = parser.try_parse(<SpecifiedImageValue as super::Parse>::parse) {
+                image = Some(value);
+                continue;
+            }
+        }
+        if position.is_none() {
+            if let Ok(value) = parser.try_parse(<SpecifiedPositionValue as super::Parse>::parse) {
+                position = Some(value);
+                // the size may only follow the position
+                if parser.try_parse(|parser| parser.expect_delim('/')).is_ok() {
+                    size = Some(<SpecifiedBackgroundSize as super::Parse>::parse(parser)?);
+                }
+                continue;
+            }
+        }
+        if repeat.is_none() {
+            if let Ok(value) = parser.try_parse(<BackgroundRepeat as super::Parse>::parse) {
+                repeat = Some(value);
+                continue;
+            }
+        }
+        if boxes.len() < 2 {
+            if let Ok(value) = parser.try_parse(<BackgroundBox as super::Parse>::parse) {
+                boxes.push(value);
+                continue;
+            }
+        }
+        break;
+    }
+    if color.is_none() && image.is_none() && position.is_none() && repeat.is_none() && boxes.is_empty() {
+        return Err(parser.new_error_for_next_token());
+    }
+    let (origin, clip) = match boxes[..] {
+        [] => (BackgroundBox::PaddingBox, BackgroundBox::BorderBox),
+        [both] => (both, both),
+        [origin, clip, ..] => (origin, clip),
+    };
+    Ok(BackgroundLayer {
+        color,
+        image: image.unwrap_or(SpecifiedImageValue::None),
+        position: position.unwrap_or(SpecifiedPositionValue::TOP_LEFT),
+        size: size.unwrap_or(SpecifiedBackgroundSize::Explicit(
+            SpecifiedLengthOrPercentageOrAuto::Auto,
+            SpecifiedLengthOrPercentageOrAuto::Auto,
+        )),
+        repeat: repeat.unwrap_or(BackgroundRepeat::REPEAT),
+        origin,
+        clip,
+    })
+}
@@ -1,3 +1,4 @@
+use super::generic::FourSides;
 use super::length::*;
 use crate::style::errors::PropertyParseError;
 use cssparser::{Color, Parser};
@@ -6,9 +7,15 @@ use cssparser::{Color, Parser};
 #[derive(Copy, Clone, Debug, Parse, SpecifiedAsComputed, PartialEq)]
 pub(crate) enum LineStyle {
     None,
-    Solid,
+    Hidden,
     Dotted,
     Dashed,
+    Solid,
+    Double,
+    Groove,
+    Ridge,
+    Inset,
+    Outset,
 }
 
 #[derive(Copy, Clone, Debug, Parse)]
@@ -30,7 +37,7 @@ impl LineWidth {
         LineWidth(LengthOrPercentage::Length(Length { px: 3. }));
 
     pub(in crate::style) fn fixup(&mut self, style: LineStyle) {
-        if let LineStyle::None = style {
+        if let LineStyle::None | LineStyle::Hidden = style {
             self.0 = LengthOrPercentage::Length(Length::zero())
         }
     }
@@ -50,6 +57,72 @@ impl super::Parse for SpecifiedLineWidth {
     }
 }
 
+/// https://drafts.csswg.org/css-backgrounds/#border-radius
+#[derive(Copy, Clone, Debug)]
+pub(in crate::style) struct SpecifiedBorderCornerRadius {
+    pub horizontal: SpecifiedLengthOrPercentage,
+    pub vertical: SpecifiedLengthOrPercentage,
+}
+
+/// The radii of the quarter ellipse of a box corner. Percentages refer to
+/// the width and height of the border box.
+#[derive(Copy, Clone, Debug, FromSpecified)]
+pub(crate) struct BorderCornerRadius {
+    pub horizontal: LengthOrPercentage,
+    pub vertical: LengthOrPercentage,
+}
+
+impl BorderCornerRadius {
+    pub(in crate::style) const ZERO: Self = BorderCornerRadius {
+        horizontal: LengthOrPercentage::Length(Length { px: 0. }),
+        vertical: LengthOrPercentage::Length(Length { px: 0. }),
+    };
+}
+
+impl super::Parse for SpecifiedBorderCornerRadius {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        let horizontal = <SpecifiedLengthOrPercentage as super::Parse>::parse(parser)?;
+        let vertical = parser
+            .try_parse(<SpecifiedLengthOrPercentage as super::Parse>::parse)
+            .unwrap_or(horizontal);
+        Ok(SpecifiedBorderCornerRadius {
+            horizontal,
+            vertical,
+        })
+    }
+}
+
+/// The `border-radius` shorthand: up to four horizontal radii, optionally
+/// followed by a slash and up to four vertical radii.
+#[derive(Debug)]
+pub(in crate::style) struct BorderRadius {
+    pub top_left: SpecifiedBorderCornerRadius,
+    pub top_right: SpecifiedBorderCornerRadius,
+    pub bottom_right: SpecifiedBorderCornerRadius,
+    pub bottom_left: SpecifiedBorderCornerRadius,
+}
+
+impl super::Parse for BorderRadius {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        let horizontal = <FourSides<SpecifiedLengthOrPercentage> as super::Parse>::parse(parser)?;
+        let vertical = if parser.try_parse(|parser| parser.expect_delim('/')).is_ok() {
+            <FourSides<SpecifiedLengthOrPercentage> as super::Parse>::parse(parser)?
+        } else {
+            horizontal.clone()
+        };
+        let corner = |horizontal, vertical| SpecifiedBorderCornerRadius {
+            horizontal,
+            vertical,
+        };
+        Ok(BorderRadius {
+            top_left: corner(horizontal.top, vertical.top),
+            top_right: corner(horizontal.right, vertical.right),
+            bottom_right: corner(horizontal.bottom, vertical.bottom),
+            bottom_left: corner(horizontal.left, vertical.left),
+        })
+    }
+}
+
 macro_rules! parse_one_or_more {
     ($type: ty { $( $field: ident, )+ }) => {
         impl crate::style::values::Parse for $type {
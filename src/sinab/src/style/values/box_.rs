@@ -13,7 +13,7 @@ pub(crate) enum Display {
 
 #[allow(dead_code)]
 fn _static_assert_size_of() {
-    let _ = std::mem::transmute::<Display, [u8; 2]>;
+    let _ = std::mem::transmute::<Display, [u8; 3]>;
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -21,10 +21,10 @@ pub(crate) enum DisplayGeneratingBox {
     OutsideInside {
         outside: DisplayOutside,
         inside: DisplayInside,
-        // list_item: bool,
+        list_item: bool,
     },
-    // Layout-internal display types go here:
     // https://drafts.csswg.org/css-display-3/#layout-specific-display
+    LayoutInternal(DisplayLayoutInternal),
 }
 
 /// https://drafts.csswg.org/css-display-3/#outer-role
@@ -39,25 +39,70 @@ pub(crate) enum DisplayOutside {
 pub(crate) enum DisplayInside {
     Flow,
     FlowRoot,
+    Table,
+    Flex,
+    Grid,
+}
+
+/// https://drafts.csswg.org/css-display-3/#layout-specific-display
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::enum_variant_names)]
+pub(crate) enum DisplayLayoutInternal {
+    TableRowGroup,
+    TableHeaderGroup,
+    TableFooterGroup,
+    TableRow,
+    TableCell,
+    TableColumnGroup,
+    TableColumn,
+    TableCaption,
 }
 
 impl Display {
     pub const INITIAL: Self = Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
         outside: DisplayOutside::Inline,
         inside: DisplayInside::Flow,
+        list_item: false,
     });
 
+    /// https://drafts.csswg.org/css-display-3/#list-items
+    pub fn is_list_item(&self) -> bool {
+        match *self {
+            Display::GeneratingBox(DisplayGeneratingBox::OutsideInside { list_item, .. }) => {
+                list_item
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_table(&self) -> bool {
+        match *self {
+            Display::GeneratingBox(DisplayGeneratingBox::OutsideInside { inside, .. }) => {
+                inside == DisplayInside::Table
+            }
+            _ => false,
+        }
+    }
+
     /// https://drafts.csswg.org/css-display-3/#blockify
     pub fn blockify(&self) -> Self {
         match *self {
             Display::GeneratingBox(value) => Display::GeneratingBox(match value {
-                DisplayGeneratingBox::OutsideInside { outside: _, inside } => {
-                    DisplayGeneratingBox::OutsideInside {
-                        outside: DisplayOutside::Block,
-                        inside,
-                    }
-                }
-                // other => other,
+                DisplayGeneratingBox::OutsideInside {
+                    outside: _,
+                    inside,
+                    list_item,
+                } => DisplayGeneratingBox::OutsideInside {
+                    outside: DisplayOutside::Block,
+                    inside,
+                    list_item,
+                },
+                // Layout-internal boxes compute to `block`
+                DisplayGeneratingBox::LayoutInternal(_) => DisplayGeneratingBox::OutsideInside {
+                    outside: DisplayOutside::Block,
+                    inside: DisplayInside::Flow,
+                    list_item: false,
+                },
             }),
             other => other,
         }
@@ -77,33 +122,127 @@ impl Display {
 
 impl super::Parse for Display {
     fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
-        let ident = parser.expect_ident()?;
-        match &**ident {
-            "none" => Ok(Display::None),
-            "contents" => Ok(Display::Contents),
-            "block" => Ok(Display::GeneratingBox(
-                DisplayGeneratingBox::OutsideInside {
-                    outside: DisplayOutside::Block,
-                    inside: DisplayInside::Flow,
-                },
-            )),
-            "flow-root" => Ok(Display::GeneratingBox(
-                DisplayGeneratingBox::OutsideInside {
-                    outside: DisplayOutside::Block,
+        let ident = parser.expect_ident()?.clone();
+        match &*ident {
+            "none" => return Ok(Display::None),
+            "contents" => return Ok(Display::Contents),
+            "inline-block" => {
+                return Ok(Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                    outside: DisplayOutside::Inline,
                     inside: DisplayInside::FlowRoot,
-                },
-            )),
-            "inline" => Ok(Display::GeneratingBox(
-                DisplayGeneratingBox::OutsideInside {
+                    list_item: false,
+                }))
+            }
+            "inline-table" => {
+                return Ok(Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
                     outside: DisplayOutside::Inline,
-                    inside: DisplayInside::Flow,
-                },
-            )),
-            _ => {
-                let token = cssparser::Token::Ident(ident.clone());
-                Err(parser.new_unexpected_token_error(token))
+                    inside: DisplayInside::Table,
+                    list_item: false,
+                }))
+            }
+            "inline-flex" => {
+                return Ok(Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                    outside: DisplayOutside::Inline,
+                    inside: DisplayInside::Flex,
+                    list_item: false,
+                }))
             }
+            "inline-grid" => {
+                return Ok(Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                    outside: DisplayOutside::Inline,
+                    inside: DisplayInside::Grid,
+                    list_item: false,
+                }))
+            }
+            _ => {}
+        }
+        if let Some(internal) = parse_display_layout_internal(&ident) {
+            return Ok(Display::GeneratingBox(DisplayGeneratingBox::LayoutInternal(internal)));
+        }
+        // https://drafts.csswg.org/css-display-3/#display-value-summary
+        // Multi-keyword values such as `inline flow-root` or `list-item block`,
+        // in any order.
+        let mut outside = None;
+        let mut inside = None;
+        let mut list_item = false;
+        if !parse_display_keyword(&ident, &mut outside, &mut inside, &mut list_item) {
+            let token = cssparser::Token::Ident(ident);
+            return Err(parser.new_unexpected_token_error(token));
+        }
+        while parser
+            .try_parse(|parser| {
+                let ident = parser.expect_ident().map_err(|_| ())?;
+                if parse_display_keyword(ident, &mut outside, &mut inside, &mut list_item) {
+                    Ok(())
+                } else {
+                    Err(())
+                }
+            })
+            .is_ok()
+        {}
+        let inside = inside.unwrap_or(DisplayInside::Flow);
+        // Only flow layouts can generate a marker box.
+        if list_item && !matches!(inside, DisplayInside::Flow | DisplayInside::FlowRoot) {
+            return Err(parser.new_error_for_next_token());
         }
+        let outside = outside.unwrap_or(DisplayOutside::Block);
+        Ok(Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+            outside,
+            inside,
+            list_item,
+        }))
+    }
+}
+
+/// Records one keyword of a multi-keyword `display` value.
+/// Returns `false` if the keyword is unknown or repeated.
+fn parse_display_keyword(
+    ident: &str,
+    outside: &mut Option<DisplayOutside>,
+    inside: &mut Option<DisplayInside>,
+    list_item: &mut bool,
+) -> bool {
+    if let Some(value) = parse_display_outside(ident) {
+        outside.replace(value).is_none()
+    } else if let Some(value) = parse_display_inside(ident) {
+        inside.replace(value).is_none()
+    } else if ident == "list-item" {
+        !std::mem::replace(list_item, true)
+    } else {
+        false
+    }
+}
+
+fn parse_display_outside(ident: &str) -> Option<DisplayOutside> {
+    match ident {
+        "block" => Some(DisplayOutside::Block),
+        "inline" => Some(DisplayOutside::Inline),
+        _ => None,
+    }
+}
+
+fn parse_display_inside(ident: &str) -> Option<DisplayInside> {
+    match ident {
+        "flow" => Some(DisplayInside::Flow),
+        "flow-root" => Some(DisplayInside::FlowRoot),
+        "table" => Some(DisplayInside::Table),
+        "flex" => Some(DisplayInside::Flex),
+        "grid" => Some(DisplayInside::Grid),
+        _ => None,
+    }
+}
+
+fn parse_display_layout_internal(ident: &str) -> Option<DisplayLayoutInternal> {
+    match ident {
+        "table-row-group" => Some(DisplayLayoutInternal::TableRowGroup),
+        "table-header-group" => Some(DisplayLayoutInternal::TableHeaderGroup),
+        "table-footer-group" => Some(DisplayLayoutInternal::TableFooterGroup),
+        "table-row" => Some(DisplayLayoutInternal::TableRow),
+        "table-cell" => Some(DisplayLayoutInternal::TableCell),
+        "table-column-group" => Some(DisplayLayoutInternal::TableColumnGroup),
+        "table-column" => Some(DisplayLayoutInternal::TableColumn),
+        "table-caption" => Some(DisplayLayoutInternal::TableCaption),
+        _ => None,
     }
 }
 
@@ -124,6 +263,25 @@ impl Float {
     }
 }
 
+/// https://drafts.csswg.org/css2/visuren.html#propdef-clear
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum Clear {
+    None,
+    Left,
+    Right,
+    Both,
+}
+
+impl Clear {
+    pub fn clears_left(self) -> bool {
+        matches!(self, Clear::Left | Clear::Both)
+    }
+
+    pub fn clears_right(self) -> bool {
+        matches!(self, Clear::Right | Clear::Both)
+    }
+}
+
 /// https://drafts.csswg.org/css-position-3/#position-property
 #[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
 pub(crate) enum Position {
@@ -141,3 +299,68 @@ impl Position {
         self == Position::Absolute
     }
 }
+
+/// https://drafts.csswg.org/css2/visuren.html#z-index
+#[derive(Copy, Clone, Debug, Eq, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum ZIndex {
+    Auto,
+    Integer(i32),
+}
+
+impl super::Parse for ZIndex {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("auto")).is_ok() {
+            return Ok(ZIndex::Auto);
+        }
+        Ok(ZIndex::Integer(parser.expect_integer()?))
+    }
+}
+
+/// https://drafts.csswg.org/css-sizing/#box-sizing
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum BoxSizing {
+    ContentBox,
+    BorderBox,
+}
+
+/// https://drafts.csswg.org/css-overflow-3/#propdef-overflow
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum Overflow {
+    Visible,
+    Hidden,
+    Clip,
+}
+
+/// https://drafts.csswg.org/css-images-3/#the-object-fit
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum ObjectFit {
+    Fill,
+    Contain,
+    Cover,
+    None,
+    ScaleDown,
+}
+
+/// The opacity of a box and its descendants, composited as a group.
+/// Numbers and percentages are clamped to the range from 0 to 1.
+/// https://drafts.csswg.org/css-color/#transparency
+#[derive(Copy, Clone, Debug, PartialEq, SpecifiedAsComputed)]
+pub(crate) struct Opacity(pub f32);
+
+impl super::Parse for Opacity {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        let value = match parser.try_parse(|parser| parser.expect_number()) {
+            Ok(value) => value,
+            Err(_) => parser.expect_percentage()?,
+        };
+        Ok(Opacity(value.clamp(0., 1.)))
+    }
+}
+
+/// https://drafts.csswg.org/css2/visufx.html#visibility
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum Visibility {
+    Visible,
+    Hidden,
+    Collapse,
+}
@@ -0,0 +1,195 @@
+use super::list::ListStyleType;
+use crate::style::errors::PropertyParseError;
+use cssparser::{Parser, Token};
+
+/// The value of `content`. On `::before` and `::after`, `normal` computes
+/// to `none`, and neither generates a box.
+/// https://drafts.csswg.org/css-content/#content-property
+#[derive(Clone, Debug, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum Content {
+    Normal,
+    None,
+    Items(Vec<ContentItem>),
+}
+
+/// https://drafts.csswg.org/css-content/#typedef-content-content-list
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ContentItem {
+    String(String),
+    Url(String),
+    /// The value of an attribute of the originating element,
+    /// or the empty string if it has no such attribute.
+    Attr(String),
+    /// https://drafts.csswg.org/css-lists-3/#counter-functions
+    Counter(String, ListStyleType),
+    Counters(String, String, ListStyleType),
+    OpenQuote,
+    CloseQuote,
+    NoOpenQuote,
+    NoCloseQuote,
+}
+
+impl super::Parse for Content {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("normal")).is_ok() {
+            return Ok(Content::Normal);
+        }
+        if parser.try_parse(|parser| parser.expect_ident_matching("none")).is_ok() {
+            return Ok(Content::None);
+        }
+        let mut items = vec![parse_content_item(parser)?];
+        while let Ok(item) = parser.try_parse(parse_content_item) {
+            items.push(item);
+        }
+        Ok(Content::Items(items))
+    }
+}
+
+fn parse_content_item<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+) -> Result<ContentItem, PropertyParseError<'i>> {
+    if let Ok(url) = parser.try_parse(|parser| parser.expect_url().map(|url| url.to_string())) {
+        return Ok(ContentItem::Url(url));
+    }
+    let location = parser.current_source_location();
+    match parser.next()?.clone() {
+        Token::QuotedString(string) => Ok(ContentItem::String(string.to_string())),
+        Token::Ident(ident) => match_ignore_ascii_case!(&ident,
+            "open-quote" => Ok(ContentItem::OpenQuote),
+            "close-quote" => Ok(ContentItem::CloseQuote),
+            "no-open-quote" => Ok(ContentItem::NoOpenQuote),
+            "no-close-quote" => Ok(ContentItem::NoCloseQuote),
+            _ => Err(location.new_unexpected_token_error(Token::Ident(ident.clone()))),
+        ),
+        Token::Function(name) => parser.parse_nested_block(|parser| {
+            match_ignore_ascii_case!(&name,
+                "attr" => Ok(ContentItem::Attr(parser.expect_ident()?.to_string())),
+                "counter" => {
+                    let counter = parser.expect_ident()?.to_string();
+                    Ok(ContentItem::Counter(counter, parse_counter_style(parser)?))
+                },
+                "counters" => {
+                    let counter = parser.expect_ident()?.to_string();
+                    parser.expect_comma()?;
+                    let separator = parser.expect_string()?.to_string();
+                    Ok(ContentItem::Counters(counter, separator, parse_counter_style(parser)?))
+                },
+                _ => Err(location.new_unexpected_token_error(Token::Function(name.clone()))),
+            )
+        }),
+        token => Err(location.new_unexpected_token_error(token)),
+    }
+}
+
+/// The optional counter style argument of `counter()` and `counters()`.
+fn parse_counter_style<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+) -> Result<ListStyleType, PropertyParseError<'i>> {
+    if parser.try_parse(|parser| parser.expect_comma()).is_err() {
+        return Ok(ListStyleType::Decimal);
+    }
+    <ListStyleType as super::Parse>::parse(parser)
+}
+
+/// The value of `counter-reset`: counters instantiated on the element,
+/// with their initial values.
+/// https://drafts.csswg.org/css-lists-3/#counter-reset
+#[derive(Clone, Debug, PartialEq, SpecifiedAsComputed)]
+pub(crate) struct CounterReset(pub Vec<(String, i32)>);
+
+/// The value of `counter-increment`.
+/// https://drafts.csswg.org/css-lists-3/#increment-and-set
+#[derive(Clone, Debug, PartialEq, SpecifiedAsComputed)]
+pub(crate) struct CounterIncrement(pub Vec<(String, i32)>);
+
+/// The value of `counter-set`.
+/// https://drafts.csswg.org/css-lists-3/#increment-and-set
+#[derive(Clone, Debug, PartialEq, SpecifiedAsComputed)]
+pub(crate) struct CounterSet(pub Vec<(String, i32)>);
+
+impl super::Parse for CounterReset {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        parse_counter_list(parser, 0).map(CounterReset)
+    }
+}
+
+impl super::Parse for CounterIncrement {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        parse_counter_list(parser, 1).map(CounterIncrement)
+    }
+}
+
+impl super::Parse for CounterSet {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        parse_counter_list(parser, 0).map(CounterSet)
+    }
+}
+
+/// `none | [ <counter-name> <integer>? ]+`, where a counter without an
+/// integer takes the given default value.
+fn parse_counter_list<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+    default: i32,
+) -> Result<Vec<(String, i32)>, PropertyParseError<'i>> {
+    if parser.try_parse(|parser| parser.expect_ident_matching("none")).is_ok() {
+        return Ok(Vec::new());
+    }
+    let mut counters = Vec::new();
+    loop {
+        let name = match parser.try_parse(|parser| parser.expect_ident().map(|s| s.to_string())) {
+            Ok(name) => name,
+            Err(_) if !counters.is_empty() => break,
+            Err(error) => return Err(error.into()),
+        };
+        let value = parser.try_parse(|parser| parser.expect_integer()).unwrap_or(default);
+        counters.push((name, value));
+    }
+    Ok(counters)
+}
+
+/// The value of `quotes`. `auto` uses the English quotation marks.
+/// https://drafts.csswg.org/css-content/#quotes-property
+#[derive(Clone, Debug, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum Quotes {
+    Auto,
+    None,
+    Pairs(Vec<(String, String)>),
+}
+
+impl Quotes {
+    /// The opening and closing quotation marks at the given nesting depth,
+    /// the last pair repeating for deeper levels.
+    pub(crate) fn at_depth(&self, depth: usize) -> (&str, &str) {
+        const AUTO: [(&str, &str); 2] = [("\u{201C}", "\u{201D}"), ("\u{2018}", "\u{2019}")];
+        match self {
+            Quotes::Auto => AUTO[depth.min(AUTO.len() - 1)],
+            Quotes::None => ("", ""),
+            Quotes::Pairs(pairs) => {
+                let (open, close) = &pairs[depth.min(pairs.len() - 1)];
+                (open, close)
+            }
+        }
+    }
+}
+
+impl super::Parse for Quotes {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("auto")).is_ok() {
+            return Ok(Quotes::Auto);
+        }
+        if parser.try_parse(|parser| parser.expect_ident_matching("none")).is_ok() {
+            return Ok(Quotes::None);
+        }
+        let mut pairs = vec![];
+        loop {
+            let open = match parser.try_parse(|parser| parser.expect_string().map(|s| s.to_string())) {
+                Ok(open) => open,
+                Err(_) if !pairs.is_empty() => break,
+                Err(error) => return Err(error.into()),
+            };
+            let close = parser.expect_string()?.to_string();
+            pairs.push((open, close));
+        }
+        Ok(Quotes::Pairs(pairs))
+    }
+}
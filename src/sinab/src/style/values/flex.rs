@@ -0,0 +1,165 @@
+use super::length::*;
+use crate::style::errors::PropertyParseError;
+use cssparser::Parser;
+
+/// https://drafts.csswg.org/css-flexbox-1/#flex-direction-property
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum FlexDirection {
+    Row,
+    RowReverse,
+    Column,
+    ColumnReverse,
+}
+
+/// https://drafts.csswg.org/css-flexbox-1/#flex-wrap-property
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum FlexWrap {
+    Nowrap,
+    Wrap,
+    WrapReverse,
+}
+
+/// https://drafts.csswg.org/css-flexbox-1/#justify-content-property
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum JustifyContent {
+    FlexStart,
+    FlexEnd,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+/// Also used for `justify-items` in grid layout.
+/// https://drafts.csswg.org/css-align-3/#align-items-property
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum AlignItems {
+    Stretch,
+    Start,
+    End,
+    FlexStart,
+    FlexEnd,
+    Center,
+    Baseline,
+}
+
+/// Also used for `justify-self` in grid layout.
+/// https://drafts.csswg.org/css-align-3/#align-self-property
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum AlignSelf {
+    Auto,
+    Stretch,
+    Start,
+    End,
+    FlexStart,
+    FlexEnd,
+    Center,
+    Baseline,
+}
+
+impl AlignSelf {
+    /// `auto` computes to the `align-items` value of the parent.
+    pub fn resolve(self, align_items: AlignItems) -> AlignItems {
+        match self {
+            AlignSelf::Auto => align_items,
+            AlignSelf::Stretch => AlignItems::Stretch,
+            AlignSelf::Start => AlignItems::Start,
+            AlignSelf::End => AlignItems::End,
+            AlignSelf::FlexStart => AlignItems::FlexStart,
+            AlignSelf::FlexEnd => AlignItems::FlexEnd,
+            AlignSelf::Center => AlignItems::Center,
+            AlignSelf::Baseline => AlignItems::Baseline,
+        }
+    }
+}
+
+impl FlexDirection {
+    pub fn is_column(self) -> bool {
+        matches!(self, FlexDirection::Column | FlexDirection::ColumnReverse)
+    }
+
+    pub fn is_reverse(self) -> bool {
+        matches!(self, FlexDirection::RowReverse | FlexDirection::ColumnReverse)
+    }
+}
+
+/// The `flex-flow` shorthand.
+/// https://drafts.csswg.org/css-flexbox-1/#flex-flow-property
+#[derive(Debug, Default)]
+pub(in crate::style) struct FlexFlow {
+    pub direction: Option<FlexDirection>,
+    pub wrap: Option<FlexWrap>,
+}
+
+parse_one_or_more!(FlexFlow {
+    direction,
+    wrap,
+});
+
+/// The `flex` shorthand. Omitted flex factors are 1,
+/// and an omitted basis is 0 rather than the initial `auto`.
+/// https://drafts.csswg.org/css-flexbox-1/#flex-property
+#[derive(Debug)]
+pub(in crate::style) struct Flex {
+    pub grow: Number,
+    pub shrink: Number,
+    pub basis: SpecifiedLengthOrPercentageOrAuto,
+}
+
+impl super::Parse for Flex {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("none")).is_ok() {
+            return Ok(Flex {
+                grow: Number { value: 0. },
+                shrink: Number { value: 0. },
+                basis: SpecifiedLengthOrPercentageOrAuto::Auto,
+            });
+        }
+        let mut factors = None;
+        let mut basis = None;
+        loop {
+            // A unitless zero is a flex factor, so factors are tried first.
+            if factors.is_none() {
+                if let Ok(grow) = parser.try_parse(<Number as super::Parse>::parse) {
+                    let shrink = parser.try_parse(<Number as super::Parse>::parse).ok();
+                    factors = Some((grow, shrink));
+                    continue;
+                }
+            }
+            if basis.is_none() {
+                if let Ok(value) = parser.try_parse(super::Parse::parse) {
+                    basis = Some(value);
+                    continue;
+                }
+            }
+            break;
+        }
+        if factors.is_none() && basis.is_none() {
+            return Err(parser.new_error_for_next_token());
+        }
+        let (grow, shrink) = factors.unwrap_or((Number { value: 1. }, None));
+        Ok(Flex {
+            grow,
+            shrink: shrink.unwrap_or(Number { value: 1. }),
+            basis: basis.unwrap_or(SpecifiedLengthOrPercentageOrAuto::Length(
+                SpecifiedLength::Absolute(Length::zero()),
+            )),
+        })
+    }
+}
+
+/// The `gap` shorthand, with one value for both gaps or the row gap then the column gap.
+/// https://drafts.csswg.org/css-align-3/#gap-shorthand
+#[derive(Debug)]
+pub(in crate::style) struct Gap {
+    pub row: SpecifiedLengthOrPercentageOrNormal,
+    pub column: SpecifiedLengthOrPercentageOrNormal,
+}
+
+impl super::Parse for Gap {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        let row = <SpecifiedLengthOrPercentageOrNormal as super::Parse>::parse(parser)?;
+        let column = parser.try_parse(super::Parse::parse).unwrap_or(row);
+        Ok(Gap { row, column })
+    }
+}
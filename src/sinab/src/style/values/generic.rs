@@ -2,7 +2,7 @@ use crate::style::errors::PropertyParseError;
 use crate::style::values::Parse;
 use cssparser::Parser;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(in crate::style) struct FourSides<T> {
     pub top: T,
     pub right: T,
@@ -0,0 +1,276 @@
+use super::length::*;
+use super::CascadeContext;
+use crate::style::errors::PropertyParseError;
+use cssparser::{Parser, Token};
+
+/// https://drafts.csswg.org/css-grid/#typedef-track-breadth
+#[derive(Copy, Clone, Debug)]
+pub(in crate::style) enum SpecifiedTrackBreadth {
+    Length(SpecifiedLengthOrPercentage),
+    /// A number of `fr` units.
+    Flex(Number),
+    Auto,
+    MinContent,
+    MaxContent,
+}
+
+#[derive(Copy, Clone, Debug, FromSpecified)]
+pub(crate) enum TrackBreadth {
+    Length(LengthOrPercentage),
+    Flex(Number),
+    Auto,
+    MinContent,
+    MaxContent,
+}
+
+/// The minimum and maximum sizing functions of a track.
+/// A single breadth is both, except that a flexible breadth has an `auto` minimum.
+/// https://drafts.csswg.org/css-grid/#typedef-track-size
+#[derive(Copy, Clone, Debug)]
+pub(in crate::style) struct SpecifiedTrackSize {
+    pub min: SpecifiedTrackBreadth,
+    pub max: SpecifiedTrackBreadth,
+}
+
+#[derive(Copy, Clone, Debug, FromSpecified)]
+pub(crate) struct TrackSize {
+    pub min: TrackBreadth,
+    pub max: TrackBreadth,
+}
+
+impl TrackSize {
+    pub(in crate::style) const AUTO: Self = TrackSize {
+        min: TrackBreadth::Auto,
+        max: TrackBreadth::Auto,
+    };
+}
+
+/// The value of `grid-template-columns` or `grid-template-rows`,
+/// with `repeat()` expanded. `none` is an empty list.
+/// https://drafts.csswg.org/css-grid/#track-sizing
+#[derive(Clone, Debug)]
+pub(in crate::style) struct SpecifiedTrackList(pub Vec<SpecifiedTrackSize>);
+
+#[derive(Clone, Debug)]
+pub(crate) struct TrackList(pub Vec<TrackSize>);
+
+impl TrackList {
+    pub(in crate::style) const NONE: Self = TrackList(Vec::new());
+}
+
+impl super::SpecifiedValue for TrackList {
+    type SpecifiedValue = SpecifiedTrackList;
+}
+
+impl super::FromSpecified for TrackList {
+    fn from_specified(specified: &SpecifiedTrackList, context: &CascadeContext) -> Self {
+        TrackList(
+            specified
+                .0
+                .iter()
+                .map(|size| super::FromSpecified::from_specified(size, context))
+                .collect(),
+        )
+    }
+}
+
+/// https://drafts.csswg.org/css-grid/#grid-auto-flow-property
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum GridAutoFlow {
+    Row,
+    Column,
+}
+
+/// The value of a grid-placement property, such as `grid-row-start`.
+/// Named lines are not supported.
+/// https://drafts.csswg.org/css-grid/#line-placement
+#[derive(Copy, Clone, Debug, Eq, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum GridLine {
+    Auto,
+    /// A line number, negative numbers counting from the end of the explicit grid.
+    Line(i32),
+    /// A number of tracks to span.
+    Span(u32),
+}
+
+/// The `grid-row` and `grid-column` shorthands.
+/// https://drafts.csswg.org/css-grid/#placement-shorthands
+#[derive(Debug)]
+pub(in crate::style) struct GridLineRange {
+    pub start: GridLine,
+    pub end: GridLine,
+}
+
+/// The `grid-area` shorthand, with lines in the order
+/// row start, column start, row end, column end.
+/// https://drafts.csswg.org/css-grid/#propdef-grid-area
+#[derive(Debug)]
+pub(in crate::style) struct GridArea {
+    pub row_start: GridLine,
+    pub column_start: GridLine,
+    pub row_end: GridLine,
+    pub column_end: GridLine,
+}
+
+impl super::Parse for SpecifiedTrackBreadth {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if let Ok(ident) = parser.try_parse(|parser| parser.expect_ident().cloned()) {
+            return match_ignore_ascii_case!(&ident,
+                "auto" => Ok(SpecifiedTrackBreadth::Auto),
+                "min-content" => Ok(SpecifiedTrackBreadth::MinContent),
+                "max-content" => Ok(SpecifiedTrackBreadth::MaxContent),
+                _ => Err(parser.new_unexpected_token_error(Token::Ident(ident))),
+            );
+        }
+        let flex = parser.try_parse(|parser| -> Result<_, PropertyParseError> {
+            match parser.next()? {
+                Token::Dimension { value, unit, .. }
+                    if unit.eq_ignore_ascii_case("fr") && *value >= 0. =>
+                {
+                    Ok(Number { value: *value })
+                }
+                token => {
+                    let token = token.clone();
+                    Err(parser.new_unexpected_token_error(token))
+                }
+            }
+        });
+        match flex {
+            Ok(flex) => Ok(SpecifiedTrackBreadth::Flex(flex)),
+            Err(_) => Ok(SpecifiedTrackBreadth::Length(super::Parse::parse(parser)?)),
+        }
+    }
+}
+
+impl super::Parse for SpecifiedTrackSize {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_function_matching("minmax")).is_ok() {
+            return parser.parse_nested_block(|parser| {
+                let min = <SpecifiedTrackBreadth as super::Parse>::parse(parser)?;
+                if let SpecifiedTrackBreadth::Flex(_) = min {
+                    return Err(parser.new_error_for_next_token());
+                }
+                parser.expect_comma()?;
+                let max = super::Parse::parse(parser)?;
+                Ok(SpecifiedTrackSize { min, max })
+            });
+        }
+        let max = <SpecifiedTrackBreadth as super::Parse>::parse(parser)?;
+        let min = match max {
+            SpecifiedTrackBreadth::Flex(_) => SpecifiedTrackBreadth::Auto,
+            breadth => breadth,
+        };
+        Ok(SpecifiedTrackSize { min, max })
+    }
+}
+
+impl super::Parse for SpecifiedTrackList {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("none")).is_ok() {
+            return Ok(SpecifiedTrackList(vec![]));
+        }
+        let mut sizes = vec![];
+        parse_track_sizes(parser, &mut sizes, true)?;
+        Ok(SpecifiedTrackList(sizes))
+    }
+}
+
+/// Parses one or more track sizes, skipping line names,
+/// and expanding `repeat()` with an integer number of repetitions if allowed.
+fn parse_track_sizes<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+    sizes: &mut Vec<SpecifiedTrackSize>,
+    allow_repeat: bool,
+) -> Result<(), PropertyParseError<'i>> {
+    let sizes_before = sizes.len();
+    loop {
+        let skipped_line_names = parser
+            .try_parse(|parser| parser.expect_square_bracket_block())
+            .is_ok();
+        if skipped_line_names {
+            parser.parse_nested_block(|parser| {
+                while parser.next().is_ok() {}
+                Ok::<_, PropertyParseError>(())
+            })?;
+            continue;
+        }
+        if allow_repeat
+            && parser.try_parse(|parser| parser.expect_function_matching("repeat")).is_ok()
+        {
+            parser.parse_nested_block(|parser| {
+                let count = parser.expect_integer()?;
+                if count < 1 {
+                    return Err(parser.new_error_for_next_token());
+                }
+                parser.expect_comma()?;
+                let mut repeated = vec![];
+                parse_track_sizes(parser, &mut repeated, false)?;
+                for _ in 0..count.min(1000) {
+                    sizes.extend_from_slice(&repeated)
+                }
+                Ok(())
+            })?;
+            continue;
+        }
+        match parser.try_parse(super::Parse::parse) {
+            Ok(size) => sizes.push(size),
+            Err(_) => break,
+        }
+    }
+    if sizes.len() == sizes_before {
+        return Err(parser.new_error_for_next_token());
+    }
+    Ok(())
+}
+
+impl super::Parse for GridLine {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("auto")).is_ok() {
+            return Ok(GridLine::Auto);
+        }
+        let span = parser.try_parse(|parser| parser.expect_ident_matching("span")).is_ok();
+        let location = parser.current_source_location();
+        let integer = parser.expect_integer()?;
+        let span = span || parser.try_parse(|parser| parser.expect_ident_matching("span")).is_ok();
+        match (span, integer) {
+            (true, n) if n > 0 => Ok(GridLine::Span(n as u32)),
+            (false, n) if n != 0 => Ok(GridLine::Line(n)),
+            _ => Err(location.new_unexpected_token_error(Token::Number {
+                has_sign: false,
+                value: integer as f32,
+                int_value: Some(integer),
+            })),
+        }
+    }
+}
+
+impl super::Parse for GridLineRange {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        let start = super::Parse::parse(parser)?;
+        let end = match parser.try_parse(|parser| parser.expect_delim('/')) {
+            Ok(()) => super::Parse::parse(parser)?,
+            Err(_) => GridLine::Auto,
+        };
+        Ok(GridLineRange { start, end })
+    }
+}
+
+impl super::Parse for GridArea {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        let mut lines = [GridLine::Auto; 4];
+        lines[0] = super::Parse::parse(parser)?;
+        for line in &mut lines[1..] {
+            if parser.try_parse(|parser| parser.expect_delim('/')).is_err() {
+                break;
+            }
+            *line = super::Parse::parse(parser)?;
+        }
+        let [row_start, column_start, row_end, column_end] = lines;
+        Ok(GridArea {
+            row_start,
+            column_start,
+            row_end,
+            column_end,
+        })
+    }
+}
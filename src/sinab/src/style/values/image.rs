@@ -0,0 +1,582 @@
+use super::length::*;
+use super::CascadeContext;
+use crate::style::errors::PropertyParseError;
+use cssparser::{Color, Parser, Token};
+
+/// https://drafts.csswg.org/css-images/#typedef-image
+#[derive(Clone, Debug)]
+pub(in crate::style) enum SpecifiedImageValue {
+    None,
+    Url(String),
+    LinearGradient(SpecifiedLinearGradient),
+    RadialGradient(SpecifiedRadialGradient),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum ImageValue {
+    None,
+    Url(String),
+    LinearGradient(LinearGradient),
+    RadialGradient(RadialGradient),
+}
+
+impl super::SpecifiedValue for ImageValue {
+    type SpecifiedValue = SpecifiedImageValue;
+}
+
+impl super::FromSpecified for ImageValue {
+    fn from_specified(specified: &SpecifiedImageValue, context: &CascadeContext) -> Self {
+        match specified {
+            SpecifiedImageValue::None => ImageValue::None,
+            SpecifiedImageValue::Url(url) => ImageValue::Url(url.clone()),
+            SpecifiedImageValue::LinearGradient(gradient) => ImageValue::LinearGradient(LinearGradient {
+                direction: gradient.direction,
+                stops: stops_from_specified(&gradient.stops, context),
+            }),
+            SpecifiedImageValue::RadialGradient(gradient) => ImageValue::RadialGradient(RadialGradient {
+                shape: gradient.shape,
+                extent: super::FromSpecified::from_specified(&gradient.extent, context),
+                center: super::FromSpecified::from_specified(&gradient.center, context),
+                stops: stops_from_specified(&gradient.stops, context),
+            }),
+        }
+    }
+}
+
+impl super::Parse for SpecifiedImageValue {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("none")).is_ok() {
+            return Ok(SpecifiedImageValue::None);
+        }
+        if let Ok(url) = parser.try_parse(|parser| parser.expect_url()) {
+            return Ok(SpecifiedImageValue::Url(url.as_ref().to_owned()));
+        }
+        let function = parser.expect_function()?.clone();
+        parser.parse_nested_block(|parser| {
+            match_ignore_ascii_case!(&function,
+                "linear-gradient" => Ok(SpecifiedImageValue::LinearGradient(parse_linear_gradient(parser)?)),
+                "radial-gradient" => Ok(SpecifiedImageValue::RadialGradient(parse_radial_gradient(parser)?)),
+                _ => Err(parser.new_unexpected_token_error(Token::Function(function.clone()))),
+            )
+        })
+    }
+}
+
+/// A color stop of a gradient. Stops without a position are spaced evenly
+/// between their neighbors.
+/// https://drafts.csswg.org/css-images/#color-stop-syntax
+#[derive(Clone, Debug)]
+pub(in crate::style) struct SpecifiedColorStop {
+    pub color: Color,
+    pub position: Option<SpecifiedLengthOrPercentage>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ColorStop {
+    pub color: Color,
+    pub position: Option<LengthOrPercentage>,
+}
+
+fn stops_from_specified(stops: &[SpecifiedColorStop], context: &CascadeContext) -> Vec<ColorStop> {
+    stops
+        .iter()
+        .map(|stop| ColorStop {
+            color: stop.color,
+            position: stop
+                .position
+                .map(|position| super::FromSpecified::from_specified(&position, context)),
+        })
+        .collect()
+}
+
+/// Parses two or more comma-separated color stops. A stop with two
+/// positions is the same as two stops of that color.
+fn parse_color_stops<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+) -> Result<Vec<SpecifiedColorStop>, PropertyParseError<'i>> {
+    let mut stops = vec![];
+    for (color, positions) in parser.parse_comma_separated(|parser| {
+        let color = Color::parse(parser)?;
+        let first = parser.try_parse(<SpecifiedLengthOrPercentage as super::Parse>::parse).ok();
+        let second = match first {
+            Some(_) => parser.try_parse(<SpecifiedLengthOrPercentage as super::Parse>::parse).ok(),
+            None => None,
+        };
+        Ok((color, [first, second]))
+    })? {
+        stops.push(SpecifiedColorStop { color, position: positions[0] });
+        if positions[1].is_some() {
+            stops.push(SpecifiedColorStop { color, position: positions[1] });
+        }
+    }
+    if stops.len() < 2 {
+        return Err(parser.new_error_for_next_token());
+    }
+    Ok(stops)
+}
+
+/// The direction of a linear gradient: an angle in degrees, clockwise from
+/// pointing up, or towards a corner of the box, whose angle depends on its
+/// aspect ratio.
+#[derive(Copy, Clone, Debug, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum GradientDirection {
+    Angle(f32),
+    Corner { right: bool, bottom: bool },
+}
+
+/// https://drafts.csswg.org/css-images/#linear-gradients
+#[derive(Clone, Debug)]
+pub(in crate::style) struct SpecifiedLinearGradient {
+    pub direction: GradientDirection,
+    pub stops: Vec<SpecifiedColorStop>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct LinearGradient {
+    pub direction: GradientDirection,
+    pub stops: Vec<ColorStop>,
+}
+
+fn parse_linear_gradient<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+) -> Result<SpecifiedLinearGradient, PropertyParseError<'i>> {
+    let direction = if let Ok(angle) = parser.try_parse(parse_angle) {
+        Some(GradientDirection::Angle(angle))
+    } else if parser.try_parse(|parser| parser.expect_ident_matching("to")).is_ok() {
+        Some(parse_side_or_corner(parser)?)
+    } else {
+        None
+    };
+    if direction.is_some() {
+        parser.expect_comma()?;
+    }
+    Ok(SpecifiedLinearGradient {
+        direction: direction.unwrap_or(GradientDirection::Angle(180.)),
+        stops: parse_color_stops(parser)?,
+    })
+}
+
+/// Parses an angle, in degrees.
+/// https://drafts.csswg.org/css-values/#angles
+pub(super) fn parse_angle<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<f32, PropertyParseError<'i>> {
+    let token = parser.next()?.clone();
+    match token {
+        Token::Dimension { value, ref unit, .. } => match_ignore_ascii_case!(unit,
+            "deg" => return Ok(value),
+            "grad" => return Ok(value * 0.9),
+            "rad" => return Ok(value.to_degrees()),
+            "turn" => return Ok(value * 360.),
+            _ => {}
+        ),
+        Token::Number { value: 0., .. } => return Ok(0.),
+        _ => {}
+    }
+    Err(parser.new_unexpected_token_error(token))
+}
+
+/// Parses the side or corner after `to`, in either order.
+fn parse_side_or_corner<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+) -> Result<GradientDirection, PropertyParseError<'i>> {
+    let horizontal = |parser: &mut Parser<'i, 't>| -> Result<bool, PropertyParseError<'i>> {
+        parser.try_parse(|parser| {
+            let ident = parser.expect_ident()?.clone();
+            match_ignore_ascii_case!(&ident,
+                "left" => Ok(false),
+                "right" => Ok(true),
+                _ => Err(parser.new_unexpected_token_error(Token::Ident(ident.clone()))),
+            )
+        })
+    };
+    let vertical = |parser: &mut Parser<'i, 't>| -> Result<bool, PropertyParseError<'i>> {
+        parser.try_parse(|parser| {
+            let ident = parser.expect_ident()?.clone();
+            match_ignore_ascii_case!(&ident,
+                "top" => Ok(false),
+                "bottom" => Ok(true),
+                _ => Err(parser.new_unexpected_token_error(Token::Ident(ident.clone()))),
+            )
+        })
+    };
+    let (right, bottom) = if let Ok(right) = horizontal(parser) {
+        (Some(right), vertical(parser).ok())
+    } else {
+        let bottom = vertical(parser)?;
+        (horizontal(parser).ok(), Some(bottom))
+    };
+    Ok(match (right, bottom) {
+        (Some(right), Some(bottom)) => GradientDirection::Corner { right, bottom },
+        (Some(right), None) => GradientDirection::Angle(if right { 90. } else { 270. }),
+        (None, Some(bottom)) => GradientDirection::Angle(if bottom { 180. } else { 0. }),
+        (None, None) => unreachable!(),
+    })
+}
+
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum RadialShape {
+    Circle,
+    Ellipse,
+}
+
+/// The size of the ending shape of a radial gradient. An explicit circle
+/// has the same length for both radii.
+/// https://drafts.csswg.org/css-images/#valdef-radial-gradient-radial-size
+#[derive(Copy, Clone, Debug)]
+pub(in crate::style) enum SpecifiedRadialExtent {
+    ClosestSide,
+    FarthestSide,
+    ClosestCorner,
+    FarthestCorner,
+    Radii(SpecifiedLengthOrPercentage, SpecifiedLengthOrPercentage),
+}
+
+#[derive(Copy, Clone, Debug, FromSpecified)]
+pub(crate) enum RadialExtent {
+    ClosestSide,
+    FarthestSide,
+    ClosestCorner,
+    FarthestCorner,
+    Radii(LengthOrPercentage, LengthOrPercentage),
+}
+
+/// https://drafts.csswg.org/css-images/#radial-gradients
+#[derive(Clone, Debug)]
+pub(in crate::style) struct SpecifiedRadialGradient {
+    pub shape: RadialShape,
+    pub extent: SpecifiedRadialExtent,
+    pub center: SpecifiedPositionValue,
+    pub stops: Vec<SpecifiedColorStop>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct RadialGradient {
+    pub shape: RadialShape,
+    pub extent: RadialExtent,
+    pub center: PositionValue,
+    pub stops: Vec<ColorStop>,
+}
+
+fn parse_radial_gradient<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+) -> Result<SpecifiedRadialGradient, PropertyParseError<'i>> {
+    let mut shape = None;
+    let mut extent = None;
+    loop {
+        if shape.is_none() {
+            if let Ok(value) = parser.try_parse(<RadialShape as super::Parse>::parse) {
+                shape = Some(value);
+                continue;
+            }
+        }
+        if extent.is_none() {
+            if let Ok(value) = parser.try_parse(parse_radial_extent) {
+                extent = Some(value);
+                continue;
+            }
+        }
+        break;
+    }
+    let center = if parser.try_parse(|parser| parser.expect_ident_matching("at")).is_ok() {
+        Some(<SpecifiedPositionValue as super::Parse>::parse(parser)?)
+    } else {
+        None
+    };
+    if shape.is_some() || extent.is_some() || center.is_some() {
+        parser.expect_comma()?;
+    }
+
+    // A single length is a circle, two lengths an ellipse.
+    let shape = match (shape, extent) {
+        (Some(shape), _) => shape,
+        (None, Some((_, true))) => RadialShape::Circle,
+        (None, _) => RadialShape::Ellipse,
+    };
+    let extent = match extent {
+        Some((extent, single)) => {
+            // Extent keywords are valid for both shapes, but a circle has a
+            // single length radius and an ellipse two radii.
+            if let SpecifiedRadialExtent::Radii(radius, _) = extent {
+                let circle = shape == RadialShape::Circle;
+                let percentage = matches!(radius, SpecifiedLengthOrPercentage::Percentage(_));
+                if circle != single || (circle && percentage) {
+                    return Err(parser.new_error_for_next_token());
+                }
+            }
+            extent
+        }
+        None => SpecifiedRadialExtent::FarthestCorner,
+    };
+    Ok(SpecifiedRadialGradient {
+        shape,
+        extent,
+        center: center.unwrap_or(SpecifiedPositionValue::CENTER),
+        stops: parse_color_stops(parser)?,
+    })
+}
+
+/// Parses an extent keyword or explicit radii, and whether these are a
+/// single length (a circle).
+fn parse_radial_extent<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+) -> Result<(SpecifiedRadialExtent, bool), PropertyParseError<'i>> {
+    if let Ok(ident) = parser.try_parse(|parser| parser.expect_ident().cloned()) {
+        return match_ignore_ascii_case!(&ident,
+            "closest-side" => Ok((SpecifiedRadialExtent::ClosestSide, false)),
+            "farthest-side" => Ok((SpecifiedRadialExtent::FarthestSide, false)),
+            "closest-corner" => Ok((SpecifiedRadialExtent::ClosestCorner, false)),
+            "farthest-corner" => Ok((SpecifiedRadialExtent::FarthestCorner, false)),
+            _ => Err(parser.new_unexpected_token_error(Token::Ident(ident.clone()))),
+        );
+    }
+    let x = <SpecifiedLengthOrPercentage as super::Parse>::parse(parser)?;
+    match parser.try_parse(<SpecifiedLengthOrPercentage as super::Parse>::parse) {
+        Ok(y) => Ok((SpecifiedRadialExtent::Radii(x, y), false)),
+        Err(_) => Ok((SpecifiedRadialExtent::Radii(x, x), true)),
+    }
+}
+
+/// The edge from which a coordinate of a position is measured.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum PositionEdge {
+    Start,
+    End,
+}
+
+/// One coordinate of a position, as an offset from the left or top edge,
+/// or from the right or bottom edge.
+#[derive(Copy, Clone, Debug)]
+pub(in crate::style) struct SpecifiedPositionComponent {
+    pub edge: PositionEdge,
+    pub offset: SpecifiedLengthOrPercentage,
+}
+
+#[derive(Copy, Clone, Debug, FromSpecified)]
+pub(crate) struct PositionComponent {
+    pub edge: PositionEdge,
+    pub offset: LengthOrPercentage,
+}
+
+impl PositionComponent {
+    /// The offset from the start edge, given the free space in which
+    /// percentages are resolved.
+    pub(crate) fn resolve(&self, available: Length) -> Length {
+        let offset = self.offset.percentage_relative_to(available);
+        match self.edge {
+            PositionEdge::Start => offset,
+            PositionEdge::End => available - offset,
+        }
+    }
+}
+
+/// A `<position>` value, for `background-position` and gradient centers.
+/// https://drafts.csswg.org/css-values/#position
+#[derive(Copy, Clone, Debug)]
+pub(in crate::style) struct SpecifiedPositionValue {
+    pub x: SpecifiedPositionComponent,
+    pub y: SpecifiedPositionComponent,
+}
+
+#[derive(Copy, Clone, Debug, FromSpecified)]
+pub(crate) struct PositionValue {
+    pub x: PositionComponent,
+    pub y: PositionComponent,
+}
+
+impl SpecifiedPositionValue {
+    pub(in crate::style) const TOP_LEFT: Self = SpecifiedPositionValue {
+        x: SpecifiedPositionComponent::START,
+        y: SpecifiedPositionComponent::START,
+    };
+
+    const CENTER: Self = SpecifiedPositionValue {
+        x: SpecifiedPositionComponent::CENTER,
+        y: SpecifiedPositionComponent::CENTER,
+    };
+}
+
+impl SpecifiedPositionComponent {
+    const START: Self = SpecifiedPositionComponent {
+        edge: PositionEdge::Start,
+        offset: SpecifiedLengthOrPercentage::Percentage(Percentage { unit_value: 0. }),
+    };
+
+    const CENTER: Self = SpecifiedPositionComponent {
+        edge: PositionEdge::Start,
+        offset: SpecifiedLengthOrPercentage::Percentage(Percentage { unit_value: 0.5 }),
+    };
+}
+
+impl PositionValue {
+    pub(in crate::style) const TOP_LEFT: Self = PositionValue {
+        x: PositionComponent {
+            edge: PositionEdge::Start,
+            offset: LengthOrPercentage::Percentage(Percentage { unit_value: 0. }),
+        },
+        y: PositionComponent {
+            edge: PositionEdge::Start,
+            offset: LengthOrPercentage::Percentage(Percentage { unit_value: 0. }),
+        },
+    };
+
+    pub(in crate::style) const CENTER: Self = PositionValue {
+        x: PositionComponent {
+            edge: PositionEdge::Start,
+            offset: LengthOrPercentage::Percentage(Percentage { unit_value: 0.5 }),
+        },
+        y: PositionComponent {
+            edge: PositionEdge::Start,
+            offset: LengthOrPercentage::Percentage(Percentage { unit_value: 0.5 }),
+        },
+    };
+}
+
+/// A keyword of a position, and the axis it applies to if not `center`.
+#[derive(Copy, Clone, PartialEq)]
+enum PositionKeyword {
+    Center,
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Copy, Clone)]
+enum PositionItem {
+    Keyword(PositionKeyword),
+    Offset(SpecifiedLengthOrPercentage),
+}
+
+impl PositionKeyword {
+    fn is_horizontal(self) -> bool {
+        matches!(self, PositionKeyword::Left | PositionKeyword::Right)
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, PositionKeyword::Top | PositionKeyword::Bottom)
+    }
+
+    fn component(self, offset: Option<SpecifiedLengthOrPercentage>) -> SpecifiedPositionComponent {
+        let zero = SpecifiedLengthOrPercentage::Percentage(Percentage { unit_value: 0. });
+        match self {
+            PositionKeyword::Center => SpecifiedPositionComponent::CENTER,
+            PositionKeyword::Left | PositionKeyword::Top => SpecifiedPositionComponent {
+                edge: PositionEdge::Start,
+                offset: offset.unwrap_or(zero),
+            },
+            PositionKeyword::Right | PositionKeyword::Bottom => SpecifiedPositionComponent {
+                edge: PositionEdge::End,
+                offset: offset.unwrap_or(zero),
+            },
+        }
+    }
+}
+
+impl super::Parse for SpecifiedPositionValue {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        let mut items = vec![];
+        while items.len() < 4 {
+            let item: Result<_, PropertyParseError> = parser.try_parse(|parser| {
+                if let Ok(offset) =
+                    parser.try_parse(<SpecifiedLengthOrPercentage as super::Parse>::parse)
+                {
+                    return Ok(PositionItem::Offset(offset));
+                }
+                let ident = parser.expect_ident()?.clone();
+                match_ignore_ascii_case!(&ident,
+                    "center" => Ok(PositionItem::Keyword(PositionKeyword::Center)),
+                    "left" => Ok(PositionItem::Keyword(PositionKeyword::Left)),
+                    "right" => Ok(PositionItem::Keyword(PositionKeyword::Right)),
+                    "top" => Ok(PositionItem::Keyword(PositionKeyword::Top)),
+                    "bottom" => Ok(PositionItem::Keyword(PositionKeyword::Bottom)),
+                    _ => Err(parser.new_unexpected_token_error(Token::Ident(ident.clone()))),
+                )
+            });
+            match item {
+                Ok(item) => items.push(item),
+                Err(_) => break,
+            }
+        }
+        position_from_items(&items).ok_or_else(|| parser.new_error_for_next_token())
+    }
+}
+
+/// Interprets the one to four keywords and offsets of a position.
+fn position_from_items(items: &[PositionItem]) -> Option<SpecifiedPositionValue> {
+    use PositionItem::{Keyword, Offset};
+    let center = SpecifiedPositionComponent::CENTER;
+    let start = |offset| SpecifiedPositionComponent {
+        edge: PositionEdge::Start,
+        offset,
+    };
+    match *items {
+        [Offset(x)] => Some(SpecifiedPositionValue { x: start(x), y: center }),
+        [Keyword(keyword)] if keyword.is_vertical() => Some(SpecifiedPositionValue {
+            x: center,
+            y: keyword.component(None),
+        }),
+        [Keyword(keyword)] => Some(SpecifiedPositionValue {
+            x: keyword.component(None),
+            y: center,
+        }),
+        // With two values, offsets are horizontal then vertical, but two
+        // keywords may come in either order.
+        [Keyword(first), Keyword(second)]
+            if first.is_vertical() || second.is_horizontal() =>
+        {
+            if first.is_horizontal() || second.is_vertical() {
+                return None;
+            }
+            Some(SpecifiedPositionValue {
+                x: second.component(None),
+                y: first.component(None),
+            })
+        }
+        [first, second] => {
+            let x = match first {
+                Offset(offset) => start(offset),
+                Keyword(keyword) if !keyword.is_vertical() => keyword.component(None),
+                _ => return None,
+            };
+            let y = match second {
+                Offset(offset) => start(offset),
+                Keyword(keyword) if !keyword.is_horizontal() => keyword.component(None),
+                _ => return None,
+            };
+            Some(SpecifiedPositionValue { x, y })
+        }
+        // With three or four values, each offset follows a side keyword.
+        [_, _, _] | [_, _, _, _] => {
+            let mut pairs = vec![];
+            let mut rest = items;
+            while let [Keyword(keyword), tail @ ..] = rest {
+                match tail {
+                    [Offset(offset), tail @ ..] if *keyword != PositionKeyword::Center => {
+                        pairs.push((*keyword, Some(*offset)));
+                        rest = tail;
+                    }
+                    _ => {
+                        pairs.push((*keyword, None));
+                        rest = tail;
+                    }
+                }
+            }
+            let (first, second) = match pairs[..] {
+                [first, second] if rest.is_empty() => (first, second),
+                _ => return None,
+            };
+            let (horizontal, vertical) = if first.0.is_vertical() || second.0.is_horizontal() {
+                (second, first)
+            } else {
+                (first, second)
+            };
+            if horizontal.0.is_vertical() || vertical.0.is_horizontal() {
+                return None;
+            }
+            Some(SpecifiedPositionValue {
+                x: horizontal.0.component(horizontal.1),
+                y: vertical.0.component(vertical.1),
+            })
+        }
+        _ => None,
+    }
+}
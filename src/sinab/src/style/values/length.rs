@@ -59,6 +59,34 @@ pub(crate) enum LengthOrPercentageOrAuto {
     Auto,
 }
 
+#[derive(Copy, Clone, Debug, Parse, FromVariants)]
+pub(in crate::style) enum SpecifiedLengthOrPercentageOrNormal {
+    Length(SpecifiedLength),
+    Percentage(Percentage),
+    Normal,
+}
+
+#[derive(Copy, Clone, Debug, FromSpecified, FromVariants)]
+pub(crate) enum LengthOrPercentageOrNormal {
+    Length(Length),
+    Percentage(Percentage),
+    Normal,
+}
+
+#[derive(Copy, Clone, Debug, Parse, FromVariants)]
+pub(in crate::style) enum SpecifiedLengthOrPercentageOrNone {
+    Length(SpecifiedLength),
+    Percentage(Percentage),
+    None,
+}
+
+#[derive(Copy, Clone, Debug, FromSpecified, FromVariants)]
+pub(crate) enum LengthOrPercentageOrNone {
+    Length(Length),
+    Percentage(Percentage),
+    None,
+}
+
 #[derive(Copy, Clone, Debug, Parse, FromVariants)]
 pub(in crate::style) enum SpecifiedLengthOrPercentageOrNumber {
     Length(SpecifiedLength),
@@ -266,6 +294,26 @@ impl LengthOrPercentage {
     }
 }
 
+impl LengthOrPercentageOrNormal {
+    pub(crate) fn percentage_relative_to(&self, reference: Length, normal: Length) -> Length {
+        match *self {
+            LengthOrPercentageOrNormal::Length(l) => l,
+            LengthOrPercentageOrNormal::Percentage(p) => reference * p,
+            LengthOrPercentageOrNormal::Normal => normal,
+        }
+    }
+}
+
+impl LengthOrPercentageOrNone {
+    pub(crate) fn percentage_relative_to(&self, reference: Length) -> Option<Length> {
+        match *self {
+            LengthOrPercentageOrNone::Length(l) => Some(l),
+            LengthOrPercentageOrNone::Percentage(p) => Some(reference * p),
+            LengthOrPercentageOrNone::None => None,
+        }
+    }
+}
+
 impl LengthOrPercentageOrNumber {
     pub(crate) fn percentage_or_number_relative_to(&self, reference: Length) -> Length {
         match *self {
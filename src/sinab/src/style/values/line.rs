@@ -1,4 +1,6 @@
 use super::{Length, SpecifiedLength, Percentage};
+use crate::style::errors::PropertyParseError;
+use cssparser::Parser;
 
 #[derive(Clone, Debug, Parse, FromVariants)]
 pub(in crate::style) enum SpecifiedVerticalAlign {
@@ -27,3 +29,31 @@ pub(crate) enum VerticalAlign {
     Length(Length),
     Percentage(Percentage),
 }
+
+/// The size of a drop or raised initial letter of `::first-letter`, in
+/// lines, and the number of lines it sinks into, by default its size
+/// rounded down.
+/// https://drafts.csswg.org/css-inline/#sizing-initial-letter
+#[derive(Copy, Clone, Debug, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum InitialLetter {
+    Normal,
+    Lines { size: f32, sink: u32 },
+}
+
+impl super::Parse for InitialLetter {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("normal")).is_ok() {
+            return Ok(InitialLetter::Normal);
+        }
+        let size = parser.expect_number()?;
+        if size < 1. {
+            return Err(parser.new_error_for_next_token());
+        }
+        let sink = match parser.try_parse(|parser| parser.expect_integer()) {
+            Ok(sink) if sink >= 1 => sink as u32,
+            Ok(_) => return Err(parser.new_error_for_next_token()),
+            Err(_) => size.floor() as u32,
+        };
+        Ok(InitialLetter::Lines { size, sink })
+    }
+}
@@ -0,0 +1,154 @@
+use crate::style::errors::PropertyParseError;
+use cssparser::Parser;
+
+/// https://drafts.csswg.org/css-lists-3/#text-markers
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum ListStyleType {
+    None,
+    Disc,
+    Circle,
+    Square,
+    Decimal,
+    DecimalLeadingZero,
+    LowerAlpha,
+    UpperAlpha,
+    LowerLatin,
+    UpperLatin,
+    LowerRoman,
+    UpperRoman,
+    LowerGreek,
+}
+
+/// https://drafts.csswg.org/css-lists-3/#list-style-position-property
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum ListStylePosition {
+    Outside,
+    Inside,
+}
+
+/// https://drafts.csswg.org/css-lists-3/#image-markers
+#[derive(Clone, Debug, Eq, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum ListStyleImage {
+    None,
+    Url(String),
+}
+
+impl super::Parse for ListStyleImage {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("none")).is_ok() {
+            return Ok(ListStyleImage::None);
+        }
+        Ok(ListStyleImage::Url(parser.expect_url()?.as_ref().to_owned()))
+    }
+}
+
+parse_one_or_more!(ListStyle {
+    type_,
+    position,
+    image,
+});
+
+/// The `list-style` shorthand. A lone `none` sets `list-style-type`,
+/// and leaves `list-style-image` at its initial value of `none`.
+#[derive(Debug, Default)]
+pub(in crate::style) struct ListStyle {
+    pub type_: Option<ListStyleType>,
+    pub position: Option<ListStylePosition>,
+    pub image: Option<ListStyleImage>,
+}
+
+impl ListStyleType {
+    /// The text of the marker for the list item with the given ordinal,
+    /// including its suffix, or `None` for `list-style-type: none`.
+    /// https://drafts.csswg.org/css-counter-styles-3/#predefined-counters
+    pub fn marker_text(self, ordinal: i32) -> Option<String> {
+        let text = self.counter_text(ordinal)?;
+        Some(match self {
+            ListStyleType::Disc | ListStyleType::Circle | ListStyleType::Square => text + " ",
+            _ => text + ". ",
+        })
+    }
+
+    /// The representation of a counter value in this style, without the
+    /// suffix of markers, or `None` for `none`.
+    /// https://drafts.csswg.org/css-counter-styles-3/#generate-a-counter
+    pub fn counter_text(self, value: i32) -> Option<String> {
+        Some(match self {
+            ListStyleType::None => return None,
+            ListStyleType::Disc => "\u{2022}".into(),
+            ListStyleType::Circle => "\u{25E6}".into(),
+            ListStyleType::Square => "\u{25AA}".into(),
+            ListStyleType::Decimal => value.to_string(),
+            ListStyleType::DecimalLeadingZero => match value {
+                0..=9 => format!("0{}", value),
+                -9..=-1 => format!("-0{}", -value),
+                _ => value.to_string(),
+            },
+            ListStyleType::LowerAlpha | ListStyleType::LowerLatin => {
+                alphabetic(value, &LATIN).to_lowercase()
+            }
+            ListStyleType::UpperAlpha | ListStyleType::UpperLatin => alphabetic(value, &LATIN),
+            ListStyleType::LowerRoman => roman(value).to_lowercase(),
+            ListStyleType::UpperRoman => roman(value),
+            ListStyleType::LowerGreek => alphabetic(value, &GREEK),
+        })
+    }
+}
+
+const LATIN: [char; 26] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
+    'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+const GREEK: [char; 24] = [
+    'α', 'β', 'γ', 'δ', 'ε', 'ζ', 'η', 'θ', 'ι', 'κ', 'λ', 'μ', 'ν', 'ξ', 'ο', 'π', 'ρ', 'σ', 'τ',
+    'υ', 'φ', 'χ', 'ψ', 'ω',
+];
+
+/// https://drafts.csswg.org/css-counter-styles-3/#alphabetic-system
+/// Falls back to decimal outside of the range of the counter style.
+fn alphabetic(ordinal: i32, symbols: &[char]) -> String {
+    if ordinal < 1 {
+        return ordinal.to_string();
+    }
+    let mut value = ordinal as usize;
+    let mut result = Vec::new();
+    while value > 0 {
+        value -= 1;
+        result.push(symbols[value % symbols.len()]);
+        value /= symbols.len();
+    }
+    result.iter().rev().collect()
+}
+
+/// https://drafts.csswg.org/css-counter-styles-3/#additive-system
+/// Falls back to decimal outside of the range of the counter style.
+fn roman(ordinal: i32) -> String {
+    const SYMBOLS: [(i32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    if !(1..=3999).contains(&ordinal) {
+        return ordinal.to_string();
+    }
+    let mut value = ordinal;
+    let mut result = String::new();
+    for &(weight, symbol) in &SYMBOLS {
+        while value >= weight {
+            result.push_str(symbol);
+            value -= weight;
+        }
+    }
+    result
+}
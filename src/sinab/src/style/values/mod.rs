@@ -4,21 +4,31 @@ use crate::style::properties::{ComputedValuesForEarlyCascade, ComputedValuesForL
 use cssparser::Parser;
 
 mod background;
+#[macro_use]
 mod border;
 mod box_;
 mod color;
+mod content;
+mod flex;
 mod fonts;
 mod fontfamily;
 mod generic;
+mod grid;
+mod image;
 mod length;
 mod line;
+mod list;
+mod multicol;
+mod shadow;
+mod table;
 mod text;
+mod transform;
 mod writing_modes;
 mod tests; // testing module for values
 
-pub(super) use self::{background::*, generic::*};
+pub(super) use self::generic::*;
 pub(crate) use self::{
-    border::*, box_::*, color::*, fonts::*, fontfamily::*, length::*, line::*, text::*, writing_modes::*
+    background::*, border::*, box_::*, color::*, content::*, flex::*, fonts::*, fontfamily::*, grid::*, image::*, length::*, line::*, list::*, multicol::*, shadow::*, table::*, text::*, transform::*, writing_modes::*
 };
 
 pub(super) trait Parse: Sized {
@@ -0,0 +1,35 @@
+use super::length::*;
+use crate::style::errors::PropertyParseError;
+use cssparser::Parser;
+
+/// https://drafts.csswg.org/css-multicol/#cc
+#[derive(Copy, Clone, Debug, Eq, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum ColumnCount {
+    Auto,
+    Integer(u32),
+}
+
+impl super::Parse for ColumnCount {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("auto")).is_ok() {
+            return Ok(ColumnCount::Auto);
+        }
+        match parser.expect_integer()? {
+            count if count > 0 => Ok(ColumnCount::Integer(count.min(1000) as u32)),
+            _ => Err(parser.new_error_for_next_token()),
+        }
+    }
+}
+
+/// The `columns` shorthand.
+/// https://drafts.csswg.org/css-multicol/#columns
+#[derive(Debug, Default)]
+pub(in crate::style) struct Columns {
+    pub width: Option<SpecifiedLengthOrPercentageOrAuto>,
+    pub count: Option<ColumnCount>,
+}
+
+parse_one_or_more!(Columns {
+    width,
+    count,
+});
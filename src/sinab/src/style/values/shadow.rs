@@ -0,0 +1,190 @@
+use super::length::*;
+use super::CascadeContext;
+use crate::style::errors::PropertyParseError;
+use cssparser::{Color, Parser};
+
+/// One layer of a `box-shadow` or `text-shadow`.
+/// An omitted color is `currentcolor`, omitted blur and spread are zero.
+/// https://drafts.csswg.org/css-backgrounds/#typedef-shadow
+#[derive(Clone, Debug)]
+pub(in crate::style) struct SpecifiedShadow {
+    pub color: Color,
+    pub offset_x: SpecifiedLength,
+    pub offset_y: SpecifiedLength,
+    pub blur: SpecifiedLength,
+    pub spread: SpecifiedLength,
+    pub inset: bool,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Shadow {
+    pub color: Color,
+    pub offset_x: Length,
+    pub offset_y: Length,
+    /// The blur radius, twice the standard deviation of the Gaussian blur.
+    pub blur: Length,
+    pub spread: Length,
+    pub inset: bool,
+}
+
+impl super::SpecifiedValue for Shadow {
+    type SpecifiedValue = SpecifiedShadow;
+}
+
+impl super::FromSpecified for Shadow {
+    fn from_specified(specified: &SpecifiedShadow, context: &CascadeContext) -> Self {
+        Shadow {
+            color: specified.color,
+            offset_x: super::FromSpecified::from_specified(&specified.offset_x, context),
+            offset_y: super::FromSpecified::from_specified(&specified.offset_y, context),
+            blur: super::FromSpecified::from_specified(&specified.blur, context),
+            spread: super::FromSpecified::from_specified(&specified.spread, context),
+            inset: specified.inset,
+        }
+    }
+}
+
+/// Parses a single shadow, whose components may come in any order.
+/// Text shadows have neither a spread distance nor the `inset` keyword.
+fn parse_shadow<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+    box_shadow: bool,
+) -> Result<SpecifiedShadow, PropertyParseError<'i>> {
+    let mut lengths = None;
+    let mut color = None;
+    let mut inset = false;
+    loop {
+        if box_shadow && !inset {
+            inset = parser
+                .try_parse(|parser| parser.expect_ident_matching("inset"))
+                .is_ok();
+            if inset {
+                continue;
+            }
+        }
+        if lengths.is_none() {
+            if let Ok(offset_x) = parser.try_parse(<SpecifiedLength as super::Parse>::parse) {
+                let offset_y = <SpecifiedLength as super::Parse>::parse(parser)?;
+                let blur = parser.try_parse(parse_non_negative_length).ok();
+                let spread = match blur {
+                    Some(_) if box_shadow => parser
+                        .try_parse(<SpecifiedLength as super::Parse>::parse)
+                        .ok(),
+                    _ => None,
+                };
+                lengths = Some((offset_x, offset_y, blur, spread));
+                continue;
+            }
+        }
+        if color.is_none() {
+            if let Ok(value) = parser.try_parse(Color::parse) {
+                color = Some(value);
+                continue;
+            }
+        }
+        break;
+    }
+    let zero = SpecifiedLength::Absolute(Length::zero());
+    match lengths {
+        Some((offset_x, offset_y, blur, spread)) => Ok(SpecifiedShadow {
+            color: color.unwrap_or(Color::CurrentColor),
+            offset_x,
+            offset_y,
+            blur: blur.unwrap_or(zero),
+            spread: spread.unwrap_or(zero),
+            inset,
+        }),
+        None => Err(parser.new_error_for_next_token()),
+    }
+}
+
+fn parse_non_negative_length<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+) -> Result<SpecifiedLength, PropertyParseError<'i>> {
+    let length = <SpecifiedLength as super::Parse>::parse(parser)?;
+    match length {
+        SpecifiedLength::Absolute(Length { px }) | SpecifiedLength::Em(px) if px < 0. => {
+            Err(parser.new_error_for_next_token())
+        }
+        _ => Ok(length),
+    }
+}
+
+/// Parses `none` or a comma-separated list of shadows.
+fn parse_shadows<'i, 't>(
+    parser: &mut Parser<'i, 't>,
+    box_shadow: bool,
+) -> Result<Vec<SpecifiedShadow>, PropertyParseError<'i>> {
+    if parser.try_parse(|parser| parser.expect_ident_matching("none")).is_ok() {
+        return Ok(vec![]);
+    }
+    parser.parse_comma_separated(|parser| parse_shadow(parser, box_shadow))
+}
+
+/// The value of `box-shadow`, the first shadow on top. `none` is an empty list.
+/// https://drafts.csswg.org/css-backgrounds/#box-shadow
+#[derive(Clone, Debug)]
+pub(in crate::style) struct SpecifiedBoxShadow(pub Vec<SpecifiedShadow>);
+
+#[derive(Clone, Debug)]
+pub(crate) struct BoxShadow(pub Vec<Shadow>);
+
+impl BoxShadow {
+    pub(in crate::style) const NONE: Self = BoxShadow(Vec::new());
+}
+
+impl super::Parse for SpecifiedBoxShadow {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        Ok(SpecifiedBoxShadow(parse_shadows(parser, true)?))
+    }
+}
+
+impl super::SpecifiedValue for BoxShadow {
+    type SpecifiedValue = SpecifiedBoxShadow;
+}
+
+impl super::FromSpecified for BoxShadow {
+    fn from_specified(specified: &SpecifiedBoxShadow, context: &CascadeContext) -> Self {
+        BoxShadow(
+            specified
+                .0
+                .iter()
+                .map(|shadow| super::FromSpecified::from_specified(shadow, context))
+                .collect(),
+        )
+    }
+}
+
+/// The value of `text-shadow`, the first shadow on top. `none` is an empty list.
+/// https://drafts.csswg.org/css-text-decor/#text-shadow-property
+#[derive(Clone, Debug)]
+pub(in crate::style) struct SpecifiedTextShadow(pub Vec<SpecifiedShadow>);
+
+#[derive(Clone, Debug)]
+pub(crate) struct TextShadow(pub Vec<Shadow>);
+
+impl TextShadow {
+    pub(in crate::style) const NONE: Self = TextShadow(Vec::new());
+}
+
+impl super::Parse for SpecifiedTextShadow {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        Ok(SpecifiedTextShadow(parse_shadows(parser, false)?))
+    }
+}
+
+impl super::SpecifiedValue for TextShadow {
+    type SpecifiedValue = SpecifiedTextShadow;
+}
+
+impl super::FromSpecified for TextShadow {
+    fn from_specified(specified: &SpecifiedTextShadow, context: &CascadeContext) -> Self {
+        TextShadow(
+            specified
+                .0
+                .iter()
+                .map(|shadow| super::FromSpecified::from_specified(shadow, context))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,57 @@
+use super::length::*;
+use crate::style::errors::PropertyParseError;
+use cssparser::Parser;
+
+/// https://drafts.csswg.org/css2/tables.html#width-layout
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum TableLayout {
+    Auto,
+    Fixed,
+}
+
+/// https://drafts.csswg.org/css2/tables.html#borders
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum BorderCollapse {
+    Separate,
+    Collapse,
+}
+
+/// https://drafts.csswg.org/css2/tables.html#caption-position
+#[derive(Copy, Clone, Debug, Eq, Parse, PartialEq, SpecifiedAsComputed)]
+pub(crate) enum CaptionSide {
+    Top,
+    Bottom,
+}
+
+/// https://drafts.csswg.org/css2/tables.html#separated-borders
+#[derive(Copy, Clone, Debug)]
+pub(in crate::style) struct SpecifiedBorderSpacing {
+    pub horizontal: SpecifiedLength,
+    pub vertical: SpecifiedLength,
+}
+
+#[derive(Copy, Clone, Debug, FromSpecified)]
+pub(crate) struct BorderSpacing {
+    pub horizontal: Length,
+    pub vertical: Length,
+}
+
+impl BorderSpacing {
+    pub(in crate::style) const ZERO: Self = BorderSpacing {
+        horizontal: Length { px: 0. },
+        vertical: Length { px: 0. },
+    };
+}
+
+impl super::Parse for SpecifiedBorderSpacing {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        let horizontal = <SpecifiedLength as super::Parse>::parse(parser)?;
+        let vertical = parser
+            .try_parse(<SpecifiedLength as super::Parse>::parse)
+            .unwrap_or(horizontal);
+        Ok(SpecifiedBorderSpacing {
+            horizontal,
+            vertical,
+        })
+    }
+}
@@ -93,4 +93,530 @@ mod tests {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_display() {
+        let inline_block = Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+            outside: DisplayOutside::Inline,
+            inside: DisplayInside::FlowRoot,
+            list_item: false,
+        });
+        assert_eq!(parse_value!("inline-block", Display), inline_block);
+        assert_eq!(parse_value!("inline flow-root", Display), inline_block);
+        assert_eq!(parse_value!("flow-root inline", Display), inline_block);
+        assert_eq!(parse_value!("inline", Display), Display::INITIAL);
+        assert_eq!(parse_value!("inline flow", Display), Display::INITIAL);
+        assert_eq!(parse_value!("none", Display), Display::None);
+        // an inside keyword on its own is block-level
+        assert_eq!(
+            parse_value!("flow", Display),
+            Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                outside: DisplayOutside::Block,
+                inside: DisplayInside::Flow,
+                list_item: false,
+            })
+        );
+        assert_eq!(
+            parse_value!("flow-root", Display),
+            Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                outside: DisplayOutside::Block,
+                inside: DisplayInside::FlowRoot,
+                list_item: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_list_style() {
+        let list_item = Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+            outside: DisplayOutside::Block,
+            inside: DisplayInside::Flow,
+            list_item: true,
+        });
+        assert_eq!(parse_value!("list-item", Display), list_item);
+        assert_eq!(parse_value!("block list-item", Display), list_item);
+        assert_eq!(parse_value!("list-item block flow", Display), list_item);
+        assert!(parse_value!("inline list-item", Display).is_list_item());
+        assert_eq!(
+            parse_value!("flow-root list-item", Display),
+            Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                outside: DisplayOutside::Block,
+                inside: DisplayInside::FlowRoot,
+                list_item: true,
+            })
+        );
+        // only flow layouts generate markers
+        for css in &["list-item flex", "table list-item", "list-item grid"] {
+            let mut input = ParserInput::new(css);
+            assert!(Display::parse(&mut Parser::new(&mut input)).is_err());
+        }
+
+        assert_eq!(parse_value!("upper-roman", ListStyleType), ListStyleType::UpperRoman);
+        assert_eq!(parse_value!("inside", ListStylePosition), ListStylePosition::Inside);
+        assert_eq!(parse_value!("none", ListStyleImage), ListStyleImage::None);
+        assert_eq!(
+            parse_value!("url(bullet.png)", ListStyleImage),
+            ListStyleImage::Url("bullet.png".to_string())
+        );
+
+        assert_eq!(ListStyleType::Disc.marker_text(1).unwrap(), "\u{2022} ");
+        assert_eq!(ListStyleType::Decimal.marker_text(12).unwrap(), "12. ");
+        assert_eq!(ListStyleType::DecimalLeadingZero.marker_text(7).unwrap(), "07. ");
+        assert_eq!(ListStyleType::LowerAlpha.marker_text(28).unwrap(), "ab. ");
+        assert_eq!(ListStyleType::UpperRoman.marker_text(1994).unwrap(), "MCMXCIV. ");
+        assert_eq!(ListStyleType::LowerRoman.marker_text(0).unwrap(), "0. ");
+        assert_eq!(ListStyleType::None.marker_text(1), None);
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(
+            parse_value!("inline-table", Display),
+            Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                outside: DisplayOutside::Inline,
+                inside: DisplayInside::Table,
+                list_item: false,
+            })
+        );
+        assert!(parse_value!("table", Display).is_table());
+        assert_eq!(
+            parse_value!("table-cell", Display),
+            Display::GeneratingBox(DisplayGeneratingBox::LayoutInternal(
+                DisplayLayoutInternal::TableCell
+            ))
+        );
+        assert_eq!(
+            parse_value!("table-header-group", Display).blockify(),
+            Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                outside: DisplayOutside::Block,
+                inside: DisplayInside::Flow,
+                list_item: false,
+            })
+        );
+
+        let spacing = parse_value!("2px", SpecifiedBorderSpacing);
+        assert_eq!(spacing.horizontal, spacing.vertical);
+        let spacing = parse_value!("1px 3px", SpecifiedBorderSpacing);
+        assert_eq!(spacing.horizontal, parse_value!("1px", SpecifiedLength));
+        assert_eq!(spacing.vertical, parse_value!("3px", SpecifiedLength));
+
+        assert_eq!(parse_value!("collapse", BorderCollapse), BorderCollapse::Collapse);
+        assert_eq!(parse_value!("bottom", CaptionSide), CaptionSide::Bottom);
+        assert_eq!(parse_value!("fixed", TableLayout), TableLayout::Fixed);
+    }
+
+    #[test]
+    fn test_flex() {
+        assert_eq!(
+            parse_value!("inline-flex", Display),
+            Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                outside: DisplayOutside::Inline,
+                inside: DisplayInside::Flex,
+                list_item: false,
+            })
+        );
+
+        let flex = parse_value!("none", Flex);
+        assert_eq!((flex.grow.value, flex.shrink.value), (0., 0.));
+        assert!(matches!(flex.basis, SpecifiedLengthOrPercentageOrAuto::Auto));
+        let flex = parse_value!("2", Flex);
+        assert_eq!((flex.grow.value, flex.shrink.value), (2., 1.));
+        assert!(matches!(
+            flex.basis,
+            SpecifiedLengthOrPercentageOrAuto::Length(SpecifiedLength::Absolute(Length { px }))
+                if px == 0.
+        ));
+        let flex = parse_value!("auto", Flex);
+        assert_eq!((flex.grow.value, flex.shrink.value), (1., 1.));
+        assert!(matches!(flex.basis, SpecifiedLengthOrPercentageOrAuto::Auto));
+        let flex = parse_value!("0 0 50%", Flex);
+        assert_eq!((flex.grow.value, flex.shrink.value), (0., 0.));
+        assert!(matches!(flex.basis, SpecifiedLengthOrPercentageOrAuto::Percentage(_)));
+
+        let flow = parse_value!("wrap column", FlexFlow);
+        assert_eq!(flow.direction, Some(FlexDirection::Column));
+        assert_eq!(flow.wrap, Some(FlexWrap::Wrap));
+
+        let gap = parse_value!("4px", Gap);
+        assert!(matches!(gap.column, SpecifiedLengthOrPercentageOrNormal::Length(_)));
+        let gap = parse_value!("4px 10%", Gap);
+        assert!(matches!(gap.row, SpecifiedLengthOrPercentageOrNormal::Length(_)));
+        assert!(matches!(gap.column, SpecifiedLengthOrPercentageOrNormal::Percentage(_)));
+
+        assert_eq!(
+            parse_value!("space-between", JustifyContent),
+            JustifyContent::SpaceBetween
+        );
+        assert_eq!(parse_value!("flex-end", AlignItems), AlignItems::FlexEnd);
+        assert_eq!(
+            parse_value!("auto", AlignSelf).resolve(AlignItems::Center),
+            AlignItems::Center
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        assert_eq!(
+            parse_value!("grid", Display),
+            Display::GeneratingBox(DisplayGeneratingBox::OutsideInside {
+                outside: DisplayOutside::Block,
+                inside: DisplayInside::Grid,
+                list_item: false,
+            })
+        );
+
+        let tracks = parse_value!("[start] 100px repeat(2, 1fr minmax(2em, auto)) [end]", SpecifiedTrackList);
+        assert_eq!(tracks.0.len(), 5);
+        assert!(matches!(
+            tracks.0[0].max,
+            SpecifiedTrackBreadth::Length(SpecifiedLengthOrPercentage::Length(_))
+        ));
+        assert!(matches!(tracks.0[1].min, SpecifiedTrackBreadth::Auto));
+        assert!(matches!(tracks.0[1].max, SpecifiedTrackBreadth::Flex(Number { value }) if value == 1.));
+        assert!(matches!(
+            tracks.0[2].min,
+            SpecifiedTrackBreadth::Length(SpecifiedLengthOrPercentage::Length(SpecifiedLength::Em(_)))
+        ));
+        assert!(matches!(tracks.0[2].max, SpecifiedTrackBreadth::Auto));
+        assert!(parse_value!("none", SpecifiedTrackList).0.is_empty());
+
+        assert_eq!(parse_value!("3", GridLine), GridLine::Line(3));
+        assert_eq!(parse_value!("-1", GridLine), GridLine::Line(-1));
+        assert_eq!(parse_value!("span 2", GridLine), GridLine::Span(2));
+        let range = parse_value!("1 / -1", GridLineRange);
+        assert_eq!((range.start, range.end), (GridLine::Line(1), GridLine::Line(-1)));
+        let range = parse_value!("span 3", GridLineRange);
+        assert_eq!((range.start, range.end), (GridLine::Span(3), GridLine::Auto));
+        let area = parse_value!("2 / 1 / span 2", GridArea);
+        assert_eq!(area.row_start, GridLine::Line(2));
+        assert_eq!(area.column_start, GridLine::Line(1));
+        assert_eq!(area.row_end, GridLine::Span(2));
+        assert_eq!(area.column_end, GridLine::Auto);
+
+        assert_eq!(parse_value!("column", GridAutoFlow), GridAutoFlow::Column);
+        assert_eq!(parse_value!("end", AlignSelf), AlignSelf::End);
+    }
+
+    #[test]
+    fn test_multicol() {
+        assert_eq!(parse_value!("3", ColumnCount), ColumnCount::Integer(3));
+        assert_eq!(parse_value!("auto", ColumnCount), ColumnCount::Auto);
+
+        let columns = parse_value!("12em 2", Columns);
+        assert!(matches!(
+            columns.width,
+            Some(SpecifiedLengthOrPercentageOrAuto::Length(SpecifiedLength::Em(_)))
+        ));
+        assert_eq!(columns.count, Some(ColumnCount::Integer(2)));
+        let columns = parse_value!("3", Columns);
+        assert!(columns.width.is_none());
+        assert_eq!(columns.count, Some(ColumnCount::Integer(3)));
+
+        assert!(matches!(
+            parse_value!("normal", SpecifiedLengthOrPercentageOrNormal),
+            SpecifiedLengthOrPercentageOrNormal::Normal
+        ));
+    }
+
+    #[test]
+    fn test_min_max_sizes() {
+        assert!(matches!(
+            parse_value!("none", SpecifiedLengthOrPercentageOrNone),
+            SpecifiedLengthOrPercentageOrNone::None
+        ));
+        assert!(matches!(
+            parse_value!("3in", SpecifiedLengthOrPercentageOrNone),
+            SpecifiedLengthOrPercentageOrNone::Length(SpecifiedLength::Absolute(Length { px }))
+                if px == 288.
+        ));
+        assert!(matches!(
+            parse_value!("50%", SpecifiedLengthOrPercentageOrNone),
+            SpecifiedLengthOrPercentageOrNone::Percentage(_)
+        ));
+        assert_eq!(parse_value!("border-box", BoxSizing), BoxSizing::BorderBox);
+        assert_eq!(parse_value!("content-box", BoxSizing), BoxSizing::ContentBox);
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(parse_value!("visible", Overflow), Overflow::Visible);
+        assert_eq!(parse_value!("hidden", Overflow), Overflow::Hidden);
+        assert_eq!(parse_value!("clip", Overflow), Overflow::Clip);
+    }
+
+    #[test]
+    fn test_z_index() {
+        assert_eq!(parse_value!("auto", ZIndex), ZIndex::Auto);
+        assert_eq!(parse_value!("3", ZIndex), ZIndex::Integer(3));
+        assert_eq!(parse_value!("-1", ZIndex), ZIndex::Integer(-1));
+    }
+
+    #[test]
+    fn test_border_radius() {
+        let px = |radius: SpecifiedLengthOrPercentage| match radius {
+            SpecifiedLengthOrPercentage::Length(SpecifiedLength::Absolute(Length { px })) => px,
+            _ => panic!("expected an absolute length"),
+        };
+
+        let corner = parse_value!("4px", SpecifiedBorderCornerRadius);
+        assert_eq!((px(corner.horizontal), px(corner.vertical)), (4., 4.));
+        let corner = parse_value!("4px 2px", SpecifiedBorderCornerRadius);
+        assert_eq!((px(corner.horizontal), px(corner.vertical)), (4., 2.));
+
+        let radius = parse_value!("1px 2px 3px", BorderRadius);
+        assert_eq!(px(radius.top_left.horizontal), 1.);
+        assert_eq!(px(radius.top_right.horizontal), 2.);
+        assert_eq!(px(radius.bottom_right.horizontal), 3.);
+        assert_eq!(px(radius.bottom_left.horizontal), 2.);
+        assert_eq!(px(radius.bottom_left.vertical), 2.);
+
+        let radius = parse_value!("10px / 5px 6px", BorderRadius);
+        assert_eq!(px(radius.top_left.horizontal), 10.);
+        assert_eq!(px(radius.top_left.vertical), 5.);
+        assert_eq!(px(radius.top_right.vertical), 6.);
+        assert_eq!(px(radius.bottom_right.vertical), 5.);
+        assert!(matches!(
+            parse_value!("50%", BorderRadius).top_left.vertical,
+            SpecifiedLengthOrPercentage::Percentage(_)
+        ));
+    }
+
+    #[test]
+    fn test_line_style() {
+        assert_eq!(parse_value!("none", LineStyle), LineStyle::None);
+        assert_eq!(parse_value!("hidden", LineStyle), LineStyle::Hidden);
+        assert_eq!(parse_value!("solid", LineStyle), LineStyle::Solid);
+        assert_eq!(parse_value!("double", LineStyle), LineStyle::Double);
+        assert_eq!(parse_value!("groove", LineStyle), LineStyle::Groove);
+        assert_eq!(parse_value!("ridge", LineStyle), LineStyle::Ridge);
+        assert_eq!(parse_value!("inset", LineStyle), LineStyle::Inset);
+        assert_eq!(parse_value!("outset", LineStyle), LineStyle::Outset);
+    }
+
+    #[test]
+    fn test_shadow() {
+        let px = |length: SpecifiedLength| match length {
+            SpecifiedLength::Absolute(Length { px }) => px,
+            _ => panic!("expected an absolute length"),
+        };
+
+        assert!(parse_value!("none", SpecifiedBoxShadow).0.is_empty());
+        let shadows = parse_value!("2px 3px 4px -1px red, inset 0 0 5px", SpecifiedBoxShadow).0;
+        assert_eq!(shadows.len(), 2);
+        let shadow = &shadows[0];
+        assert_eq!(
+            (px(shadow.offset_x), px(shadow.offset_y), px(shadow.blur), px(shadow.spread)),
+            (2., 3., 4., -1.)
+        );
+        assert_eq!(shadow.color, Color::RGBA(RGBA::new(255, 0, 0, 255)));
+        assert!(!shadow.inset);
+        let shadow = &shadows[1];
+        assert_eq!((px(shadow.blur), px(shadow.spread)), (5., 0.));
+        assert_eq!(shadow.color, Color::CurrentColor);
+        assert!(shadow.inset);
+        let shadow = &parse_value!("blue 1px 1px inset", SpecifiedBoxShadow).0[0];
+        assert!(shadow.inset);
+        assert_eq!(px(shadow.blur), 0.);
+
+        let shadows = parse_value!("white 0 0 2px, 1px 1px", SpecifiedTextShadow).0;
+        assert_eq!(shadows.len(), 2);
+        assert_eq!(shadows[0].color, Color::RGBA(RGBA::new(255, 255, 255, 255)));
+        assert_eq!(px(shadows[0].blur), 2.);
+        assert_eq!(px(shadows[1].offset_x), 1.);
+    }
+
+    #[test]
+    fn test_background() {
+        let offset = |component: SpecifiedPositionComponent| match component.offset {
+            SpecifiedLengthOrPercentage::Length(SpecifiedLength::Absolute(Length { px })) => px,
+            SpecifiedLengthOrPercentage::Percentage(Percentage { unit_value }) => unit_value,
+            _ => panic!("expected an absolute length or a percentage"),
+        };
+
+        let position = parse_value!("right 10px bottom", SpecifiedPositionValue);
+        assert_eq!((position.x.edge, offset(position.x)), (PositionEdge::End, 10.));
+        assert_eq!((position.y.edge, offset(position.y)), (PositionEdge::End, 0.));
+        let position = parse_value!("top left", SpecifiedPositionValue);
+        assert_eq!((position.x.edge, offset(position.x)), (PositionEdge::Start, 0.));
+        assert_eq!((position.y.edge, offset(position.y)), (PositionEdge::Start, 0.));
+        let positions = parse_value!("25% 75%, center", Layers<SpecifiedPositionValue>).0;
+        assert_eq!((offset(positions[0].x), offset(positions[0].y)), (0.25, 0.75));
+        assert_eq!((offset(positions[1].x), offset(positions[1].y)), (0.5, 0.5));
+
+        let repeat = parse_value!("repeat-x", BackgroundRepeat);
+        assert_eq!((repeat.x, repeat.y), (RepeatStyle::Repeat, RepeatStyle::NoRepeat));
+        let repeat = parse_value!("space round", BackgroundRepeat);
+        assert_eq!((repeat.x, repeat.y), (RepeatStyle::Space, RepeatStyle::Round));
+        assert!(matches!(parse_value!("cover", SpecifiedBackgroundSize), SpecifiedBackgroundSize::Cover));
+        assert!(matches!(
+            parse_value!("auto 50%", SpecifiedBackgroundSize),
+            SpecifiedBackgroundSize::Explicit(
+                SpecifiedLengthOrPercentageOrAuto::Auto,
+                SpecifiedLengthOrPercentageOrAuto::Percentage(_)
+            )
+        ));
+
+        match parse_value!("url(tile.png)", SpecifiedImageValue) {
+            SpecifiedImageValue::Url(url) => assert_eq!(url, "tile.png"),
+            _ => panic!("expected a url"),
+        }
+        match parse_value!("linear-gradient(to top right, red, blue 40% 60%)", SpecifiedImageValue) {
+            SpecifiedImageValue::LinearGradient(gradient) => {
+                assert_eq!(gradient.direction, GradientDirection::Corner { right: true, bottom: false });
+                assert_eq!(gradient.stops.len(), 3);
+                assert!(gradient.stops[0].position.is_none());
+            }
+            _ => panic!("expected a linear gradient"),
+        }
+        match parse_value!("linear-gradient(0.25turn, red, blue)", SpecifiedImageValue) {
+            SpecifiedImageValue::LinearGradient(gradient) => {
+                assert_eq!(gradient.direction, GradientDirection::Angle(90.));
+            }
+            _ => panic!("expected a linear gradient"),
+        }
+        match parse_value!("radial-gradient(circle at left 10px top, white, black)", SpecifiedImageValue) {
+            SpecifiedImageValue::RadialGradient(gradient) => {
+                assert_eq!(gradient.shape, RadialShape::Circle);
+                assert!(matches!(gradient.extent, SpecifiedRadialExtent::FarthestCorner));
+                assert_eq!(offset(gradient.center.x), 10.);
+                assert_eq!(gradient.center.y.edge, PositionEdge::Start);
+            }
+            _ => panic!("expected a radial gradient"),
+        }
+        match parse_value!("radial-gradient(20px, white, black)", SpecifiedImageValue) {
+            SpecifiedImageValue::RadialGradient(gradient) => {
+                assert_eq!(gradient.shape, RadialShape::Circle);
+                assert_eq!(offset(gradient.center.x), 0.5);
+            }
+            _ => panic!("expected a radial gradient"),
+        }
+        match parse_value!("radial-gradient(circle closest-side, red, blue)", SpecifiedImageValue) {
+            SpecifiedImageValue::RadialGradient(gradient) => {
+                assert_eq!(gradient.shape, RadialShape::Circle);
+                assert!(matches!(gradient.extent, SpecifiedRadialExtent::ClosestSide));
+            }
+            _ => panic!("expected a radial gradient"),
+        }
+        match parse_value!("radial-gradient(circle farthest-corner at 10px 10px, red, blue)", SpecifiedImageValue) {
+            SpecifiedImageValue::RadialGradient(gradient) => {
+                assert_eq!(gradient.shape, RadialShape::Circle);
+                assert!(matches!(gradient.extent, SpecifiedRadialExtent::FarthestCorner));
+                assert_eq!(offset(gradient.center.x), 10.);
+            }
+            _ => panic!("expected a radial gradient"),
+        }
+        match parse_value!("radial-gradient(ellipse farthest-corner, red, blue)", SpecifiedImageValue) {
+            SpecifiedImageValue::RadialGradient(gradient) => {
+                assert_eq!(gradient.shape, RadialShape::Ellipse);
+                assert!(matches!(gradient.extent, SpecifiedRadialExtent::FarthestCorner));
+            }
+            _ => panic!("expected a radial gradient"),
+        }
+        let mut input = ParserInput::new("radial-gradient(circle 10px 20px, red, blue)");
+        assert!(SpecifiedImageValue::parse(&mut Parser::new(&mut input)).is_err());
+
+        let background = parse_value!(
+            "url(a.png) center / cover no-repeat, linear-gradient(red, blue) padding-box green",
+            Background
+        );
+        let images = background.image.unwrap().0;
+        assert!(matches!(images[0], SpecifiedImageValue::Url(_)));
+        assert!(matches!(images[1], SpecifiedImageValue::LinearGradient(_)));
+        let sizes = background.size.unwrap().0;
+        assert!(matches!(sizes[0], SpecifiedBackgroundSize::Cover));
+        let repeats = background.repeat.unwrap().0;
+        assert_eq!(repeats[0].x, RepeatStyle::NoRepeat);
+        assert_eq!(repeats[1].x, RepeatStyle::Repeat);
+        let clips = background.clip.unwrap().0;
+        assert_eq!((clips[0], clips[1]), (BackgroundBox::BorderBox, BackgroundBox::PaddingBox));
+        assert_eq!(background.color, Some(Color::RGBA(RGBA::new(0, 128, 0, 255))));
+    }
+
+    #[test]
+    fn test_opacity() {
+        assert_eq!(parse_value!("0.5", Opacity), Opacity(0.5));
+        assert_eq!(parse_value!("25%", Opacity), Opacity(0.25));
+        assert_eq!(parse_value!("1.5", Opacity), Opacity(1.));
+        assert_eq!(parse_value!("-1", Opacity), Opacity(0.));
+        assert_eq!(parse_value!("hidden", Visibility), Visibility::Hidden);
+        assert_eq!(parse_value!("collapse", Visibility), Visibility::Collapse);
+    }
+
+    #[test]
+    fn test_transform() {
+        let transform = parse_value!("translate(10px, 50%) rotate(45deg) scale(2)", SpecifiedTransform);
+        assert_eq!(transform.0.len(), 3);
+        match transform.0[0] {
+            SpecifiedTransformFunction::Translate(
+                SpecifiedLengthOrPercentage::Length(SpecifiedLength::Absolute(x)),
+                SpecifiedLengthOrPercentage::Percentage(y),
+            ) => assert_eq!((x.px, y.unit_value), (10., 0.5)),
+            ref function => panic!("unexpected {:?}", function),
+        }
+        assert!(matches!(transform.0[1], SpecifiedTransformFunction::Rotate(angle) if angle == 45.));
+        assert!(matches!(transform.0[2], SpecifiedTransformFunction::Scale(x, y) if x == 2. && y == 2.));
+        assert!(parse_value!("none", SpecifiedTransform).0.is_empty());
+        assert!(matches!(
+            parse_value!("skewX(0.25turn)", SpecifiedTransform).0[0],
+            SpecifiedTransformFunction::Skew(x, y) if x == 90. && y == 0.
+        ));
+        assert!(matches!(
+            parse_value!("matrix(1, 0, 0, 1, 5, 6)", SpecifiedTransform).0[0],
+            SpecifiedTransformFunction::Matrix([1., 0., 0., 1., 5., 6.])
+        ));
+        let origin = parse_value!("left top", SpecifiedPositionValue);
+        assert_eq!((origin.x.edge, origin.y.edge), (PositionEdge::Start, PositionEdge::Start));
+    }
+
+    #[test]
+    fn test_content() {
+        assert_eq!(parse_value!("normal", Content), Content::Normal);
+        assert_eq!(parse_value!("none", Content), Content::None);
+        assert_eq!(
+            parse_value!(r#""Note: " attr(title) counter(item, upper-roman) open-quote"#, Content),
+            Content::Items(vec![
+                ContentItem::String("Note: ".into()),
+                ContentItem::Attr("title".into()),
+                ContentItem::Counter("item".into(), ListStyleType::UpperRoman),
+                ContentItem::OpenQuote,
+            ])
+        );
+        assert_eq!(
+            parse_value!(r#"counters(section, ".")"#, Content),
+            Content::Items(vec![ContentItem::Counters(
+                "section".into(),
+                ".".into(),
+                ListStyleType::Decimal
+            )])
+        );
+        assert_eq!(ListStyleType::LowerAlpha.counter_text(2).unwrap(), "b");
+        let quotes = parse_value!(r#""<" ">" "(" ")""#, Quotes);
+        assert_eq!(quotes.at_depth(0), ("<", ">"));
+        assert_eq!(quotes.at_depth(5), ("(", ")"));
+        assert_eq!(parse_value!("auto", Quotes).at_depth(0), ("\u{201C}", "\u{201D}"));
+    }
+
+    #[test]
+    fn test_initial_letter() {
+        assert_eq!(parse_value!("normal", InitialLetter), InitialLetter::Normal);
+        assert_eq!(parse_value!("3", InitialLetter), InitialLetter::Lines { size: 3., sink: 3 });
+        assert_eq!(parse_value!("2.5", InitialLetter), InitialLetter::Lines { size: 2.5, sink: 2 });
+        assert_eq!(parse_value!("3 1", InitialLetter), InitialLetter::Lines { size: 3., sink: 1 });
+        let mut input = ParserInput::new("0.5");
+        assert!(InitialLetter::parse(&mut Parser::new(&mut input)).is_err());
+    }
+
+    #[test]
+    fn test_counters() {
+        assert_eq!(parse_value!("none", CounterReset), CounterReset(vec![]));
+        assert_eq!(
+            parse_value!("section chapter 3", CounterReset),
+            CounterReset(vec![("section".into(), 0), ("chapter".into(), 3)])
+        );
+        assert_eq!(
+            parse_value!("panel list-item -2", CounterIncrement),
+            CounterIncrement(vec![("panel".into(), 1), ("list-item".into(), -2)])
+        );
+        assert_eq!(parse_value!("item 5", CounterSet), CounterSet(vec![("item".into(), 5)]));
+    }
+}
@@ -0,0 +1,145 @@
+use super::image::parse_angle;
+use super::length::*;
+use super::CascadeContext;
+use crate::style::errors::PropertyParseError;
+use cssparser::{Parser, Token};
+
+/// A 2D transform function. Angles are in degrees, clockwise.
+/// https://drafts.csswg.org/css-transforms/#two-d-transform-functions
+#[derive(Copy, Clone, Debug)]
+pub(in crate::style) enum SpecifiedTransformFunction {
+    Matrix([f32; 6]),
+    Translate(SpecifiedLengthOrPercentage, SpecifiedLengthOrPercentage),
+    Scale(f32, f32),
+    Rotate(f32),
+    Skew(f32, f32),
+}
+
+/// Percentages of translations refer to the size of the border box.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum TransformFunction {
+    Matrix([f32; 6]),
+    Translate(LengthOrPercentage, LengthOrPercentage),
+    Scale(f32, f32),
+    Rotate(f32),
+    Skew(f32, f32),
+}
+
+impl super::SpecifiedValue for TransformFunction {
+    type SpecifiedValue = SpecifiedTransformFunction;
+}
+
+impl super::FromSpecified for TransformFunction {
+    fn from_specified(specified: &SpecifiedTransformFunction, context: &CascadeContext) -> Self {
+        match *specified {
+            SpecifiedTransformFunction::Matrix(matrix) => TransformFunction::Matrix(matrix),
+            SpecifiedTransformFunction::Translate(x, y) => TransformFunction::Translate(
+                super::FromSpecified::from_specified(&x, context),
+                super::FromSpecified::from_specified(&y, context),
+            ),
+            SpecifiedTransformFunction::Scale(x, y) => TransformFunction::Scale(x, y),
+            SpecifiedTransformFunction::Rotate(angle) => TransformFunction::Rotate(angle),
+            SpecifiedTransformFunction::Skew(x, y) => TransformFunction::Skew(x, y),
+        }
+    }
+}
+
+impl super::Parse for SpecifiedTransformFunction {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        let function = parser.expect_function()?.clone();
+        parser.parse_nested_block(|parser| {
+            let length = <SpecifiedLengthOrPercentage as super::Parse>::parse;
+            let zero = SpecifiedLengthOrPercentage::Length(SpecifiedLength::Absolute(Length::zero()));
+            // the optional second argument of a function
+            fn second<'i, 't, T>(
+                parser: &mut Parser<'i, 't>,
+                parse: impl FnOnce(&mut Parser<'i, 't>) -> Result<T, PropertyParseError<'i>>,
+            ) -> Result<Option<T>, PropertyParseError<'i>> {
+                if parser.try_parse(|parser| parser.expect_comma()).is_err() {
+                    return Ok(None);
+                }
+                Ok(Some(parse(parser)?))
+            }
+            let number = |parser: &mut Parser<'i, '_>| -> Result<f32, PropertyParseError<'i>> {
+                Ok(parser.expect_number()?)
+            };
+            match_ignore_ascii_case!(&function,
+                "matrix" => {
+                    let mut matrix = [0.; 6];
+                    for (i, value) in matrix.iter_mut().enumerate() {
+                        if i > 0 {
+                            parser.expect_comma()?;
+                        }
+                        *value = parser.expect_number()?;
+                    }
+                    Ok(SpecifiedTransformFunction::Matrix(matrix))
+                },
+                "translate" => {
+                    let x = length(parser)?;
+                    let y = second(parser, length)?.unwrap_or(zero);
+                    Ok(SpecifiedTransformFunction::Translate(x, y))
+                },
+                "translatex" => Ok(SpecifiedTransformFunction::Translate(length(parser)?, zero)),
+                "translatey" => Ok(SpecifiedTransformFunction::Translate(zero, length(parser)?)),
+                "scale" => {
+                    let x = parser.expect_number()?;
+                    let y = second(parser, number)?.unwrap_or(x);
+                    Ok(SpecifiedTransformFunction::Scale(x, y))
+                },
+                "scalex" => Ok(SpecifiedTransformFunction::Scale(parser.expect_number()?, 1.)),
+                "scaley" => Ok(SpecifiedTransformFunction::Scale(1., parser.expect_number()?)),
+                "rotate" => Ok(SpecifiedTransformFunction::Rotate(parse_angle(parser)?)),
+                "skew" => {
+                    let x = parse_angle(parser)?;
+                    let y = second(parser, parse_angle)?.unwrap_or(0.);
+                    Ok(SpecifiedTransformFunction::Skew(x, y))
+                },
+                "skewx" => Ok(SpecifiedTransformFunction::Skew(parse_angle(parser)?, 0.)),
+                "skewy" => Ok(SpecifiedTransformFunction::Skew(0., parse_angle(parser)?)),
+                _ => Err(parser.new_unexpected_token_error(Token::Function(function.clone()))),
+            )
+        })
+    }
+}
+
+/// The value of `transform`, the functions applied from right to left.
+/// `none` is an empty list.
+/// https://drafts.csswg.org/css-transforms/#transform-property
+#[derive(Clone, Debug)]
+pub(in crate::style) struct SpecifiedTransform(pub Vec<SpecifiedTransformFunction>);
+
+#[derive(Clone, Debug)]
+pub(crate) struct Transform(pub Vec<TransformFunction>);
+
+impl Transform {
+    pub(in crate::style) const NONE: Self = Transform(Vec::new());
+}
+
+impl super::Parse for SpecifiedTransform {
+    fn parse<'i, 't>(parser: &mut Parser<'i, 't>) -> Result<Self, PropertyParseError<'i>> {
+        if parser.try_parse(|parser| parser.expect_ident_matching("none")).is_ok() {
+            return Ok(SpecifiedTransform(vec![]));
+        }
+        let mut functions = vec![<SpecifiedTransformFunction as super::Parse>::parse(parser)?];
+        while let Ok(function) = parser.try_parse(<SpecifiedTransformFunction as super::Parse>::parse) {
+            functions.push(function);
+        }
+        Ok(SpecifiedTransform(functions))
+    }
+}
+
+impl super::SpecifiedValue for Transform {
+    type SpecifiedValue = SpecifiedTransform;
+}
+
+impl super::FromSpecified for Transform {
+    fn from_specified(specified: &SpecifiedTransform, context: &CascadeContext) -> Self {
+        Transform(
+            specified
+                .0
+                .iter()
+                .map(|function| super::FromSpecified::from_specified(function, context))
+                .collect(),
+        )
+    }
+}
@@ -3,7 +3,7 @@
 use crate::graphics_engine::renderer::*;
 use crate::utils::c_helper::*;
 use crate::markdown::md_to_html;
-use crate::paint::render_html;
+use crate::paint::{html_content_sizes, render_html};
 use crate::primitives::*;
 
 use std::panic;
@@ -35,3 +35,37 @@ pub extern "C" fn sinab_test_renderer(
         println!("Rust error: {:?}", result);
     }
 }
+
+/// Computes the min-content and max-content widths of the rendered text, in px.
+/// Rendering at a width between the two wraps lines only where necessary.
+///
+/// # Safety
+///
+/// `min_width_px` and `max_width_px` must be null or valid for writes.
+/// Nothing is written if either is null.
+#[no_mangle]
+pub unsafe extern "C" fn sinab_content_sizes(
+    text: *const c_char,
+    css: *const c_char,
+    min_width_px: *mut c_double,
+    max_width_px: *mut c_double ) {
+    if min_width_px.is_null() || max_width_px.is_null() {
+        return;
+    }
+    let text_input = match cstring_to_str(text) {
+        Ok(s) => md_to_html(s),
+        Err(..) => "".to_string(),
+    };
+    let css_input = match cstring_to_str(css) {
+        Ok(s) => s,
+        Err(..) => "",
+    };
+
+    let result = panic::catch_unwind(move || {
+        html_content_sizes(text_input.as_str(), css_input)
+    });
+
+    let (min_width, max_width) = result.unwrap_or((0., 0.));
+    *min_width_px = min_width as c_double;
+    *max_width_px = max_width as c_double;
+}